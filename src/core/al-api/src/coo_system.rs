@@ -2,6 +2,14 @@ use cgmath::Matrix4;
 pub trait CooBaseFloat: Sized + 'static {
     const GALACTIC_TO_J2000: &'static Matrix4<Self>;
     const J2000_TO_GALACTIC: &'static Matrix4<Self>;
+    const ECLIPTIC_TO_J2000: &'static Matrix4<Self>;
+    const J2000_TO_ECLIPTIC: &'static Matrix4<Self>;
+    const SUPERGALACTIC_TO_J2000: &'static Matrix4<Self>;
+    const J2000_TO_SUPERGALACTIC: &'static Matrix4<Self>;
+    const FK4B1950_TO_J2000: &'static Matrix4<Self>;
+    const J2000_TO_FK4B1950: &'static Matrix4<Self>;
+    const FK5J2000_TO_J2000: &'static Matrix4<Self>;
+    const J2000_TO_FK5J2000: &'static Matrix4<Self>;
     const ID: &'static Matrix4<Self>;
 }
 
@@ -44,6 +52,159 @@ impl CooBaseFloat for f32 {
         1.0,
     );
 
+
+    const J2000_TO_ECLIPTIC: &'static Matrix4<Self> = &Matrix4::new(
+        0.91748214,
+        -0.39777697,
+        0.0,
+        0.0,
+        0.39777697,
+        0.91748214,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const ECLIPTIC_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        0.91748214,
+        0.39777697,
+        0.0,
+        0.0,
+        -0.39777697,
+        0.91748214,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_SUPERGALACTIC: &'static Matrix4<Self> = &Matrix4::new(
+        -0.095727141,
+        -0.93504565,
+        0.34135896,
+        0.0,
+        0.42878511,
+        0.27075058,
+        0.86188018,
+        0.0,
+        -0.89832046,
+        0.22887497,
+        0.37501548,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const SUPERGALACTIC_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        -0.095727141,
+        0.42878511,
+        -0.89832046,
+        0.0,
+        -0.93504565,
+        0.27075058,
+        0.22887497,
+        0.0,
+        0.34135896,
+        0.86188018,
+        0.37501548,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_FK4B1950: &'static Matrix4<Self> = &Matrix4::new(
+        0.99993748,
+        -2.71765000e-05,
+        0.011182061,
+        0.0,
+        -2.71474000e-05,
+        0.9999882,
+        0.0048579479,
+        0.0,
+        -0.011182061,
+        -0.0048579477,
+        0.99992568,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const FK4B1950_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        0.99993748,
+        -2.71474000e-05,
+        -0.011182061,
+        0.0,
+        -2.71765000e-05,
+        0.9999882,
+        -0.0048579477,
+        0.0,
+        0.011182061,
+        0.0048579479,
+        0.99992568,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_FK5J2000: &'static Matrix4<Self> = &Matrix4::new(
+        1.0,
+        9.64779225e-08,
+        1.11022333e-07,
+        0.0,
+        -9.64779225e-08,
+        1.0,
+        4.41180450e-08,
+        0.0,
+        -1.11022333e-07,
+        -4.41180450e-08,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const FK5J2000_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        1.0,
+        -9.64779225e-08,
+        -1.11022333e-07,
+        0.0,
+        9.64779225e-08,
+        1.0,
+        -4.41180450e-08,
+        0.0,
+        1.11022333e-07,
+        4.41180450e-08,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
     const ID: &'static Matrix4<Self> = &Matrix4::new(
         1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
     );
@@ -87,33 +248,253 @@ impl CooBaseFloat for f64 {
         1.0,
     );
 
+    const J2000_TO_ECLIPTIC: &'static Matrix4<Self> = &Matrix4::new(
+        0.9174821430652418,
+        -0.397776969112606,
+        0.0,
+        0.0,
+        0.397776969112606,
+        0.9174821430652418,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const ECLIPTIC_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        0.9174821430652418,
+        0.397776969112606,
+        0.0,
+        0.0,
+        -0.397776969112606,
+        0.9174821430652418,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_SUPERGALACTIC: &'static Matrix4<Self> = &Matrix4::new(
+        -0.09572714072498734,
+        -0.9350456532745491,
+        0.34135896182902536,
+        0.0,
+        0.4287851134887344,
+        0.2707505787008762,
+        0.861880183426672,
+        0.0,
+        -0.8983204556165199,
+        0.22887496679521818,
+        0.3750154778078048,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const SUPERGALACTIC_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        -0.09572714072498734,
+        0.4287851134887344,
+        -0.8983204556165199,
+        0.0,
+        -0.9350456532745491,
+        0.2707505787008762,
+        0.22887496679521818,
+        0.0,
+        0.34135896182902536,
+        0.861880183426672,
+        0.3750154778078048,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_FK4B1950: &'static Matrix4<Self> = &Matrix4::new(
+        0.9999374784,
+        -2.71765e-05,
+        0.011182061,
+        0.0,
+        -2.71474e-05,
+        0.9999881997,
+        0.0048579479,
+        0.0,
+        -0.0111820611,
+        -0.0048579477,
+        0.9999256782,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const FK4B1950_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        0.9999374784,
+        -2.71474e-05,
+        -0.0111820611,
+        0.0,
+        -2.71765e-05,
+        0.9999881997,
+        -0.0048579477,
+        0.0,
+        0.011182061,
+        0.0048579479,
+        0.9999256782,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const J2000_TO_FK5J2000: &'static Matrix4<Self> = &Matrix4::new(
+        1.0,
+        9.647792254079764e-08,
+        1.1102233297408372e-07,
+        0.0,
+        -9.647792254079764e-08,
+        1.0,
+        4.411804498096777e-08,
+        0.0,
+        -1.1102233297408372e-07,
+        -4.411804498096777e-08,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
+    const FK5J2000_TO_J2000: &'static Matrix4<Self> = &Matrix4::new(
+        1.0,
+        -9.647792254079764e-08,
+        -1.1102233297408372e-07,
+        0.0,
+        9.647792254079764e-08,
+        1.0,
+        -4.411804498096777e-08,
+        0.0,
+        1.1102233297408372e-07,
+        4.411804498096777e-08,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+
     const ID: &'static Matrix4<Self> = &Matrix4::new(
         1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
     );
 }
 
-use cgmath::BaseFloat;
+use crate::Abort;
+use cgmath::{BaseFloat, Matrix};
 use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Hash)]
 pub enum CooSystem {
+    /// International Celestial Reference System
     ICRS = 0,
+    /// Galactic (IAU 1958)
     GAL = 1,
+    /// Ecliptic, mean equinox and obliquity of J2000 (IAU 2006)
+    ECL = 2,
+    /// Supergalactic (de Vaucouleurs)
+    SGAL = 3,
+    /// FK5, equinox J2000
+    FK5J2000 = 4,
+    /// FK4, equinox B1950. The E-terms of aberration are not taken into account
+    FK4B1950 = 5,
+    /// Horizontal frame of a ground based observer. The latitude is the altitude and
+    /// the longitude is the opposite of the azimuth (from the North towards the East)
+    /// so that the frame is right-handed as the others.
+    /// Its orientation depends on the observer location and on the time so
+    /// its rotation from ICRS must be given to the conversions
+    ALTAZ = 6,
 }
 
-pub const NUM_COOSYSTEM: usize = 2;
+pub const NUM_COOSYSTEM: usize = 7;
 
 impl CooSystem {
+    /// Matrix converting a vector expressed in this frame into `coo_system`.
+    /// Conversions between two non ICRS frames go through ICRS
+    ///
+    /// `icrs_to_altaz` is the rotation from ICRS to the horizontal frame of the observer.
+    /// It is only used when one of the two frames is [`CooSystem::ALTAZ`]
     #[inline]
-    pub fn to<S>(&self, coo_system: Self) -> &Matrix4<S>
+    pub fn to<S>(&self, coo_system: Self, icrs_to_altaz: &Matrix4<f64>) -> Matrix4<S>
     where
         S: BaseFloat + CooBaseFloat,
     {
         match (self, coo_system) {
-            (CooSystem::GAL, CooSystem::ICRS) => S::GALACTIC_TO_J2000,
-            (CooSystem::ICRS, CooSystem::GAL) => S::J2000_TO_GALACTIC,
-            (_, _) => S::ID,
+            (c1, c2) if *c1 == c2 => *S::ID,
+            (CooSystem::ICRS, c2) => c2.mat_from_icrs::<S>(icrs_to_altaz),
+            (c1, CooSystem::ICRS) => c1.mat_to_icrs::<S>(icrs_to_altaz),
+            // Go through ICRS
+            (c1, c2) => c2.mat_from_icrs::<S>(icrs_to_altaz) * c1.mat_to_icrs::<S>(icrs_to_altaz),
+        }
+    }
+
+    /// Matrix converting a vector given in this frame into ICRS
+    #[inline]
+    fn mat_to_icrs<S>(&self, icrs_to_altaz: &Matrix4<f64>) -> Matrix4<S>
+    where
+        S: BaseFloat + CooBaseFloat,
+    {
+        match self {
+            CooSystem::ICRS => *S::ID,
+            CooSystem::GAL => *S::GALACTIC_TO_J2000,
+            CooSystem::ECL => *S::ECLIPTIC_TO_J2000,
+            CooSystem::SGAL => *S::SUPERGALACTIC_TO_J2000,
+            CooSystem::FK5J2000 => *S::FK5J2000_TO_J2000,
+            CooSystem::FK4B1950 => *S::FK4B1950_TO_J2000,
+            // The inverse of a rotation is its transpose
+            CooSystem::ALTAZ => icrs_to_altaz.transpose().cast::<S>().unwrap_abort(),
+        }
+    }
+
+    /// Matrix converting an ICRS vector into this frame
+    #[inline]
+    fn mat_from_icrs<S>(&self, icrs_to_altaz: &Matrix4<f64>) -> Matrix4<S>
+    where
+        S: BaseFloat + CooBaseFloat,
+    {
+        match self {
+            CooSystem::ICRS => *S::ID,
+            CooSystem::GAL => *S::J2000_TO_GALACTIC,
+            CooSystem::ECL => *S::J2000_TO_ECLIPTIC,
+            CooSystem::SGAL => *S::J2000_TO_SUPERGALACTIC,
+            CooSystem::FK5J2000 => *S::J2000_TO_FK5J2000,
+            CooSystem::FK4B1950 => *S::J2000_TO_FK4B1950,
+            CooSystem::ALTAZ => icrs_to_altaz.cast::<S>().unwrap_abort(),
         }
     }
+
+    /// Is the frame an equatorial one (i.e. whose longitude is a right ascension)
+    #[inline]
+    pub fn is_equatorial(&self) -> bool {
+        matches!(
+            self,
+            CooSystem::ICRS | CooSystem::FK5J2000 | CooSystem::FK4B1950
+        )
+    }
 }
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::angle_fmt::AngleSerializeFmt;
 
 use super::color::ColorRGB;
+use super::coo_system::CooSystem;
 
 #[wasm_bindgen]
 #[derive(Deserialize, Serialize)]
@@ -23,6 +24,34 @@ pub struct GridCfg {
     pub enabled: Option<bool>,
     #[serde(default = "default_fmt")]
     pub fmt: Option<AngleSerializeFmt>,
+    /// Draw the horizon line when the view frame is the horizontal one
+    #[serde(default = "default_horizon")]
+    pub show_horizon: Option<bool>,
+}
+
+/// Options of the HEALPix grid drawn at the order of the tiles in view
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HEALPixGridCfg {
+    #[serde(default = "default_color")]
+    pub color: Option<ColorRGB>,
+    #[serde(default = "default_thickness")]
+    pub thickness: Option<f32>,
+    pub opacity: Option<f32>,
+    /// Label the cells with their `norder/npix` index
+    #[serde(default = "default_labels")]
+    pub show_labels: Option<bool>,
+    #[serde(default = "default_label_size")]
+    pub label_size: Option<f32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: Option<bool>,
+    /// Frame in which the HEALPix tessellation is defined
+    #[serde(default = "default_frame")]
+    pub frame: Option<CooSystem>,
+}
+
+fn default_frame() -> Option<CooSystem> {
+    None
 }
 
 fn default_labels() -> Option<bool> {
@@ -48,3 +77,7 @@ fn default_thickness() -> Option<f32> {
 fn default_fmt() -> Option<AngleSerializeFmt> {
     None
 }
+
+fn default_horizon() -> Option<bool> {
+    None
+}
@@ -36,6 +36,8 @@ pub struct FITSCfg {
 }
 
 use crate::coo_system::CooSystem;
+use crate::properties::PropertiesFile;
+use std::str::FromStr;
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -57,6 +59,16 @@ pub struct HiPSProperties {
     hips_initial_ra: Option<f64>,
     hips_initial_dec: Option<f64>,
 
+    cube_depth: Option<u32>,
+    cube_first_frame: Option<u32>,
+    cube_crpix3: Option<f64>,
+    cube_crval3: Option<f64>,
+    cube_cdelt3: Option<f64>,
+    cube_bunit3: Option<String>,
+
+    // The urls of the mirrors of the HiPS service
+    mirrors: Option<Vec<String>>,
+
     // Parametrable by the user
     min_cutout: Option<f32>,
     max_cutout: Option<f32>,
@@ -65,6 +77,199 @@ pub struct HiPSProperties {
 }
 
 impl HiPSProperties {
+    /// Describe a full sky HiPS whose tiles are not retrieved from a server
+    /// but generated by the client, e.g. from a HEALPix map
+    pub fn new(
+        url: String,
+        creator_did: String,
+        max_order: u8,
+        tile_size: i32,
+        coo_frame: CooSystem,
+        formats: Vec<ImageExt>,
+        bitpix: Option<i32>,
+    ) -> Self {
+        Self {
+            url,
+            max_order,
+            coo_frame,
+            tile_size,
+            formats,
+            is_planetary_body: None,
+            bitpix,
+            sky_fraction: Some(1.0),
+            min_order: None,
+            hips_initial_fov: None,
+            hips_initial_ra: None,
+            hips_initial_dec: None,
+            cube_depth: None,
+            cube_first_frame: None,
+            cube_crpix3: None,
+            cube_crval3: None,
+            cube_cdelt3: None,
+            cube_bunit3: None,
+            mirrors: None,
+            min_cutout: None,
+            max_cutout: None,
+            creator_did,
+        }
+    }
+
+    /// Read the properties of a HiPS from its `properties` file
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The base url of the HiPS
+    /// * `file` - Its parsed `properties` file
+    ///
+    /// An error is returned if a mandatory key is missing, if a value is malformed or if
+    /// the HiPS cannot be displayed, e.g. a catalog HiPS or a HiPS without any supported tile format
+    pub fn from_properties_file(url: String, file: &PropertiesFile) -> Result<Self, String> {
+        if let Some(dataproduct_type) = file.get("dataproduct_type") {
+            if !matches!(dataproduct_type, "image" | "cube") {
+                return Err(format!(
+                    "Only image and cube HiPS can be displayed, found a {} HiPS",
+                    dataproduct_type
+                ));
+            }
+        }
+
+        let max_order: u8 = parse_value(file, "hips_order")?
+            .ok_or_else(|| "Missing the mandatory hips_order".to_string())?;
+        if max_order > 29 {
+            return Err(format!("hips_order {} is greater than 29", max_order));
+        }
+        let min_order = parse_value(file, "hips_order_min")?;
+
+        let tile_size: i32 = parse_value(file, "hips_tile_width")?.unwrap_or(512);
+        if tile_size <= 0 || (tile_size & (tile_size - 1)) != 0 {
+            return Err(format!(
+                "hips_tile_width {} is not a power of two",
+                tile_size
+            ));
+        }
+
+        // Planetary bodies are drawn as seen in the equatorial frame
+        let is_planetary_body = file.get("hips_body").is_some();
+        let coo_frame = match file.get("hips_frame") {
+            _ if is_planetary_body => CooSystem::ICRS,
+            Some(frame) => match frame.to_lowercase().as_str() {
+                "equatorial" | "icrs" => CooSystem::ICRS,
+                "galactic" => CooSystem::GAL,
+                "ecliptic" => CooSystem::ECL,
+                _ => return Err(format!("Unknown hips_frame {}", frame)),
+            },
+            None => return Err("Missing the mandatory hips_frame".to_string()),
+        };
+
+        // The formats not supported, e.g. the tsv ones of catalogs, are skipped
+        let tile_formats = file.get("hips_tile_format").unwrap_or("jpeg");
+        let formats = tile_formats
+            .split_whitespace()
+            .filter_map(|format| match format.to_lowercase().as_str() {
+                "fits" => Some(ImageExt::Fits),
+                "jpeg" | "jpg" => Some(ImageExt::Jpeg),
+                "png" => Some(ImageExt::Png),
+                "webp" => Some(ImageExt::Webp),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if formats.is_empty() {
+            return Err(format!(
+                "No supported format in hips_tile_format {}, expected fits, jpeg, png or webp",
+                tile_formats
+            ));
+        }
+
+        let bitpix = parse_value(file, "hips_pixel_bitpix")?;
+        if let Some(bitpix) = bitpix {
+            if ![8, 16, 32, -32, -64].contains(&bitpix) {
+                return Err(format!("Invalid hips_pixel_bitpix {}", bitpix));
+            }
+        }
+
+        let (min_cutout, max_cutout) = if let Some(cuts) = file.get("hips_pixel_cut") {
+            let values = cuts
+                .split_whitespace()
+                .map(|cut| cut.parse::<f32>())
+                .collect::<Result<Vec<_>, _>>();
+            match values.as_deref() {
+                Ok([min, max]) => (Some(*min), Some(*max)),
+                _ => return Err(format!("Invalid hips_pixel_cut {}", cuts)),
+            }
+        } else {
+            (None, None)
+        };
+
+        let sky_fraction = parse_value(file, "moc_sky_fraction")?;
+        if let Some(sky_fraction) = sky_fraction {
+            if !(0.0..=1.0).contains(&sky_fraction) {
+                return Err(format!(
+                    "moc_sky_fraction {} is not between 0 and 1",
+                    sky_fraction
+                ));
+            }
+        }
+
+        let cube_depth = parse_value(file, "hips_cube_depth")?;
+        if cube_depth == Some(0) {
+            return Err("hips_cube_depth must be at least 1".to_string());
+        }
+        let cube_first_frame = parse_value(file, "hips_cube_firstframe")?;
+        if let (Some(first_frame), Some(depth)) = (cube_first_frame, cube_depth) {
+            if first_frame >= depth {
+                return Err(format!(
+                    "hips_cube_firstframe {} is not lower than hips_cube_depth {}",
+                    first_frame, depth
+                ));
+            }
+        }
+
+        // hips_service_url, hips_service_url_1, hips_service_url_2, ...
+        let mirrors = file
+            .iter()
+            .filter(|(key, _)| {
+                key.strip_prefix("hips_service_url").is_some_and(|suffix| {
+                    suffix.is_empty()
+                        || suffix
+                            .strip_prefix('_')
+                            .is_some_and(|num| num.parse::<u32>().is_ok())
+                })
+            })
+            .map(|(_, url)| url.to_string())
+            .collect::<Vec<_>>();
+
+        let creator_did = file
+            .get("creator_did")
+            .or_else(|| file.get("publisher_did"))
+            .map(|did| did.to_string())
+            .unwrap_or_else(|| url.clone());
+
+        Ok(Self {
+            url,
+            max_order,
+            coo_frame,
+            tile_size,
+            formats,
+            is_planetary_body: Some(is_planetary_body),
+            bitpix,
+            sky_fraction,
+            min_order,
+            hips_initial_fov: parse_value(file, "hips_initial_fov")?,
+            hips_initial_ra: parse_value(file, "hips_initial_ra")?,
+            hips_initial_dec: parse_value(file, "hips_initial_dec")?,
+            cube_depth,
+            cube_first_frame,
+            cube_crpix3: parse_value(file, "hips_cube_crpix3")?,
+            cube_crval3: parse_value(file, "hips_cube_crval3")?,
+            cube_cdelt3: parse_value(file, "hips_cube_cdelt3")?,
+            cube_bunit3: file.get("hips_cube_bunit3").map(|unit| unit.to_string()),
+            mirrors: Some(mirrors).filter(|mirrors| !mirrors.is_empty()),
+            min_cutout,
+            max_cutout,
+            creator_did,
+        })
+    }
+
     #[inline(always)]
     pub fn get_url(&self) -> &str {
         &self.url
@@ -95,6 +300,15 @@ impl HiPSProperties {
         &self.formats[..]
     }
 
+    /// The format of the tiles displayed when none is asked for: jpeg, then png, then fits
+    pub fn get_default_format(&self) -> Option<ImageExt> {
+        [ImageExt::Jpeg, ImageExt::Png, ImageExt::Fits]
+            .iter()
+            .copied()
+            .find(|format| self.formats.contains(format))
+            .or_else(|| self.formats.first().copied())
+    }
+
     #[inline(always)]
     pub fn get_tile_size(&self) -> i32 {
         self.tile_size
@@ -124,9 +338,77 @@ impl HiPSProperties {
     pub fn get_initial_dec(&self) -> Option<f64> {
         self.hips_initial_dec
     }
+
+    /// The urls of the mirrors of the HiPS service, by order of preference
+    #[inline(always)]
+    pub fn get_mirrors(&self) -> &[String] {
+        self.mirrors.as_deref().unwrap_or(&[])
+    }
+
+    /// The number of slices of a HiPS cube
+    #[inline(always)]
+    pub fn get_cube_depth(&self) -> Option<u32> {
+        self.cube_depth
+    }
+
+    /// The slice of a HiPS cube to display first
+    #[inline(always)]
+    pub fn get_cube_first_frame(&self) -> Option<u32> {
+        self.cube_first_frame
+    }
+
+    /// The world coordinates along the third axis of a HiPS cube
+    ///
+    /// None is returned if the properties do not give the value of the reference slice
+    pub fn get_cube_axis(&self) -> Option<CubeAxis> {
+        let crval3 = self.cube_crval3?;
+
+        Some(CubeAxis {
+            crpix3: self.cube_crpix3.unwrap_or(1.0),
+            crval3,
+            cdelt3: self.cube_cdelt3.unwrap_or(1.0),
+            unit: self.cube_bunit3.clone(),
+        })
+    }
+}
+
+/// The linear world coordinates along the third axis of a HiPS cube,
+/// e.g. the frequency or the velocity of its slices
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeAxis {
+    crpix3: f64,
+    crval3: f64,
+    cdelt3: f64,
+    unit: Option<String>,
+}
+
+impl CubeAxis {
+    /// The world coordinate of a slice
+    ///
+    /// Slices are numbered from 0 whereas the reference pixel `hips_cube_crpix3`
+    /// follows the FITS convention and starts at 1
+    pub fn get_frame_coo(&self, frame: u32) -> f64 {
+        self.crval3 + (frame as f64 + 1.0 - self.crpix3) * self.cdelt3
+    }
+
+    #[inline(always)]
+    pub fn get_unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+}
+
+// Parse the value of an optional key
+fn parse_value<T: FromStr>(file: &PropertiesFile, key: &str) -> Result<Option<T>, String> {
+    file.get(key)
+        .map(|value| {
+            value
+                .parse::<T>()
+                .map_err(|_| format!("Invalid value for {}: {}", key, value))
+        })
+        .transpose()
 }
 
-#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[wasm_bindgen]
 #[serde(rename_all = "camelCase")]
 pub enum ImageExt {
@@ -327,3 +609,109 @@ impl ImageMetadata {
         self.opacity > 0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{HiPSProperties, ImageExt};
+    use crate::coo_system::CooSystem;
+    use crate::properties::PropertiesFile;
+
+    fn from_str(properties: &str) -> Result<HiPSProperties, String> {
+        let file = PropertiesFile::parse(properties)?;
+        HiPSProperties::from_properties_file("https://alasky.cds.unistra.fr/DSS".to_string(), &file)
+    }
+
+    #[test]
+    fn from_properties_file() {
+        let properties = from_str(
+            "creator_did = ivo://CDS/P/DSS2/red\n\
+             dataproduct_type = image\n\
+             hips_order = 9\n\
+             hips_frame = equatorial\n\
+             hips_tile_format = jpeg fits\n\
+             hips_pixel_bitpix = 16\n\
+             hips_pixel_cut = 1000 15000\n\
+             hips_initial_ra = 85.25\n\
+             moc_sky_fraction = 1\n\
+             hips_service_url = https://alasky.cds.unistra.fr/DSS/DSSColor\n\
+             hips_service_url_1 = https://alaskybis.cds.unistra.fr/DSS/DSSColor\n\
+             hips_service_url_copyright = CNRS/Unistra\n\
+             hips_copyright = CNRS/Unistra\n",
+        )
+        .unwrap();
+
+        assert_eq!(properties.get_creator_did(), "ivo://CDS/P/DSS2/red");
+        assert_eq!(properties.get_max_order(), 9);
+        assert_eq!(properties.get_min_order(), None);
+        assert_eq!(properties.get_frame(), CooSystem::ICRS);
+        assert_eq!(properties.get_tile_size(), 512);
+        assert_eq!(properties.get_formats(), &[ImageExt::Jpeg, ImageExt::Fits]);
+        assert_eq!(properties.get_bitpix(), Some(16));
+        assert_eq!(properties.get_initial_ra(), Some(85.25));
+        assert_eq!(properties.get_sky_fraction(), Some(1.0));
+        assert_eq!(properties.get_cube_depth(), None);
+        assert_eq!(properties.get_cube_axis(), None);
+        assert_eq!(
+            properties.get_mirrors(),
+            &[
+                "https://alasky.cds.unistra.fr/DSS/DSSColor",
+                "https://alaskybis.cds.unistra.fr/DSS/DSSColor"
+            ]
+        );
+
+        // the creator did defaults to the url
+        let properties = from_str(
+            "hips_order = 3\n\
+             hips_frame = galactic\n\
+             hips_cube_depth = 10\n\
+             hips_cube_firstframe = 5\n\
+             hips_cube_crpix3 = 3\n\
+             hips_cube_crval3 = 1420.0\n\
+             hips_cube_cdelt3 = 0.5\n\
+             hips_cube_bunit3 = MHz",
+        )
+        .unwrap();
+        assert_eq!(
+            properties.get_creator_did(),
+            "https://alasky.cds.unistra.fr/DSS"
+        );
+        assert_eq!(properties.get_frame(), CooSystem::GAL);
+        assert!(properties.get_mirrors().is_empty());
+        assert_eq!(properties.get_cube_depth(), Some(10));
+        assert_eq!(properties.get_cube_first_frame(), Some(5));
+        let axis = properties.get_cube_axis().unwrap();
+        assert_eq!(axis.get_frame_coo(2), 1420.0);
+        assert_eq!(axis.get_frame_coo(5), 1421.5);
+        assert_eq!(axis.get_unit(), Some("MHz"));
+    }
+
+    #[test]
+    fn invalid_properties() {
+        // missing order or frame
+        assert!(from_str("hips_frame = equatorial").is_err());
+        assert!(from_str("hips_order = 3").is_err());
+
+        // the first value of a key is the one read, the valid ones given next are thus ignored
+        for invalid in [
+            "hips_order = 30",
+            "hips_order = three",
+            "hips_frame = mars",
+            "hips_tile_width = 500",
+            "hips_tile_format = tsv",
+            "hips_pixel_bitpix = 12",
+            "hips_pixel_cut = 10",
+            "moc_sky_fraction = 2",
+            "dataproduct_type = catalog",
+            "hips_cube_depth = 0",
+            "hips_cube_depth = 4\nhips_cube_firstframe = 4",
+        ] {
+            assert!(
+                from_str(&format!(
+                    "{invalid}\nhips_order = 3\nhips_frame = equatorial"
+                ))
+                .is_err(),
+                "{invalid}"
+            );
+        }
+    }
+}
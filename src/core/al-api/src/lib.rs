@@ -9,7 +9,10 @@ pub mod coo_system;
 pub mod grid;
 pub mod hips;
 pub mod moc;
+pub mod projection;
+pub mod properties;
 pub mod resources;
+pub mod tissot;
 pub mod cell;
 pub mod fov;
 pub mod image;
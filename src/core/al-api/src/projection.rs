@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the projections, i.e. the values of the FITS PVi_m keywords
+/// attached to the latitude axis.
+///
+/// Angles are given in degrees. A parameter left undefined takes the default
+/// value of the FITS WCS standard (Calabretta & Greisen 2002).
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionParams {
+    /// Distance of the point of projection from the centre of the sphere
+    /// in spherical radii (AZP: PV2_1, CYP: PV2_1)
+    pub mu: Option<f64>,
+    /// Tilt angle of the projection plane (AZP: PV2_2)
+    pub gamma: Option<f64>,
+    /// Latitude of the minimisation of the error (AIR: PV2_1)
+    pub theta_b: Option<f64>,
+    /// Radius of the cylinder in spherical radii (CYP: PV2_2, CEA: PV2_1)
+    pub lambda: Option<f64>,
+    /// Mean of the two standard parallels (COD: PV2_1)
+    pub theta_a: Option<f64>,
+    /// Half of the angle between the two standard parallels (COD: PV2_2)
+    pub eta: Option<f64>,
+    /// Polynomial coefficients P0, P1, ..., P20 (ZPN: PV2_0 to PV2_20)
+    pub coeffs: Option<Vec<f64>>,
+}
@@ -0,0 +1,92 @@
+/// The key/value pairs of a HiPS `properties` file
+///
+/// Keys are kept in their order of appearance. A key can be given several times,
+/// and unknown keys are kept so that an application can read its own metadata.
+///
+/// See the HiPS 1.0 recommendation: <https://www.ivoa.net/documents/HiPS/>
+#[derive(Debug, Clone, Default)]
+pub struct PropertiesFile {
+    entries: Vec<(String, String)>,
+}
+
+impl PropertiesFile {
+    /// Parse the content of a `properties` file, made of `key = value` lines.
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut entries = vec![];
+        for (num, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                format!("Line {}: expected `key = value`, found {}", num + 1, line)
+            })?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(format!("Line {}: invalid key '{}'", num + 1, key));
+            }
+
+            entries.push((key.to_string(), value.trim().to_string()));
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// The first value given to a key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All the values given to a key, in their order of appearance
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All the (key, value) pairs, in their order of appearance
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PropertiesFile;
+
+    #[test]
+    fn parse() {
+        let file = PropertiesFile::parse(
+            "# A comment\r\n\
+             creator_did = ivo://CDS/P/DSS2/red\r\n\
+             \r\n\
+             hips_tile_format=jpeg fits\n\
+             obs_description = a = b\n\
+             hips_service_url_1 = https://alasky.cds.unistra.fr/DSS/DSS2Merged\n\
+             hips_service_url_1 = http://alaskybis.cds.unistra.fr/DSS/DSS2Merged\n",
+        )
+        .unwrap();
+
+        assert_eq!(file.get("creator_did"), Some("ivo://CDS/P/DSS2/red"));
+        assert_eq!(file.get("hips_tile_format"), Some("jpeg fits"));
+        assert_eq!(file.get("obs_description"), Some("a = b"));
+        assert_eq!(file.get_all("hips_service_url_1").count(), 2);
+        assert_eq!(file.get("hips_order"), None);
+        assert_eq!(file.iter().count(), 5);
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(PropertiesFile::parse("hips_order 3").is_err());
+        assert!(PropertiesFile::parse("= 3").is_err());
+        assert!(PropertiesFile::parse("hips order = 3").is_err());
+    }
+}
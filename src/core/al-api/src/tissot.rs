@@ -0,0 +1,38 @@
+use wasm_bindgen::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use super::color::ColorRGB;
+
+/// Options of the overlay drawing the Tissot indicatrices of the projection
+#[wasm_bindgen]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TissotCfg {
+    #[serde(default = "default_color")]
+    pub color: Option<ColorRGB>,
+    #[serde(default = "default_thickness")]
+    pub thickness: Option<f32>,
+    pub opacity: Option<f32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: Option<bool>,
+    /// Number of ellipses drawn along the width of the view
+    #[serde(default = "default_num")]
+    pub num: Option<u32>,
+}
+
+fn default_color() -> Option<ColorRGB> {
+    None
+}
+
+fn default_thickness() -> Option<f32> {
+    None
+}
+
+fn default_enabled() -> Option<bool> {
+    None
+}
+
+fn default_num() -> Option<u32> {
+    None
+}
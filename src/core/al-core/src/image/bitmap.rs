@@ -18,6 +18,11 @@ where
             format: std::marker::PhantomData,
         }
     }
+
+    // Estimation of the memory taken by the decoded image
+    pub fn byte_size(&self) -> usize {
+        (self.image.width() * self.image.height()) as usize * F::NUM_CHANNELS
+    }
 }
 use wasm_bindgen::JsValue;
 use crate::texture::Texture2DArray;
@@ -15,6 +15,11 @@ where
             format: std::marker::PhantomData,
         }
     }
+
+    // Estimation of the memory taken by the canvas pixels
+    pub fn byte_size(&self) -> usize {
+        (self.canvas.width() * self.canvas.height()) as usize * F::NUM_CHANNELS
+    }
 }
 
 use cgmath::Vector3;
@@ -15,6 +15,11 @@ where
             format: std::marker::PhantomData,
         }
     }
+
+    // Estimation of the memory taken by the decoded image
+    pub fn byte_size(&self) -> usize {
+        (self.image.natural_width() * self.image.natural_height()) as usize * F::NUM_CHANNELS
+    }
 }
 
 use cgmath::Vector3;
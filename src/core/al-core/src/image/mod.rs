@@ -288,6 +288,26 @@ pub enum ImageType {
     RawR32f { image: ImageBuffer<R32F> },
 }
 
+impl ImageType {
+    /// Estimation of the memory taken by the image, in bytes
+    pub fn byte_size(&self) -> usize {
+        match self {
+            ImageType::FitsImage { raw_bytes } => raw_bytes.length() as usize,
+            ImageType::Canvas { canvas } => canvas.byte_size(),
+            ImageType::ImageRgba8u { image } => image.byte_size(),
+            ImageType::ImageRgb8u { image } => image.byte_size(),
+            ImageType::HTMLImageRgba8u { image } => image.byte_size(),
+            ImageType::HTMLImageRgb8u { image } => image.byte_size(),
+            ImageType::RawRgb8u { image } => image.byte_size(),
+            ImageType::RawRgba8u { image } => image.byte_size(),
+            ImageType::RawR32f { image } => image.byte_size(),
+            ImageType::RawR32i { image } => image.byte_size(),
+            ImageType::RawR16i { image } => image.byte_size(),
+            ImageType::RawR8ui { image } => image.byte_size(),
+        }
+    }
+}
+
 use cgmath::Vector3;
 impl Image for ImageType {
     fn tex_sub_image_3d(
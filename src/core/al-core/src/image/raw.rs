@@ -125,6 +125,10 @@ where
     pub fn height(&self) -> i32 {
         self.size.y
     }
+
+    pub fn byte_size(&self) -> usize {
+        self.data.len() * std::mem::size_of::<<<T as ImageFormat>::P as Pixel>::Item>()
+    }
 }
 
 use crate::image::format::{R16I, R32F, R32I, R8UI, RGB8U, RGBA8U};
@@ -0,0 +1,298 @@
+use cgmath::Matrix4;
+
+use al_api::coo_system::CooBaseFloat;
+
+use crate::epoch::{self, Epoch, SECONDS_PER_DAY};
+
+/// Julian date of the J2000.0 epoch
+const JD_J2000: f64 = 2451545.0;
+
+/// Location of a ground based observer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observer {
+    /// Geodetic longitude in degrees, positive towards the East
+    pub lon: f64,
+    /// Geodetic latitude in degrees
+    pub lat: f64,
+    /// Elevation above the sea level in meters. It is not used by the
+    /// conversions as the atmospheric refraction is not taken into account
+    pub elevation: f64,
+}
+
+impl Observer {
+    pub fn new(lon: f64, lat: f64, elevation: f64) -> Self {
+        Self {
+            lon,
+            lat,
+            elevation,
+        }
+    }
+}
+
+impl Default for Observer {
+    fn default() -> Self {
+        // Observatoire astronomique de Strasbourg
+        Self::new(7.768, 48.583, 142.0)
+    }
+}
+
+/// The horizontal (Alt-Az) frame of an observer at a given UTC time.
+///
+/// The frame is defined by the mean equator and equinox of date (FK5 precession
+/// from J2000) rotated by the local mean sidereal time. Nutation, aberration
+/// and the UT1-UTC difference are neglected so that positions are accurate
+/// to about one arcminute. The atmospheric refraction is not taken into account.
+///
+/// The frame is right-handed with its x axis towards the West so that its longitude
+/// is the opposite of the azimuth, see [`azimuth`] for getting the latter.
+///
+/// The frame is owned by the camera and given to the coordinate conversions
+/// involving [`CooSystem::ALTAZ`](al_api::coo_system::CooSystem::ALTAZ)
+#[derive(Clone, Copy, Debug)]
+pub struct HorizontalFrame {
+    observer: Observer,
+    // UTC time as a Julian date
+    time: Epoch,
+    // Rotation from ICRS, computed once the observer or the time changes
+    icrs_to_altaz: Matrix4<f64>,
+}
+
+impl HorizontalFrame {
+    pub fn new(observer: Observer, time: Epoch) -> Self {
+        let icrs_to_altaz = compute_icrs_to_altaz(&observer, time);
+
+        Self {
+            observer,
+            time,
+            icrs_to_altaz,
+        }
+    }
+
+    pub fn set_observer(&mut self, observer: Observer) {
+        *self = Self::new(observer, self.time);
+    }
+
+    pub fn set_time(&mut self, time: Epoch) {
+        *self = Self::new(self.observer, time);
+    }
+
+    pub fn get_observer(&self) -> &Observer {
+        &self.observer
+    }
+
+    pub fn get_time(&self) -> Epoch {
+        self.time
+    }
+
+    /// Local mean sidereal time in radians
+    pub fn local_sidereal_time(&self) -> f64 {
+        local_sidereal_time(self.time.jd(), self.observer.lon.to_radians())
+    }
+
+    /// Rotation matrix converting an ICRS vector into the horizontal frame
+    pub fn icrs_to_altaz(&self) -> &Matrix4<f64> {
+        &self.icrs_to_altaz
+    }
+}
+
+/// Rotation matrix converting an ICRS vector into the horizontal frame
+/// of an observer at a given UTC time
+fn compute_icrs_to_altaz(observer: &Observer, time: Epoch) -> Matrix4<f64> {
+    let lst = local_sidereal_time(time.jd(), observer.lon.to_radians());
+    let (s_lst, c_lst) = lst.sin_cos();
+    let (s_lat, c_lat) = observer.lat.to_radians().sin_cos();
+
+    // Equatorial of date to horizontal. In model space, the output x axis
+    // points towards the West, y towards the zenith and z towards the North
+    let hor = Matrix4::new(
+        -c_lst,
+        c_lat * s_lst,
+        -s_lat * s_lst,
+        0.0,
+        0.0,
+        s_lat,
+        c_lat,
+        0.0,
+        s_lst,
+        c_lat * c_lst,
+        -s_lat * c_lst,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+    let prec = epoch::fk5_precession(Epoch::J2000, time);
+    let bias = *f64::J2000_TO_FK5J2000;
+
+    hor * prec * bias
+}
+
+/// Azimuth in degrees, from the North (0) towards the East (90), of a longitude of the
+/// horizontal frame given in degrees
+///
+/// The azimuth being the opposite of the longitude, this also gives the longitude of an azimuth.
+pub fn azimuth(lon: f64) -> f64 {
+    (-lon).rem_euclid(360.0)
+}
+
+/// Greenwich mean sidereal time in radians (IAU 1982) for a UT Julian date
+pub fn greenwich_sidereal_time(jd: f64) -> f64 {
+    let t = (jd - JD_J2000) / 36525.0;
+    let gmst_sec = 67310.54841 + (876600.0 * 3600.0 + 8640184.812866) * t + 0.093104 * t * t
+        - 6.2e-6 * t * t * t;
+
+    (gmst_sec / SECONDS_PER_DAY * crate::math::TWICE_PI).rem_euclid(crate::math::TWICE_PI)
+}
+
+/// Local mean sidereal time in radians for a UT Julian date and
+/// an east positive longitude given in radians
+pub fn local_sidereal_time(jd: f64, lon: f64) -> f64 {
+    (greenwich_sidereal_time(jd) + lon).rem_euclid(crate::math::TWICE_PI)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn greenwich_sidereal_time() {
+        // Meeus, Astronomical Algorithms, example 12.a: 1987 April 10, 0h UT
+        let gmst = super::greenwich_sidereal_time(2446895.5).to_degrees();
+        // 13h10m46.3668s
+        assert!((gmst - 197.693195).abs() < 1e-5);
+    }
+
+    #[test]
+    fn zenith_and_pole() {
+        use super::{HorizontalFrame, Observer};
+        use crate::epoch::Epoch;
+        use crate::math::lonlat::LonLat;
+        use al_api::coo_system::CooSystem;
+        use cgmath::Vector4;
+
+        let observer = Observer::new(7.768, 48.583, 142.0);
+        let time = Epoch::from_jd(2460000.5);
+        let frame = HorizontalFrame::new(observer, time);
+
+        // The zenith has a right ascension equal to the local sidereal time
+        // and a declination equal to the latitude in the equatorial frame of date
+        let zenith_altaz = Vector4::new(0.0, 1.0, 0.0, 1.0);
+        let zenith = crate::coosys::apply_coo_system(
+            CooSystem::ALTAZ,
+            CooSystem::ICRS,
+            &zenith_altaz,
+            &frame,
+        );
+        let zenith_of_date =
+            crate::epoch::apply_precession(CooSystem::ICRS, Epoch::J2000, time, &zenith).lonlat();
+        let lst = frame.local_sidereal_time();
+        let d_lon = (zenith_of_date.lon().to_radians() - lst)
+            .sin()
+            .abs()
+            .to_degrees();
+        assert!(d_lon < 1e-3);
+        assert!((zenith_of_date.lat().to_degrees() - 48.583).abs() < 1e-3);
+
+        // The celestial pole of date is at azimuth 0 and altitude equal to the latitude
+        let pole_of_date = Vector4::new(0.0, 1.0, 0.0, 1.0);
+        let pole =
+            crate::epoch::apply_precession(CooSystem::ICRS, time, Epoch::J2000, &pole_of_date);
+        let pole_altaz =
+            crate::coosys::apply_coo_system(CooSystem::ICRS, CooSystem::ALTAZ, &pole, &frame);
+        let pole_altaz = pole_altaz.lonlat();
+        assert!(pole_altaz.lon().to_radians().sin().abs() < 1e-4);
+        assert!((pole_altaz.lat().to_degrees() - 48.583).abs() < 1e-2);
+    }
+
+    #[test]
+    fn rotation() {
+        use super::{HorizontalFrame, Observer};
+        use crate::epoch::Epoch;
+        use cgmath::SquareMatrix;
+
+        let frame = HorizontalFrame::new(Observer::default(), Epoch::from_jd(2460000.5));
+        // A rotation and not a reflection
+        assert!((frame.icrs_to_altaz().determinant() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn star_azimuth_and_altitude() {
+        use super::{azimuth, HorizontalFrame, Observer};
+        use crate::epoch::Epoch;
+        use crate::math::angle::ToAngle;
+        use crate::math::lonlat::{LonLat, LonLatT};
+        use al_api::coo_system::CooSystem;
+        use cgmath::{InnerSpace, Vector4};
+
+        let lat = 48.583_f64;
+        let time = Epoch::from_jd(2460000.5);
+        let frame = HorizontalFrame::new(Observer::new(7.768, lat, 142.0), time);
+
+        // Vega in ICRS
+        let vega: Vector4<f64> = LonLatT::new(
+            279.234735_f64.to_radians().to_angle(),
+            38.783689_f64.to_radians().to_angle(),
+        )
+        .vector();
+
+        // The azimuth and altitude from the hour angle and the declination of date
+        let of_date =
+            crate::epoch::apply_precession(CooSystem::ICRS, Epoch::J2000, time, &vega).lonlat();
+        let h = frame.local_sidereal_time() - of_date.lon().to_radians();
+        let dec = of_date.lat().to_radians();
+        let (s_lat, c_lat) = lat.to_radians().sin_cos();
+        let alt = (s_lat * dec.sin() + c_lat * dec.cos() * h.cos()).asin();
+        let az = (-dec.cos() * h.sin())
+            .atan2(dec.sin() * c_lat - dec.cos() * h.cos() * s_lat)
+            .to_degrees()
+            .rem_euclid(360.0);
+
+        let altaz =
+            crate::coosys::apply_coo_system(CooSystem::ICRS, CooSystem::ALTAZ, &vega, &frame);
+        let lonlat = altaz.lonlat();
+        let d_az = (azimuth(lonlat.lon().to_degrees()) - az + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(d_az.abs() < 1e-3);
+        assert!((lonlat.lat().to_radians() - alt).abs().to_degrees() < 1e-3);
+
+        // Back to ICRS
+        let icrs =
+            crate::coosys::apply_coo_system(CooSystem::ALTAZ, CooSystem::ICRS, &altaz, &frame);
+        assert!((icrs - vega).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn independent_frames() {
+        use super::{HorizontalFrame, Observer};
+        use crate::epoch::Epoch;
+        use al_api::coo_system::CooSystem;
+        use cgmath::{InnerSpace, Vector4};
+
+        let time = Epoch::from_jd(2460000.5);
+        let zenith_altaz = Vector4::new(0.0, 1.0, 0.0, 1.0);
+
+        let strasbourg = HorizontalFrame::new(Observer::new(7.768, 48.583, 142.0), time);
+        let zenith = crate::coosys::apply_coo_system(
+            CooSystem::ALTAZ,
+            CooSystem::ICRS,
+            &zenith_altaz,
+            &strasbourg,
+        );
+
+        // Defining the frame of another observer does not change the first one
+        let paranal = HorizontalFrame::new(Observer::new(-70.404, -24.627, 2635.0), time);
+        let zenith_paranal = crate::coosys::apply_coo_system(
+            CooSystem::ALTAZ,
+            CooSystem::ICRS,
+            &zenith_altaz,
+            &paranal,
+        );
+        assert!((zenith - zenith_paranal).magnitude() > 0.5);
+
+        let zenith_again = crate::coosys::apply_coo_system(
+            CooSystem::ALTAZ,
+            CooSystem::ICRS,
+            &zenith_altaz,
+            &strasbourg,
+        );
+        assert_eq!(zenith, zenith_again);
+    }
+}
@@ -1,9 +1,11 @@
 use crate::{
     //async_task::{BuildCatalogIndex, ParseTableTask, TaskExecutor, TaskResult, TaskType},
+    altaz::{HorizontalFrame, Observer},
     camera::CameraViewPort,
     downloader::Downloader,
-    grid::ProjetedGrid,
-    healpix::coverage::HEALPixCoverage,
+    epoch::Epoch,
+    grid::{hpx::HEALPixGrid, ProjetedGrid},
+    healpix::{coverage::HEALPixCoverage, map::HEALPixMap, space_time::SpaceTimeCoverage},
     inertia::Inertia,
     math::{
         self,
@@ -12,10 +14,11 @@ use crate::{
     },
     renderable::Layers,
     renderable::{
-        catalog::Manager, coverage::MOCRenderer, line::RasterizedLineRenderer, ImageCfg, Renderer,
+        catalog::Manager, coverage::MOCRenderer, line::RasterizedLineRenderer,
+        tissot::TissotOverlay, CreatorDid, HEALPixMapCfg, HiPS, ImageCfg, Renderer,
     },
     shader::ShaderManager,
-    tile_fetcher::TileFetcherQueue,
+    tile_fetcher::{self, TileFetcherQueue, TilePriority},
     time::DeltaTime,
 };
 use al_core::{
@@ -32,8 +35,10 @@ use super::coosys;
 use crate::Abort;
 use al_api::{
     coo_system::CooSystem,
-    grid::GridCfg,
-    hips::{FITSCfg, HiPSCfg, ImageMetadata},
+    grid::{GridCfg, HEALPixGridCfg},
+    hips::{FITSCfg, HiPSCfg, HiPSProperties, ImageMetadata},
+    properties::PropertiesFile,
+    tissot::TissotCfg,
 };
 use cgmath::Vector4;
 use fitsrs::{fits::AsyncFits, hdu::extension::AsyncXtensionHDU};
@@ -44,9 +49,12 @@ use web_sys::{HtmlElement, WebGl2RenderingContext};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 use crate::renderable::final_pass::RenderPass;
+use crate::renderable::image::native_wcs::NativeWCS;
+use crate::survey::config::HiPSConfig;
 use al_core::FrameBufferObject;
 
 use al_api::image::ImageParams;
@@ -61,6 +69,8 @@ pub struct App {
     downloader: Downloader,
     tile_fetcher: TileFetcherQueue,
     layers: Layers,
+    // The HEALPix maps from which the tiles of their HiPS are generated
+    hpx_maps: HashMap<CreatorDid, HEALPixMap>,
 
     time_start_blending: Time,
     request_redraw: bool,
@@ -68,8 +78,14 @@ pub struct App {
 
     // The grid renderable
     grid: ProjetedGrid,
+    // The HEALPix grid renderable
+    hpx_grid: HEALPixGrid,
+    // The Tissot indicatrices overlay
+    tissot: TissotOverlay,
     // The moc renderable
     moc: MOCRenderer,
+    // The T-MOCs and ST-MOCs, keyed by the uuid of the MOC drawing their spatial projection
+    space_time_mocs: HashMap<String, SpaceTimeCoverage>,
     // Catalog manager
     manager: Manager,
 
@@ -104,15 +120,25 @@ pub struct App {
     fits_send: async_channel::Sender<ImageCfg>,
     fits_recv: async_channel::Receiver<ImageCfg>,
 
+    hpx_map_send: async_channel::Sender<HEALPixMapCfg>,
+    hpx_map_recv: async_channel::Receiver<HEALPixMapCfg>,
+
+    // The HiPS added from their url, each one with the channel acknowledging its addition
+    hips_send: async_channel::Sender<(HiPSCfg, async_channel::Sender<Result<(), JsValue>>)>,
+    hips_recv: async_channel::Receiver<(HiPSCfg, async_channel::Sender<Result<(), JsValue>>)>,
+
     ack_send: async_channel::Sender<ImageParams>,
     ack_recv: async_channel::Receiver<ImageParams>,
     // callbacks
     //callback_position_changed: js_sys::Function,
+    // Called with the creator did and the new url of a HiPS whose mirror has changed
+    callback_mirror_changed: Option<js_sys::Function>,
 }
 
 use cgmath::{Vector2, Vector3};
 use futures::{io::BufReader, stream::StreamExt}; // for `next`
 
+use crate::math::projection::distortion::Distortion;
 use crate::math::projection::*;
 pub const BLENDING_ANIM_DURATION: DeltaTime = DeltaTime::from_millis(200.0); // in ms
                                                                              //use crate::buffer::Tile;
@@ -153,7 +179,11 @@ impl App {
         // The tile buffer responsible for the tile requests
         let downloader = Downloader::new();
 
-        let camera = CameraViewPort::new(&gl, CooSystem::ICRS, &projection);
+        // The horizontal frame is defined for the current time
+        let now = Epoch::from_unix_millis(js_sys::Date::now());
+        let horizontal_frame = HorizontalFrame::new(Observer::default(), now);
+
+        let camera = CameraViewPort::new(&gl, CooSystem::ICRS, horizontal_frame, &projection);
         let screen_size = &camera.get_screen_size();
 
         let _fbo_view =
@@ -170,6 +200,8 @@ impl App {
 
         // Grid definition
         let grid = ProjetedGrid::new(aladin_div)?;
+        let hpx_grid = HEALPixGrid::new(aladin_div)?;
+        let tissot = TissotOverlay::new();
 
         // Variable storing the location to move to
         let inertia = None;
@@ -198,6 +230,8 @@ impl App {
         gl.clear_color(0.15, 0.15, 0.15, 1.0);
 
         let (fits_send, fits_recv) = async_channel::unbounded::<ImageCfg>();
+        let (hpx_map_send, hpx_map_recv) = async_channel::unbounded::<HEALPixMapCfg>();
+        let (hips_send, hips_recv) = async_channel::unbounded();
         let (ack_send, ack_recv) = async_channel::unbounded::<ImageParams>();
 
         let line_renderer = RasterizedLineRenderer::new(&gl)?;
@@ -219,14 +253,18 @@ impl App {
             request_for_new_tiles,
             downloader,
             layers,
+            hpx_maps: HashMap::new(),
 
             time_start_blending,
             rendering,
             request_redraw,
             // The grid renderable
             grid,
+            hpx_grid,
+            tissot,
             // MOCs renderable
             moc,
+            space_time_mocs: HashMap::new(),
             // The catalog renderable
             manager,
             //exec,
@@ -258,19 +296,59 @@ impl App {
 
             fits_send,
             fits_recv,
+            hpx_map_send,
+            hpx_map_recv,
+            hips_send,
+            hips_recv,
             ack_send,
             ack_recv,
             //callback_position_changed,
+            callback_mirror_changed: None,
         })
     }
 
     fn look_for_new_tiles(&mut self) -> Result<(), JsValue> {
         // Move the views of the different active surveys
         self.tile_fetcher.clear();
+
+        // Cancel the downloads of the tiles that left the view
+        let (camera, layers) = (&self.camera, &self.layers);
+        self.downloader.cancel_tile_requests(|tile| {
+            layers
+                .get_hips_from_cdid(tile.get_hips_cdid())
+                .map_or(false, |survey| {
+                    let cfg = survey.get_config();
+                    tile_fetcher::is_tile_in_view(
+                        tile.cell(),
+                        cfg.delta_depth(),
+                        cfg.get_min_depth_tile(),
+                        camera.get_cov(cfg.get_frame()),
+                    )
+                })
+        });
+
         // Loop over the surveys
         let raytracer = self.layers.get_raytracer();
 
         for survey in self.layers.values_mut_hips() {
+            // The tiles of a HEALPix map are not downloaded but generated from the map
+            if let Some(map) = self.hpx_maps.get(survey.get_config().get_creator_did()) {
+                let tiles = survey
+                    .look_for_new_tiles(&mut self.camera, &self.projection)
+                    .map(|tiles| tiles.collect::<Vec<_>>())
+                    .unwrap_or_default();
+
+                for tile_cell in tiles.iter() {
+                    push_hpx_map_tile(survey, map, tile_cell)?;
+                }
+
+                if !tiles.is_empty() {
+                    self.request_redraw = true;
+                }
+
+                continue;
+            }
+
             if self.camera.get_texture_depth() == 0
                 && self
                     .downloader
@@ -289,14 +367,32 @@ impl App {
             let creator_did = survey.get_config().get_creator_did().to_string();
             let root_url = survey.get_config().get_root_url().to_string();
             let format = survey.get_config().get_format();
+            let cube_frame = survey.get_config().get_cube_frame();
+            // The tiles are fetched from the center of the view
+            let center = coosys::apply_coo_system(
+                self.camera.get_coo_system(),
+                survey.get_config().get_frame(),
+                self.camera.get_center(),
+                self.camera.get_horizontal_frame(),
+            )
+            .truncate();
 
             if let Some(tiles_iter) = survey.look_for_new_tiles(&mut self.camera, &self.projection)
             {
                 for tile_cell in tiles_iter.into_iter() {
-                    self.tile_fetcher.append(
-                        query::Tile::new(&tile_cell, creator_did.clone(), root_url.clone(), format),
-                        &mut self.downloader,
+                    let query = query::Tile::new(
+                        &tile_cell,
+                        creator_did.clone(),
+                        root_url.clone(),
+                        format,
+                        cube_frame,
                     );
+                    // Do not request again a tile the service does not have
+                    if !survey.get_config().get_mirrors().is_not_found(&query.id) {
+                        let priority = TilePriority::new(&tile_cell, &center);
+                        self.tile_fetcher
+                            .append(query, priority, &mut self.downloader);
+                    }
 
                     if tile_cell.depth() >= min_tile_depth + 3 {
                         let ancestor_tile_cell = tile_cell.ancestor(3);
@@ -317,10 +413,18 @@ impl App {
             // Request for ancestor
             for ancestor in ancestors {
                 if !survey.update_priority_tile(&ancestor) {
-                    self.tile_fetcher.append(
-                        query::Tile::new(&ancestor, creator_did.clone(), root_url.clone(), format),
-                        &mut self.downloader,
+                    let query = query::Tile::new(
+                        &ancestor,
+                        creator_did.clone(),
+                        root_url.clone(),
+                        format,
+                        cube_frame,
                     );
+                    if !survey.get_config().get_mirrors().is_not_found(&query.id) {
+                        let priority = TilePriority::new(&ancestor, &center);
+                        self.tile_fetcher
+                            .append(query, priority, &mut self.downloader);
+                    }
                 }
             }
         }
@@ -404,6 +508,8 @@ impl App {
 }
 
 use crate::downloader::request::Resource;
+use crate::downloader::query::QueryId;
+use crate::lru_cache::Cache;
 use al_api::cell::HEALPixCellProjeted;
 
 use crate::downloader::request::tile::Tile;
@@ -425,6 +531,7 @@ impl App {
             self.camera.get_center(),
             self.camera.get_coo_system(),
             CooSystem::ICRS,
+            self.camera.get_horizontal_frame(),
             &self.projection,
         );
 
@@ -443,6 +550,7 @@ impl App {
                         CooSystem::ICRS,
                         self.camera.get_coo_system(),
                         &xyzw,
+                        self.camera.get_horizontal_frame(),
                     );
 
                     // 3. project on screen
@@ -498,6 +606,18 @@ impl App {
         Ok(res)
     }*/
 
+    /// The MOCs covering a screen position along with their cell containing it,
+    /// from the MOC drawn on top
+    pub(crate) fn pick_mocs(&self, pos: &Vector2<f64>) -> Vec<(&al_api::moc::MOC, HEALPixCell)> {
+        if let Some(lonlat) = self.screen_to_world(pos) {
+            // the MOCs are defined in ICRS
+            let lonlat = self.view_to_icrs_coosys(&lonlat);
+            self.moc.pick(&lonlat)
+        } else {
+            vec![]
+        }
+    }
+
     pub(crate) fn get_moc(&self, cfg: &al_api::moc::MOC) -> Option<&HEALPixCoverage> {
         self.moc.get_hpx_coverage(cfg)
     }
@@ -514,10 +634,85 @@ impl App {
         Ok(())
     }
 
+    /// Add a T-MOC or a ST-MOC, drawing its spatial projection over all its time ranges
+    pub(crate) fn add_space_time_moc(
+        &mut self,
+        cfg: al_api::moc::MOC,
+        moc: SpaceTimeCoverage,
+    ) -> Result<(), JsValue> {
+        let uuid = cfg.get_uuid().clone();
+        self.add_moc(cfg, moc.project(&(f64::NEG_INFINITY..f64::INFINITY)))?;
+        self.space_time_mocs.insert(uuid, moc);
+
+        Ok(())
+    }
+
+    /// The time interval covered by a T-MOC or a ST-MOC, in julian days
+    pub(crate) fn get_moc_time_range(
+        &self,
+        cfg: &al_api::moc::MOC,
+    ) -> Result<Option<Range<f64>>, JsValue> {
+        let moc = self
+            .space_time_mocs
+            .get(cfg.get_uuid())
+            .ok_or_else(|| JsValue::from_str("Not a T-MOC or a ST-MOC"))?;
+
+        Ok(moc.time_range())
+    }
+
+    /// Draw the spatial projection of a T-MOC or a ST-MOC over a time interval given in julian days
+    pub(crate) fn set_moc_time_range(
+        &mut self,
+        cfg: &al_api::moc::MOC,
+        jd: Range<f64>,
+    ) -> Result<(), JsValue> {
+        let moc = self
+            .space_time_mocs
+            .get(cfg.get_uuid())
+            .ok_or_else(|| JsValue::from_str("Not a T-MOC or a ST-MOC"))?
+            .project(&jd);
+
+        self.moc
+            .set_coverage(cfg, moc)
+            .ok_or_else(|| JsValue::from_str("MOC not found"))?;
+        self.request_redraw = true;
+
+        Ok(())
+    }
+
+    /// Credible region of a HEALPix probability map layer, i.e. the smallest region
+    /// containing a fraction `level` of its total probability
+    pub(crate) fn get_credible_region(
+        &self,
+        layer: &str,
+        level: f64,
+    ) -> Result<HEALPixCoverage, JsValue> {
+        if !(level > 0.0 && level <= 1.0) {
+            return Err(JsValue::from_str("The credible level must be in ]0, 1]"));
+        }
+
+        let map = self
+            .layers
+            .get_hips_from_layer(layer)
+            .and_then(|hips| self.hpx_maps.get(hips.get_config().get_creator_did()))
+            .ok_or_else(|| JsValue::from_str(&format!("{layer} is not a HEALPix map layer")))?;
+
+        // MOCs are drawn in the ICRS frame
+        if map.frame() != CooSystem::ICRS {
+            return Err(JsValue::from_str(
+                "Credible regions are only available for maps in the ICRS frame",
+            ));
+        }
+
+        map.credible_region(level)
+            .map_err(|e| JsValue::from_str(&format!("Credible region: reason: {}", e)))
+    }
+
     pub(crate) fn remove_moc(&mut self, cfg: &al_api::moc::MOC) -> Result<(), JsValue> {
         self.moc
             .remove(cfg, &mut self.camera, &self.projection)
             .ok_or_else(|| JsValue::from_str("MOC not found"))?;
+        self.space_time_mocs.remove(cfg.get_uuid());
 
         self.request_redraw = true;
 
@@ -542,6 +737,10 @@ impl App {
         self.callback_position_changed = callback;
     }*/
 
+    pub(crate) fn set_callback_mirror_changed(&mut self, callback: js_sys::Function) {
+        self.callback_mirror_changed = Some(callback);
+    }
+
     pub(crate) fn is_inerting(&self) -> bool {
         return self.inertia.is_some();
     }
@@ -606,6 +805,8 @@ impl App {
 
             let _num_tile_handled = 0;
             let _tile_copied = false;
+            // The HiPS whose tiles are now fetched from another mirror
+            let mut mirrors_changed = vec![];
             for rsc in rscs_received {
                 match rsc {
                     Resource::Tile(tile) => {
@@ -615,7 +816,28 @@ impl App {
                             {
                                 let cfg = survey.get_config_mut();
 
-                                if cfg.get_format() == tile.format {
+                                // Count the failures of the mirror the tile has been requested to
+                                let mirrors = cfg.get_mirrors_mut();
+                                if let Some(err) = tile.get_error() {
+                                    if let Some(url) =
+                                        mirrors.notify_failure(tile.id(), tile.get_url(), err)
+                                    {
+                                        mirrors_changed
+                                            .push((tile.get_hips_cdid().clone(), url.clone()));
+                                    }
+                                } else {
+                                    mirrors.notify_success(tile.get_url());
+                                }
+
+                                // A tile failing after its retries is not pushed so that it is
+                                // requested again, possibly from another mirror.
+                                // Discard the tiles of a former format or of another slice of a cube
+                                let transient_failure =
+                                    tile.get_error().is_some_and(|err| err.is_transient());
+                                if !transient_failure
+                                    && cfg.get_format() == tile.format
+                                    && cfg.get_cube_frame() == tile.cube_frame
+                                {
                                     let delta_depth = cfg.delta_depth();
                                     let fov_coverage = self.camera.get_cov(cfg.get_frame());
                                     let included_or_near_coverage = tile
@@ -724,7 +946,14 @@ impl App {
                     Resource::Allsky(allsky) => {
                         let hips_cdid = allsky.get_hips_cdid();
 
-                        if let Some(survey) = self.layers.get_mut_hips_from_cdid(hips_cdid) {
+                        let cube_frame = allsky.cube_frame;
+
+                        // Discard the allsky of another slice of a cube
+                        if let Some(survey) = self
+                            .layers
+                            .get_mut_hips_from_cdid(hips_cdid)
+                            .filter(|survey| survey.get_config().get_cube_frame() == cube_frame)
+                        {
                             let is_missing = allsky.missing();
                             if is_missing {
                                 // The allsky image is missing so we donwload all the tiles contained into
@@ -738,6 +967,7 @@ impl App {
                                             cfg.get_creator_did().to_string(),
                                             cfg.get_root_url().to_string(),
                                             cfg.get_format(),
+                                            cfg.get_cube_frame(),
                                         );
                                         self.tile_fetcher
                                             .append_base_tile(query, &mut self.downloader);
@@ -781,6 +1011,19 @@ impl App {
                 }
             }
 
+            for (hips_cdid, url) in mirrors_changed {
+                // Request again the tiles that failed, from the new mirror
+                self.request_for_new_tiles = true;
+
+                if let Some(callback) = &self.callback_mirror_changed {
+                    callback.call2(
+                        &JsValue::null(),
+                        &JsValue::from_str(&hips_cdid),
+                        &JsValue::from_str(&url),
+                    )?;
+                }
+            }
+
             // We fetch when we does not move
             /*let has_not_moved_recently =
                 (Time::now() - self.camera.get_time_of_last_move()) > DeltaTime(100.0);
@@ -849,6 +1092,25 @@ impl App {
             })
         }
 
+        if let Ok(hpx_map) = self.hpx_map_recv.try_recv() {
+            let params = hpx_map.get_params();
+            self.add_healpix_map(hpx_map)?;
+
+            let ack_send = self.ack_send.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                ack_send.send(params).await.unwrap_throw();
+            })
+        }
+
+        if let Ok((hips, ack_send)) = self.hips_recv.try_recv() {
+            // The error is given back to the js promise
+            let res = self.add_image_hips(hips);
+            wasm_bindgen_futures::spawn_local(async move {
+                // The promise may have been dropped
+                let _ = ack_send.send(res).await;
+            })
+        }
+
         self.rendering = blending_anim_occuring | has_camera_moved | self.request_redraw /*| start_fading*/;
         self.request_redraw = false;
 
@@ -882,7 +1144,8 @@ impl App {
     }
 
     pub(crate) fn draw_grid_labels(&mut self) -> Result<(), JsValue> {
-        self.grid.draw_labels(&self.camera)
+        self.grid.draw_labels(&self.camera)?;
+        self.hpx_grid.draw_labels()
     }
 
     pub(crate) fn draw(&mut self, force_render: bool) -> Result<(), JsValue> {
@@ -984,6 +1247,10 @@ impl App {
                 &self.projection,
                 &mut self.line_renderer,
             )?;
+            self.hpx_grid
+                .draw(&mut self.camera, &self.projection, &mut self.line_renderer)?;
+            self.tissot
+                .draw(&self.camera, &self.projection, &mut self.line_renderer);
             self.line_renderer.end();
             self.line_renderer.draw(&self.camera)?;
 
@@ -1006,6 +1273,11 @@ impl App {
         self.layers
             .remove_layer(layer, &mut self.camera, &self.projection)?;
 
+        // Free the HEALPix maps no longer displayed
+        let layers = &mut self.layers;
+        self.hpx_maps
+            .retain(|cdid, _| layers.get_hips_from_cdid(cdid).is_some());
+
         self.request_redraw = true;
 
         Ok(())
@@ -1042,11 +1314,54 @@ impl App {
         Ok(())
     }
 
+    fn add_healpix_map(&mut self, cfg: HEALPixMapCfg) -> Result<(), JsValue> {
+        let properties = cfg.get_properties();
+        let creator_did = properties.get_creator_did().to_string();
+        let HEALPixMapCfg {
+            layer, map, meta, ..
+        } = cfg;
+
+        self.layers.add_image_hips(
+            &self.gl,
+            HiPSCfg {
+                layer,
+                properties,
+                meta,
+            },
+            &mut self.camera,
+            &self.projection,
+        )?;
+
+        let hips = self
+            .layers
+            .get_mut_hips_from_cdid(&creator_did)
+            .ok_or_else(|| JsValue::from_str("HEALPix map layer not found"))?;
+        hips.get_config_mut()
+            .set_fits_metadata(1.0, 0.0, std::f32::NAN);
+
+        // The base tiles are generated at once so that the whole sky is always covered
+        let delta_depth = hips.get_config().delta_depth();
+        for texture_cell in crate::healpix::cell::ALLSKY_HPX_CELLS_D0 {
+            for cell in texture_cell.get_tile_cells(delta_depth) {
+                push_hpx_map_tile(hips, &map, &cell)?;
+            }
+        }
+
+        self.hpx_maps.insert(creator_did, map);
+
+        self.request_for_new_tiles = true;
+        self.request_redraw = true;
+
+        Ok(())
+    }
+
     pub(crate) fn add_image_fits(&mut self, cfg: FITSCfg) -> Result<js_sys::Promise, JsValue> {
         let FITSCfg { layer, url, meta } = cfg;
         let gl = self.gl.clone();
+        let horizontal_frame = *self.camera.get_horizontal_frame();
 
         let fits_sender = self.fits_send.clone();
+        let hpx_map_sender = self.hpx_map_send.clone();
         let ack_recv = self.ack_recv.clone();
         // Stop the current inertia
         self.inertia = None;
@@ -1057,7 +1372,7 @@ impl App {
         let fut = async move {
             use crate::renderable::image::Image;
             use futures::future::Either;
-            use futures::TryStreamExt;
+            use futures::{AsyncReadExt, TryStreamExt};
             use js_sys::Uint8Array;
             use wasm_streams::ReadableStream;
             use web_sys::window;
@@ -1079,7 +1394,7 @@ impl App {
             let body = ReadableStream::from_raw(raw_body.dyn_into()?);
 
             // Convert the JS ReadableStream to a Rust stream
-            let bytes_reader = match body.try_into_async_read() {
+            let mut bytes_reader = match body.try_into_async_read() {
                 Ok(async_read) => Either::Left(async_read),
                 Err((_err, body)) => Either::Right(
                     body.into_stream()
@@ -1093,6 +1408,39 @@ impl App {
                 ),
             };
 
+            // HEALPix maps are stored in a binary table extension, so the headers
+            // are read first to find out whether the file is one of them
+            let mut header_bytes = vec![];
+            if crate::healpix::map::is_healpix_map(&mut bytes_reader, &mut header_bytes).await {
+                let mut bytes = header_bytes;
+                bytes_reader
+                    .read_to_end(&mut bytes)
+                    .await
+                    .map_err(|e| JsValue::from_str(&format!("Fits file reading: reason: {}", e)))?;
+
+                let map = HEALPixMap::from_fits(bytes).map_err(|e| {
+                    JsValue::from_str(&format!("HEALPix map parsing: reason: {}", e))
+                })?;
+
+                hpx_map_sender
+                    .send(HEALPixMapCfg {
+                        layer,
+                        url,
+                        map,
+                        meta,
+                    })
+                    .await
+                    .unwrap();
+
+                let image_params = ack_recv
+                    .recv()
+                    .await
+                    .map_err(|_| JsValue::from_str("Problem receiving HEALPix map"))?;
+
+                return serde_wasm_bindgen::to_value(&[image_params]).map_err(|e| e.into());
+            }
+
+            let bytes_reader = futures::io::Cursor::new(header_bytes).chain(bytes_reader);
             let mut reader = BufReader::new(bytes_reader);
 
             let AsyncFits { mut hdu } = AsyncFits::from_reader(&mut reader)
@@ -1102,7 +1450,7 @@ impl App {
             let mut hdu_ext_idx = 0;
             let mut images_params = vec![];
 
-            match Image::from_fits_hdu_async(&gl, &mut hdu.0).await {
+            match Image::from_fits_hdu_async(&gl, &mut hdu.0, &horizontal_frame).await {
                 Ok(image) => {
                     let layer_ext = layer.clone();
                     let url_ext = url.clone();
@@ -1130,7 +1478,9 @@ impl App {
                     while let Ok(Some(mut xhdu)) = hdu_ext {
                         match &mut xhdu {
                             AsyncXtensionHDU::Image(xhdu_img) => {
-                                match Image::from_fits_hdu_async(&gl, xhdu_img).await {
+                                match Image::from_fits_hdu_async(&gl, xhdu_img, &horizontal_frame)
+                                    .await
+                                {
                                     Ok(image) => {
                                         let layer_ext =
                                             layer.clone() + "_ext_" + &format!("{hdu_ext_idx}");
@@ -1181,7 +1531,9 @@ impl App {
                     while let Ok(Some(mut xhdu)) = hdu_ext {
                         match &mut xhdu {
                             AsyncXtensionHDU::Image(xhdu_img) => {
-                                match Image::from_fits_hdu_async(&gl, xhdu_img).await {
+                                match Image::from_fits_hdu_async(&gl, xhdu_img, &horizontal_frame)
+                                    .await
+                                {
                                     Ok(image) => {
                                         let layer_ext =
                                             layer.clone() + "_ext_" + &format!("{hdu_ext_idx}");
@@ -1250,6 +1602,107 @@ impl App {
         Ok(promise)
     }
 
+    /// Add a HiPS from its url, its `properties` file being fetched and parsed first
+    ///
+    /// The returned promise resolves, once the layer has been added, to the key/value pairs
+    /// of the `properties` file, a key given several times being associated to the array of its values.
+    /// If `meta` gives no `imgFormat`, one of the formats of the HiPS is chosen
+    pub(crate) fn add_image_hips_from_url(
+        &mut self,
+        layer: String,
+        url: String,
+        meta: JsValue,
+    ) -> Result<js_sys::Promise, JsValue> {
+        let hips_sender = self.hips_send.clone();
+
+        let fut = async move {
+            use web_sys::window;
+            use web_sys::Response;
+            use web_sys::{Request, RequestInit, RequestMode};
+
+            // The url can be the one of the properties file itself
+            let url = url.trim_end_matches('/');
+            let url = url.strip_suffix("/properties").unwrap_or(url).to_string();
+            let properties_url = format!("{}/properties", url);
+
+            let mut opts = RequestInit::new();
+            opts.method("GET");
+            opts.mode(RequestMode::Cors);
+
+            let window = window().unwrap_abort();
+            let request = Request::new_with_str_and_init(&properties_url, &opts)?;
+
+            let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
+            let resp: Response = resp_value.dyn_into()?;
+            if !resp.ok() {
+                return Err(JsValue::from_str(&format!(
+                    "HiPS properties fetching: {} answered with the status {}",
+                    properties_url,
+                    resp.status()
+                )));
+            }
+
+            let text = JsFuture::from(resp.text()?)
+                .await?
+                .as_string()
+                .unwrap_or_default();
+            let file = PropertiesFile::parse(&text).map_err(|e| {
+                JsValue::from_str(&format!("HiPS properties parsing: reason: {}", e))
+            })?;
+            let properties = HiPSProperties::from_properties_file(url, &file).map_err(|e| {
+                JsValue::from_str(&format!("Incompatible HiPS properties: reason: {}", e))
+            })?;
+
+            let img_format = js_sys::Reflect::get(&meta, &"imgFormat".into())?;
+            if img_format.is_undefined() || img_format.is_null() {
+                let format = properties
+                    .get_default_format()
+                    .ok_or_else(|| JsValue::from_str("No tile format found for the HiPS"))?;
+                js_sys::Reflect::set(
+                    &meta,
+                    &"imgFormat".into(),
+                    &serde_wasm_bindgen::to_value(&format)?,
+                )?;
+            }
+            let meta: ImageMetadata = serde_wasm_bindgen::from_value(meta)?;
+
+            let entries = js_sys::Object::new();
+            for (key, _) in file.iter() {
+                let values = file.get_all(key).map(JsValue::from_str).collect::<Vec<_>>();
+                let value = if let [value] = &values[..] {
+                    value.clone()
+                } else {
+                    values.into_iter().collect::<js_sys::Array>().into()
+                };
+
+                js_sys::Reflect::set(&entries, &key.into(), &value)?;
+            }
+
+            let (ack_send, ack_recv) = async_channel::bounded(1);
+            hips_sender
+                .send((
+                    HiPSCfg {
+                        layer,
+                        properties,
+                        meta,
+                    },
+                    ack_send,
+                ))
+                .await
+                .map_err(|_| JsValue::from_str("Problem sending the HiPS"))?;
+
+            // Wait for the ack here
+            ack_recv
+                .recv()
+                .await
+                .map_err(|_| JsValue::from_str("Problem receiving the HiPS ack"))??;
+
+            Ok(entries.into())
+        };
+
+        Ok(wasm_bindgen_futures::future_to_promise(fut))
+    }
+
     pub(crate) fn get_layer_cfg(&self, layer: &str) -> Result<ImageMetadata, JsValue> {
         self.layers.get_layer_cfg(layer)
     }
@@ -1297,6 +1750,49 @@ impl App {
         Ok(())
     }
 
+    pub(crate) fn set_hips_cube_frame(
+        &mut self,
+        layer: &str,
+        cube_frame: u32,
+    ) -> Result<(), JsValue> {
+        let hips = self
+            .layers
+            .get_mut_hips_from_layer(layer)
+            .ok_or_else(|| JsValue::from_str("Layer not found"))?;
+        let is_kept = hips.set_cube_frame(cube_frame)?;
+
+        if !is_kept {
+            // Relaunch the base tiles for the new slice
+            self.tile_fetcher
+                .launch_starting_hips_requests(hips, &mut self.downloader);
+        }
+
+        // Request the tiles of the slice in the view
+        self.request_for_new_tiles = true;
+        self.request_redraw = true;
+
+        Ok(())
+    }
+
+    pub(crate) fn get_hips_config(&self, layer: &str) -> Result<&HiPSConfig, JsValue> {
+        self.layers
+            .get_hips_from_layer(layer)
+            .map(|hips| hips.get_config())
+            .ok_or_else(|| JsValue::from_str("Layer not found"))
+    }
+
+    pub(crate) fn set_resource_cache_budget(&mut self, budget: usize) {
+        self.downloader.set_cache_budget(budget);
+    }
+
+    pub(crate) fn get_resource_cache(&self) -> &Cache<QueryId, Resource> {
+        self.downloader.get_cache()
+    }
+
+    pub(crate) fn set_max_tile_requests(&mut self, max: usize, host: Option<String>) {
+        self.tile_fetcher.set_max_num_fetching(max, host.as_deref());
+    }
+
     // Width and height given are in pixels
     pub(crate) fn set_projection(&mut self, projection: ProjectionType) -> Result<(), JsValue> {
         self.projection = projection;
@@ -1367,6 +1863,16 @@ impl App {
         self.layers.set_survey_url(cdid, new_url)
     }
 
+    pub(crate) fn set_catalog_epoch(&mut self, epoch: Option<Epoch>) {
+        self.manager.set_epoch(epoch);
+
+        self.request_redraw = true;
+    }
+
+    pub(crate) fn get_catalog_epoch(&self) -> Option<Epoch> {
+        self.manager.get_epoch()
+    }
+
     pub(crate) fn set_catalog_opacity(
         &mut self,
         name: String,
@@ -1399,6 +1905,24 @@ impl App {
         Ok(())
     }
 
+    pub(crate) fn set_hpx_grid_cfg(&mut self, cfg: HEALPixGridCfg) -> Result<(), JsValue> {
+        self.hpx_grid
+            .set_cfg(cfg, &mut self.camera, &self.projection)?;
+        self.request_redraw = true;
+
+        Ok(())
+    }
+
+    pub(crate) fn set_tissot_cfg(&mut self, cfg: TissotCfg) {
+        self.tissot.set_cfg(cfg);
+        self.request_redraw = true;
+    }
+
+    pub(crate) fn get_distortion(&self, x: f64, y: f64) -> Option<Distortion> {
+        self.projection
+            .distortion_at(&Vector2::new(x, y), &self.camera)
+    }
+
     pub(crate) fn set_grid_cfg(&mut self, cfg: GridCfg) -> Result<(), JsValue> {
         self.grid.set_cfg(cfg, &self.camera, &self.projection)?;
         self.request_redraw = true;
@@ -1413,6 +1937,35 @@ impl App {
         self.request_redraw = true;
     }
 
+    pub(crate) fn set_observer(&mut self, observer: Observer) {
+        let mut horizontal_frame = *self.camera.get_horizontal_frame();
+        horizontal_frame.set_observer(observer);
+
+        self.set_horizontal_frame(horizontal_frame);
+    }
+
+    pub(crate) fn set_observation_time(&mut self, time: Epoch) {
+        let mut horizontal_frame = *self.camera.get_horizontal_frame();
+        horizontal_frame.set_time(time);
+
+        self.set_horizontal_frame(horizontal_frame);
+    }
+
+    pub(crate) fn get_horizontal_frame(&self) -> &HorizontalFrame {
+        self.camera.get_horizontal_frame()
+    }
+
+    // The sky moves in the horizontal frame whenever the observer or the time changes
+    fn set_horizontal_frame(&mut self, horizontal_frame: HorizontalFrame) {
+        self.camera
+            .set_horizontal_frame(horizontal_frame, &self.projection);
+
+        if self.camera.get_coo_system() == CooSystem::ALTAZ {
+            self.request_for_new_tiles = true;
+            self.request_redraw = true;
+        }
+    }
+
     pub(crate) fn world_to_screen(&self, ra: f64, dec: f64) -> Option<Vector2<f64>> {
         let lonlat = LonLatT::new(ArcDeg(ra).into(), ArcDeg(dec).into());
         let model_pos_xyz = lonlat.vector();
@@ -1428,6 +1981,79 @@ impl App {
             .map(|model_pos| model_pos.lonlat())
     }
 
+    /// Project positions onto the screen
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - Longitudes in degrees
+    /// * `lat` - Latitudes in degrees
+    /// * `frame` - The frame of the positions
+    ///
+    /// The screen positions are returned as interleaved (x, y) pixel coordinates.
+    /// Positions that cannot be projected, i.e. that lie behind the boundary
+    /// of the projection, are given as NaN
+    pub(crate) fn world_to_screen_vec(
+        &self,
+        lon: &[f64],
+        lat: &[f64],
+        frame: CooSystem,
+    ) -> Vec<f64> {
+        let view_frame = self.camera.get_coo_system();
+
+        lon.iter()
+            .zip(lat.iter())
+            .flat_map(|(&lon, &lat)| {
+                let xyzw = LonLatT::new(ArcDeg(lon).into(), ArcDeg(lat).into()).vector();
+                let model_pos = coosys::apply_coo_system(
+                    frame,
+                    view_frame,
+                    &xyzw,
+                    self.camera.get_horizontal_frame(),
+                );
+
+                self.projection
+                    .model_to_screen_space(&model_pos, &self.camera)
+                    .map_or([f64::NAN, f64::NAN], |xy| [xy.x, xy.y])
+            })
+            .collect()
+    }
+
+    /// Unproject screen positions
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Screen x coordinates in pixels
+    /// * `y` - Screen y coordinates in pixels
+    /// * `frame` - The frame of the returned positions
+    ///
+    /// The positions are returned as interleaved (lon, lat) in degrees with the longitude
+    /// in [0, 360[. Screen positions out of the projection definition domain are given as NaN
+    pub(crate) fn screen_to_world_vec(&self, x: &[f64], y: &[f64], frame: CooSystem) -> Vec<f64> {
+        let view_frame = self.camera.get_coo_system();
+
+        x.iter()
+            .zip(y.iter())
+            .flat_map(|(&x, &y)| {
+                self.projection
+                    .screen_to_model_space(&Vector2::new(x, y), &self.camera)
+                    .map_or([f64::NAN, f64::NAN], |model_pos| {
+                        let lonlat = coosys::apply_coo_system(
+                            view_frame,
+                            frame,
+                            &model_pos,
+                            self.camera.get_horizontal_frame(),
+                        )
+                        .lonlat();
+
+                        [
+                            lonlat.lon().to_degrees().rem_euclid(360.0),
+                            lonlat.lat().to_degrees(),
+                        ]
+                    })
+            })
+            .collect()
+    }
+
     pub(crate) fn screen_to_clip(&self, pos: &Vector2<f64>) -> Vector2<f64> {
         // Select the HiPS layer rendered lastly
         crate::math::projection::screen_to_clip_space(pos, &self.camera)
@@ -1444,6 +2070,7 @@ impl App {
             view_system,
             CooSystem::ICRS,
             &icrs_pos,
+            self.camera.get_horizontal_frame(),
         ));
 
         LonLatT::new(ra, dec)
@@ -1565,6 +2192,45 @@ impl App {
         self.request_redraw = true;
     }
 
+    /// Make the view adopt the WCS of a FITS image layer.
+    ///
+    /// The view takes the projection, the frame, the reference point and the orientation
+    /// of the image WCS and is zoomed so that an image pixel covers a screen pixel.
+    /// HiPS, MOCs and catalogs are then reprojected onto that WCS
+    pub(crate) fn set_native_wcs(&mut self, layer: &str) -> Result<NativeWCS, JsValue> {
+        let native_wcs = self
+            .layers
+            .get_image_from_layer(layer)
+            .ok_or_else(|| JsValue::from_str(&format!("{} is not a FITS image layer", layer)))?
+            .get_native_wcs()
+            .cloned()
+            .ok_or_else(|| JsValue::from_str("The image does not have a celestial WCS"))?;
+
+        let projection = ProjectionType::from_name(
+            native_wcs.get_projection_name(),
+            native_wcs.get_projection_params(),
+        )?;
+        self.set_projection(projection)?;
+        self.set_coo_system(native_wcs.get_coo_system());
+
+        self.camera
+            .set_longitude_reversed(native_wcs.is_longitude_reversed(), &self.projection);
+        self.camera.set_center(
+            native_wcs.get_crval(),
+            native_wcs.get_coo_system(),
+            &self.projection,
+        );
+        self.camera
+            .set_rotation_around_center(native_wcs.rotation().to_angle(), &self.projection);
+
+        let aperture = self
+            .camera
+            .aperture_from_pixel_scale(native_wcs.pixel_scale(), &self.projection);
+        self.set_fov(aperture);
+
+        Ok(native_wcs)
+    }
+
     pub(crate) fn set_inertia(&mut self, inertia: bool) {
         *self.disable_inertia.borrow_mut() = !inertia;
     }
@@ -1644,3 +2310,13 @@ impl App {
         self.rendering
     }
 }
+
+// Generate the tile of a HEALPix map and push it to its HiPS
+fn push_hpx_map_tile(hips: &mut HiPS, map: &HEALPixMap, cell: &HEALPixCell) -> Result<(), JsValue> {
+    use al_core::image::{format::R32F, raw::ImageBuffer, ImageType};
+
+    let tile_size = hips.get_config().get_tile_size();
+    let image = ImageBuffer::<R32F>::new(map.tile(cell, tile_size), tile_size, tile_size);
+
+    hips.add_tile(cell, Some(ImageType::RawR32f { image }), Time::now())
+}
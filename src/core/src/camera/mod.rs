@@ -7,6 +7,7 @@ pub mod fov;
 pub use fov::FieldOfView;
 pub mod view_hpx_cells;
 
+use crate::altaz::HorizontalFrame;
 use crate::CooSystem;
 use crate::HEALPixCoverage;
 use crate::ProjectionType;
@@ -17,6 +18,7 @@ pub fn build_fov_coverage(
     camera_center: &XYZWModel,
     camera_frame: CooSystem,
     frame: CooSystem,
+    horizontal_frame: &HorizontalFrame,
     proj: &ProjectionType,
 ) -> HEALPixCoverage {
     if let Some(vertices) = fov.get_vertices() {
@@ -24,7 +26,7 @@ pub fn build_fov_coverage(
         // but cdshealpix accepts them to be given in ICRS coo sys
         let vertices_iter = vertices
             .iter()
-            .map(|v| crate::coosys::apply_coo_system(camera_frame, frame, v));
+            .map(|v| crate::coosys::apply_coo_system(camera_frame, frame, v, horizontal_frame));
 
         // Check if the polygon is too small with respect to the angular size
         // of a cell at depth order
@@ -47,7 +49,12 @@ pub fn build_fov_coverage(
             HEALPixCoverage::from_fixed_hpx_cells(depth, hpx_idxs_iter, Some(vertices.len()))
         } else {
             // The polygon is not too small for the depth asked
-            let inside_vertex = crate::coosys::apply_coo_system(camera_frame, frame, camera_center);
+            let inside_vertex = crate::coosys::apply_coo_system(
+                camera_frame,
+                frame,
+                camera_center,
+                horizontal_frame,
+            );
 
             // Prefer to query from_polygon with depth >= 2
             let moc = HEALPixCoverage::from_3d_coos(depth, vertices_iter, &inside_vertex);
@@ -18,7 +18,7 @@ pub fn project(
     projection: &ProjectionType,
 ) -> Option<HEALPixCellProjeted> {
     match projection {
-        /*ProjectionType::Hpx(_) => {
+        ProjectionType::Hpx(_) => {
             let tri_idx_in_collignon_zone = |x: f64, y: f64| -> u8 {
                 let zoom_factor = camera.get_clip_zoom_factor() as f32;
                 let x = (((x as f32) / camera.get_width()) - 0.5) * zoom_factor;
@@ -54,7 +54,7 @@ pub fn project(
             } else {
                 Some(cell)
             }
-        }*/
+        }
         _ => Some(cell),
     }
 }
@@ -70,6 +70,11 @@ impl ViewHpxCells {
         let hpx_cells = [
             HpxCells::new(CooSystem::ICRS),
             HpxCells::new(CooSystem::GAL),
+            HpxCells::new(CooSystem::ECL),
+            HpxCells::new(CooSystem::SGAL),
+            HpxCells::new(CooSystem::FK5J2000),
+            HpxCells::new(CooSystem::FK4B1950),
+            HpxCells::new(CooSystem::ALTAZ),
         ];
 
         Self {
@@ -84,6 +89,7 @@ impl ViewHpxCells {
         fov: &FieldOfView,
         center: &XYZWModel,
         camera_frame: CooSystem,
+        horizontal_frame: &HorizontalFrame,
         proj: &ProjectionType,
         // survey frame
         frame: CooSystem,
@@ -92,7 +98,14 @@ impl ViewHpxCells {
 
         if self.reg_frames[frame as usize] == 1 {
             // a new frame has been added
-            self.update(camera_depth, fov, center, camera_frame, proj);
+            self.update(
+                camera_depth,
+                fov,
+                center,
+                camera_frame,
+                horizontal_frame,
+                proj,
+            );
         }
     }
 
@@ -102,6 +115,7 @@ impl ViewHpxCells {
         fov: &FieldOfView,
         center: &XYZWModel,
         camera_frame: CooSystem,
+        horizontal_frame: &HorizontalFrame,
         proj: &ProjectionType,
         // survey frame
         frame: CooSystem,
@@ -112,7 +126,14 @@ impl ViewHpxCells {
 
         if self.reg_frames[frame as usize] == 0 {
             // a frame has been deleted
-            self.update(camera_depth, fov, center, camera_frame, proj);
+            self.update(
+                camera_depth,
+                fov,
+                center,
+                camera_frame,
+                horizontal_frame,
+                proj,
+            );
         }
     }
 
@@ -122,12 +143,20 @@ impl ViewHpxCells {
         fov: &FieldOfView,
         center: &XYZWModel,
         camera_frame: CooSystem,
+        horizontal_frame: &HorizontalFrame,
         proj: &ProjectionType,
     ) {
         for (frame, num_req) in self.reg_frames.iter().enumerate() {
             // if there are surveys/camera requesting the coverage
             if *num_req > 0 {
-                self.hpx_cells[frame].update(camera_depth, fov, center, camera_frame, proj);
+                self.hpx_cells[frame].update(
+                    camera_depth,
+                    fov,
+                    center,
+                    camera_frame,
+                    horizontal_frame,
+                    proj,
+                );
             }
         }
     }
@@ -162,6 +191,7 @@ impl Default for HpxCells {
     }
 }
 
+use crate::altaz::HorizontalFrame;
 use crate::camera::CameraViewPort;
 use al_api::coo_system::{CooSystem, NUM_COOSYSTEM};
 
@@ -193,11 +223,19 @@ impl HpxCells {
         fov: &FieldOfView,
         center: &XYZWModel,
         camera_frame: CooSystem,
+        horizontal_frame: &HorizontalFrame,
         proj: &ProjectionType,
     ) {
         // Compute the new coverage for that frame
-        self.cov =
-            super::build_fov_coverage(camera_depth, fov, center, camera_frame, self.frame, proj);
+        self.cov = super::build_fov_coverage(
+            camera_depth,
+            fov,
+            center,
+            camera_frame,
+            self.frame,
+            horizontal_frame,
+            proj,
+        );
 
         // Clear the old cells
         self.cells.clear();
@@ -67,12 +67,15 @@ pub struct CameraViewPort {
     // A reference to the WebGL2 context
     gl: WebGlContext,
     coo_sys: CooSystem,
+    // The observer and the time defining the horizontal frame
+    horizontal_frame: HorizontalFrame,
     reversed_longitude: bool,
 }
 use al_api::coo_system::CooSystem;
 use al_core::WebGlContext;
 
 use crate::{
+    altaz::HorizontalFrame,
     coosys,
     math::{angle::Angle, projection::Projection, rotation::Rotation},
 };
@@ -90,6 +93,7 @@ impl CameraViewPort {
     pub fn new(
         gl: &WebGlContext,
         coo_sys: CooSystem,
+        horizontal_frame: HorizontalFrame,
         projection: &ProjectionType,
     ) -> CameraViewPort {
         let last_user_action = UserAction::Starting;
@@ -179,6 +183,7 @@ impl CameraViewPort {
             gl,
             // coo system
             coo_sys,
+            horizontal_frame,
             // a flag telling if the viewport has a reversed longitude axis
             reversed_longitude,
         }
@@ -190,6 +195,7 @@ impl CameraViewPort {
             &self.fov,
             &self.center,
             self.coo_sys,
+            &self.horizontal_frame,
             proj,
             frame,
         );
@@ -201,6 +207,7 @@ impl CameraViewPort {
             &self.fov,
             &self.center,
             self.coo_sys,
+            &self.horizontal_frame,
             proj,
             frame,
         );
@@ -227,13 +234,24 @@ impl CameraViewPort {
 
         // check the projection
         match proj {
-            ProjectionType::Tan(_) => self.aperture >= 100.0_f64.to_radians().to_angle(),
-            ProjectionType::Mer(_) => self.aperture >= 200.0_f64.to_radians().to_angle(),
-            ProjectionType::Stg(_) => self.aperture >= 200.0_f64.to_radians().to_angle(),
-            ProjectionType::Sin(_) => false,
-            ProjectionType::Ait(_) => false,
-            ProjectionType::Mol(_) => false,
-            ProjectionType::Zea(_) => false,
+            ProjectionType::Tan(_) | ProjectionType::Azp(_) => {
+                self.aperture >= 100.0_f64.to_radians().to_angle()
+            }
+            ProjectionType::Mer(_) | ProjectionType::Stg(_) | ProjectionType::Air(_) => {
+                self.aperture >= 200.0_f64.to_radians().to_angle()
+            }
+            ProjectionType::Sin(_)
+            | ProjectionType::Zea(_)
+            | ProjectionType::Feye(_)
+            | ProjectionType::Arc(_)
+            | ProjectionType::Ncp(_)
+            | ProjectionType::Zpn(_) => false,
+            ProjectionType::Ait(_)
+            | ProjectionType::Mol(_)
+            | ProjectionType::Par(_)
+            | ProjectionType::Sfl(_) => false,
+            ProjectionType::Car(_) | ProjectionType::Cea(_) | ProjectionType::Cyp(_) => false,
+            ProjectionType::Cod(_) | ProjectionType::Hpx(_) => false,
         }
     }
 
@@ -344,12 +362,13 @@ impl CameraViewPort {
         let can_unzoom_more = match proj {
             ProjectionType::Tan(_)
             | ProjectionType::Mer(_)
-            //| ProjectionType::Air(_)
-            | ProjectionType::Stg(_) => false,
-            //| ProjectionType::Car(_)
-            //| ProjectionType::Cea(_)
-            //| ProjectionType::Cyp(_)
-            //| ProjectionType::Hpx(_) => false,
+            | ProjectionType::Air(_)
+            | ProjectionType::Azp(_)
+            | ProjectionType::Stg(_)
+            | ProjectionType::Car(_)
+            | ProjectionType::Cea(_)
+            | ProjectionType::Cyp(_)
+            | ProjectionType::Hpx(_) => false,
             _ => true,
         };
 
@@ -408,10 +427,34 @@ impl CameraViewPort {
             &self.fov,
             &self.center,
             self.get_coo_system(),
+            &self.horizontal_frame,
             proj,
         );
     }
 
+    /// Aperture for which a pixel of the screen covers a given angular size
+    ///
+    /// # Arguments
+    ///
+    /// * `pixel_scale` - Size of a screen pixel in radians
+    pub fn aperture_from_pixel_scale(&self, pixel_scale: f64, proj: &ProjectionType) -> Angle<f64> {
+        // A screen pixel covers 2 / width in NDC, i.e. ndc_to_clip.x * czf * x_len / width
+        // in the projection plane. The scale of the projection plane is the one of the sphere
+        // at the center of projection
+        let x_len = proj.bounds_size().x;
+        let clip_zoom_factor = pixel_scale * (self.width as f64) / (self.ndc_to_clip.x * x_len);
+
+        // Find the aperture giving that clip zoom factor (see set_aperture)
+        let center = Vector4::new(0.0, 0.0, 1.0, 1.0);
+        let half_aperture = proj
+            .world_to_clip_space(&center)
+            .and_then(|c| proj.clip_to_world_space(&(c + Vector2::new(clip_zoom_factor, 0.0))))
+            .map(|v| math::vector::angle3(&v.truncate(), &center.truncate()).to_radians())
+            .unwrap_or(std::f64::consts::PI);
+
+        Angle(2.0 * half_aperture)
+    }
+
     fn compute_texture_depth(&mut self) {
         /*// Compute a depth from a number of pixels on screen
         let width = self.width;
@@ -477,7 +520,12 @@ impl CameraViewPort {
     pub fn set_center(&mut self, lonlat: &LonLatT<f64>, coo_sys: CooSystem, proj: &ProjectionType) {
         let icrs_pos: Vector4<_> = lonlat.vector();
 
-        let view_pos = coosys::apply_coo_system(coo_sys, self.get_coo_system(), &icrs_pos);
+        let view_pos = coosys::apply_coo_system(
+            coo_sys,
+            self.get_coo_system(),
+            &icrs_pos,
+            &self.horizontal_frame,
+        );
         let rot = Rotation::from_sky_position(&view_pos);
 
         // Apply the rotation to the camera to go
@@ -497,7 +545,12 @@ impl CameraViewPort {
 
     pub fn set_coo_system(&mut self, new_coo_sys: CooSystem, proj: &ProjectionType) {
         // Compute the center position according to the new coordinate frame system
-        let new_center = coosys::apply_coo_system(self.coo_sys, new_coo_sys, &self.center);
+        let new_center = coosys::apply_coo_system(
+            self.coo_sys,
+            new_coo_sys,
+            &self.center,
+            &self.horizontal_frame,
+        );
         // Create a rotation object from that position
         let new_rotation = Rotation::from_sky_position(&new_center);
         // Apply it to the center of the view
@@ -513,6 +566,7 @@ impl CameraViewPort {
             &self.fov,
             &self.center,
             new_coo_sys,
+            &self.horizontal_frame,
             proj,
         );
 
@@ -616,6 +670,26 @@ impl CameraViewPort {
         self.coo_sys
     }
 
+    /// Change the observer or the time of the horizontal frame.
+    /// The sky moves in the view if it is the horizontal one
+    pub fn set_horizontal_frame(
+        &mut self,
+        horizontal_frame: HorizontalFrame,
+        proj: &ProjectionType,
+    ) {
+        self.horizontal_frame = horizontal_frame;
+
+        if self.coo_sys == CooSystem::ALTAZ {
+            // The view keeps its alt/az center but the layers and the
+            // coverages of the other frames must be recomputed
+            self.update_rot_matrices(proj);
+        }
+    }
+
+    pub fn get_horizontal_frame(&self) -> &HorizontalFrame {
+        &self.horizontal_frame
+    }
+
     pub fn set_rotation_around_center(&mut self, theta: Angle<f64>, proj: &ProjectionType) {
         self.rotation_center_angle = theta;
         self.update_rot_matrices(proj);
@@ -649,6 +723,7 @@ impl CameraViewPort {
             &self.fov,
             &self.center,
             self.get_coo_system(),
+            &self.horizontal_frame,
             proj,
         );
     }
@@ -3,21 +3,29 @@ use cgmath::{BaseFloat, Vector4};
 use al_api::coo_system::CooBaseFloat;
 use al_api::coo_system::CooSystem;
 
+use crate::altaz::HorizontalFrame;
 use crate::math::lonlat::LonLat;
 /// This is conversion method returning a transformation
 /// matrix when the system requested by the user is not
 /// icrs j2000.
 /// The core projections are always performed in icrs j2000
 /// so one must call these methods to convert them to icrs before.
+/// `horizontal_frame` gives the orientation of the horizontal frame, usually the one of the camera
 #[inline]
-pub fn apply_coo_system<S>(c1: CooSystem, c2: CooSystem, v: &Vector4<S>) -> Vector4<S>
+pub fn apply_coo_system<S>(
+    c1: CooSystem,
+    c2: CooSystem,
+    v: &Vector4<S>,
+    horizontal_frame: &HorizontalFrame,
+) -> Vector4<S>
 where
     S: BaseFloat + CooBaseFloat,
 {
-    let c1_2_c2_mat = c1.to::<S>(c2);
-    c1_2_c2_mat * (*v)
+    let c1_2_c2_mat = c1.to::<S>(c2, horizontal_frame.icrs_to_altaz());
+    c1_2_c2_mat * v
 }
 
+#[cfg(test)]
 mod tests {
     #[allow(unused_macros)]
     macro_rules! assert_delta {
@@ -28,6 +36,14 @@ mod tests {
         };
     }
 
+    // The conversions between celestial frames do not depend on the observer
+    fn horizontal_frame() -> crate::altaz::HorizontalFrame {
+        use crate::altaz::{HorizontalFrame, Observer};
+        use crate::epoch::Epoch;
+
+        HorizontalFrame::new(Observer::default(), Epoch::J2000)
+    }
+
     #[test]
     fn j2000_to_gal() {
         use super::CooSystem;
@@ -36,8 +52,13 @@ mod tests {
         use crate::LonLatT;
 
         let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
-        let gal_lonlat =
-            super::apply_coo_system(CooSystem::ICRS, CooSystem::GAL, &lonlat.vector()).lonlat();
+        let gal_lonlat = super::apply_coo_system(
+            CooSystem::ICRS,
+            CooSystem::GAL,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
 
         let gal_lon_deg = gal_lonlat.lon().0 * 360.0 / (2.0 * std::f64::consts::PI);
         let gal_lat_deg = gal_lonlat.lat().0 * 360.0 / (2.0 * std::f64::consts::PI);
@@ -54,8 +75,13 @@ mod tests {
         use crate::LonLatT;
 
         let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
-        let j2000_lonlat =
-            super::apply_coo_system(CooSystem::GAL, CooSystem::ICRS, &lonlat.vector()).lonlat();
+        let j2000_lonlat = super::apply_coo_system(
+            CooSystem::GAL,
+            CooSystem::ICRS,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
         let j2000_lon_deg = j2000_lonlat.lon().0 * 360.0 / (2.0 * std::f64::consts::PI);
         let j2000_lat_deg = j2000_lonlat.lat().0 * 360.0 / (2.0 * std::f64::consts::PI);
 
@@ -72,10 +98,19 @@ mod tests {
 
         let gal_lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
 
-        let icrs_pos =
-            super::apply_coo_system(CooSystem::GAL, CooSystem::ICRS, &gal_lonlat.vector());
+        let icrs_pos = super::apply_coo_system(
+            CooSystem::GAL,
+            CooSystem::ICRS,
+            &gal_lonlat.vector(),
+            &horizontal_frame(),
+        );
 
-        let gal_lonlat = super::apply_coo_system(CooSystem::ICRS, CooSystem::GAL, &icrs_pos);
+        let gal_lonlat = super::apply_coo_system(
+            CooSystem::ICRS,
+            CooSystem::GAL,
+            &icrs_pos,
+            &horizontal_frame(),
+        );
 
         let gal_lon_deg = gal_lonlat.lon().0 * 360.0 / (2.0 * std::f64::consts::PI);
         let gal_lat_deg = gal_lonlat.lat().0 * 360.0 / (2.0 * std::f64::consts::PI);
@@ -83,4 +118,142 @@ mod tests {
         assert_delta!(gal_lon_deg, 0.0, 1e-3);
         assert_delta!(gal_lat_deg, 0.0, 1e-3);
     }
+
+    fn ang_dist_deg(lonlat: &crate::LonLatT<f64>, lon_deg: f64, lat_deg: f64) -> f64 {
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        use cgmath::{InnerSpace, Vector3};
+
+        let expected: LonLatT<f64> = LonLatT::new(ArcDeg(lon_deg).into(), ArcDeg(lat_deg).into());
+        let v1: Vector3<f64> = lonlat.vector();
+        let v2: Vector3<f64> = expected.vector();
+
+        v1.cross(v2).magnitude().atan2(v1.dot(v2)).to_degrees()
+    }
+
+    #[test]
+    fn j2000_to_ecliptic() {
+        use super::CooSystem;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        // The vernal equinox is common to both frames
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
+        let ecl_lonlat = super::apply_coo_system(
+            CooSystem::ICRS,
+            CooSystem::ECL,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+        assert!(ang_dist_deg(&ecl_lonlat, 0.0, 0.0) < 1e-6);
+
+        // The equator is tilted by the obliquity of the ecliptic
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(90.0).into(), ArcDeg(0.0).into());
+        let ecl_lonlat = super::apply_coo_system(
+            CooSystem::ICRS,
+            CooSystem::ECL,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+        assert!(ang_dist_deg(&ecl_lonlat, 90.0, -23.43927944) < 1e-6);
+    }
+
+    #[test]
+    fn supergal_to_j2000() {
+        use super::CooSystem;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        // Supergalactic north pole
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(90.0).into());
+        let j2000_lonlat = super::apply_coo_system(
+            CooSystem::SGAL,
+            CooSystem::ICRS,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+        assert!(ang_dist_deg(&j2000_lonlat, 283.75409034, 15.70893553) < 1e-6);
+
+        // Supergalactic origin
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
+        let j2000_lonlat = super::apply_coo_system(
+            CooSystem::SGAL,
+            CooSystem::ICRS,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+        assert!(ang_dist_deg(&j2000_lonlat, 42.31012266, 59.52834978) < 1e-6);
+    }
+
+    #[test]
+    fn fk4_to_j2000() {
+        use super::CooSystem;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(0.0).into());
+        let j2000_lonlat = super::apply_coo_system(
+            CooSystem::FK4B1950,
+            CooSystem::ICRS,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+        assert!(ang_dist_deg(&j2000_lonlat, 0.64070581, 0.27834101) < 1e-6);
+    }
+
+    #[test]
+    fn fk5_is_close_to_icrs() {
+        use super::CooSystem;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(83.63308).into(), ArcDeg(22.0145).into());
+        let fk5_lonlat = super::apply_coo_system(
+            CooSystem::ICRS,
+            CooSystem::FK5J2000,
+            &lonlat.vector(),
+            &horizontal_frame(),
+        )
+        .lonlat();
+
+        // The two frames are aligned to a few tens of mas
+        assert!(ang_dist_deg(&fk5_lonlat, 83.63308, 22.0145) < 1e-5);
+    }
+
+    #[test]
+    fn all_frames_roundtrip() {
+        use super::CooSystem;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        let frames = [
+            CooSystem::ICRS,
+            CooSystem::GAL,
+            CooSystem::ECL,
+            CooSystem::SGAL,
+            CooSystem::FK5J2000,
+            CooSystem::FK4B1950,
+        ];
+
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(123.4).into(), ArcDeg(-56.7).into());
+        for &c1 in frames.iter() {
+            for &c2 in frames.iter() {
+                let pos = super::apply_coo_system(c1, c2, &lonlat.vector(), &horizontal_frame());
+                let lonlat_c1 = super::apply_coo_system(c2, c1, &pos, &horizontal_frame()).lonlat();
+
+                assert!(ang_dist_deg(&lonlat_c1, 123.4, -56.7) < 1e-6);
+            }
+        }
+    }
 }
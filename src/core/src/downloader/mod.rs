@@ -1,5 +1,6 @@
 pub mod query;
 pub mod request;
+pub mod retry;
 
 use crate::renderable::Url;
 use std::collections::HashSet;
@@ -15,16 +16,16 @@ pub struct Downloader {
     queried_cached_ids: Vec<QueryId>,
 }
 
-use crate::fifo_cache::Cache;
+use crate::lru_cache::{Cache, DEFAULT_CACHE_BUDGET};
 
 use query::Query;
-use request::{RequestType, Resource};
+use request::{tile::TileRequest, RequestType, Resource};
 
 impl Downloader {
     pub fn new() -> Downloader {
         let requests = Vec::with_capacity(32);
         let queried_list = HashSet::with_capacity(64);
-        let cache = Cache::new();
+        let cache = Cache::new(DEFAULT_CACHE_BUDGET);
         let queried_cached_ids = Vec::with_capacity(64);
         Self {
             requests,
@@ -34,29 +35,30 @@ impl Downloader {
         }
     }
     // Returns true if the fetch has been done
-    // Returns false if the query has already been done
+    // Returns false if the query is already in flight or its resource is cached.
+    // A cached resource is given back by the next call to get_received_resources
     pub fn fetch<T>(&mut self, query: T) -> bool
     where
         T: Query,
     {
-        let id = query.id();
-        if self.cache.contains(id) {
-            //self.queried_cached_urls.push(url.clone());
-            false
-        } else {
-            let query_id = query.id();
+        let query_id = query.id();
 
-            let not_already_requested = !self.queried_list.contains(query_id);
+        // The query is already requested or its resource is about to be given back
+        if self.queried_list.contains(query_id) || self.queried_cached_ids.contains(query_id) {
+            return false;
+        }
 
-            // The cell is not already requested
-            if not_already_requested {
-                self.queried_list.insert(query_id.to_string());
+        if self.cache.get(query_id).is_some() {
+            self.queried_cached_ids.push(query_id.clone());
 
-                let request = T::Request::from(query);
-                self.requests.push(request.into());
-            }
+            false
+        } else {
+            self.queried_list.insert(query_id.to_string());
+
+            let request = T::Request::from(query);
+            self.requests.push(request.into());
 
-            not_already_requested
+            true
         }
     }
 
@@ -85,9 +87,19 @@ impl Downloader {
             self.queried_list.remove(&query_id);
         }
 
+        // The received resources are kept in the cache until they are evicted.
+        // A tile that could not be fetched must be requested again
+        for rsc in &rscs {
+            let failed = matches!(rsc, Resource::Tile(tile) if tile.get_error().is_some());
+            if !failed {
+                self.cache
+                    .insert(rsc.id().clone(), rsc.clone(), rsc.byte_size());
+            }
+        }
+
         while let Some(id) = self.queried_cached_ids.pop() {
-            if let Some(rsc) = self.cache.extract(&id) {
-                rscs.push(rsc);
+            if let Some(rsc) = self.cache.peek(&id) {
+                rscs.push(rsc.clone());
             }
         }
 
@@ -98,14 +110,203 @@ impl Downloader {
         self.queried_list.contains(id)
     }
 
+    /// Number of tile requests in flight to a host
+    pub fn num_tile_requests(&self, host: &str) -> usize {
+        self.requests
+            .iter()
+            .filter(|request| match request {
+                RequestType::Tile(tile) => {
+                    !tile.is_resolved() && query::host(tile.get_url()) == host
+                }
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Abort the tile requests in flight that are not kept
+    ///
+    /// The aborted tiles can be queried again later.
+    pub fn cancel_tile_requests<F>(&mut self, keep: F)
+    where
+        F: FnMut(&TileRequest) -> bool,
+    {
+        cancel_tile_requests(
+            &mut self.requests,
+            &mut self.queried_list,
+            tile_request,
+            keep,
+        );
+    }
+
     //pub fn get_cached_resources(&mut self) -> Vec<Resource> {}
 
     /*pub fn cache_rsc(&mut self, rsc: Resource) {
         self.cache.insert(rsc.url().clone(), rsc);
     }*/
 
+    // Give back a resource at the next call to get_received_resources
     pub fn delay_rsc(&mut self, rsc: Resource) {
-        self.queried_cached_ids.push(rsc.id().clone());
-        self.cache.insert(rsc.id().clone(), rsc);
+        let id = rsc.id().clone();
+        // The resource may have been evicted from the cache
+        if !self.cache.contains(&id) {
+            let size = rsc.byte_size();
+            self.cache.insert(id.clone(), rsc, size);
+        }
+
+        if !self.queried_cached_ids.contains(&id) {
+            self.queried_cached_ids.push(id);
+        }
+    }
+
+    /// Set the memory budget of the resources cache, in bytes
+    pub fn set_cache_budget(&mut self, budget: usize) {
+        self.cache.set_budget(budget);
+    }
+
+    pub fn get_cache(&self) -> &Cache<QueryId, Resource> {
+        &self.cache
+    }
+}
+
+fn tile_request(request: &RequestType) -> Option<&TileRequest> {
+    match request {
+        RequestType::Tile(tile) => Some(tile),
+        _ => None,
+    }
+}
+
+/// A tile request that can be cancelled, this is where the network layer can be mocked
+pub trait InFlightTile {
+    fn id(&self) -> &QueryId;
+    fn is_resolved(&self) -> bool;
+    fn abort(&self);
+}
+
+impl InFlightTile for TileRequest {
+    fn id(&self) -> &QueryId {
+        &self.id
+    }
+
+    fn is_resolved(&self) -> bool {
+        TileRequest::is_resolved(self)
+    }
+
+    fn abort(&self) {
+        TileRequest::abort(self)
+    }
+}
+
+/// Abort the tile requests that are neither resolved nor kept and remove them from the requests
+///
+/// Their ids are removed from the queried ones so that the tiles can be queried again.
+/// `as_tile` gives the tile requests among the requests
+pub fn cancel_tile_requests<R, T, F>(
+    requests: &mut Vec<R>,
+    queried_list: &mut HashSet<QueryId>,
+    as_tile: fn(&R) -> Option<&T>,
+    mut keep: F,
+) where
+    T: InFlightTile,
+    F: FnMut(&T) -> bool,
+{
+    requests.retain(|request| match as_tile(request) {
+        Some(tile) if !tile.is_resolved() && !keep(tile) => {
+            tile.abort();
+            queried_list.remove(tile.id());
+
+            false
+        }
+        _ => true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cancel_tile_requests, InFlightTile, QueryId};
+    use std::cell::Cell;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    enum MockRequest {
+        Tile(MockTile),
+        Allsky(QueryId),
+    }
+
+    struct MockTile {
+        id: QueryId,
+        resolved: bool,
+        aborted: Rc<Cell<bool>>,
+    }
+
+    impl InFlightTile for MockTile {
+        fn id(&self) -> &QueryId {
+            &self.id
+        }
+
+        fn is_resolved(&self) -> bool {
+            self.resolved
+        }
+
+        fn abort(&self) {
+            self.aborted.set(true);
+        }
+    }
+
+    fn as_tile(request: &MockRequest) -> Option<&MockTile> {
+        match request {
+            MockRequest::Tile(tile) => Some(tile),
+            _ => None,
+        }
+    }
+
+    // A tile request with the flag telling whether it has been aborted
+    fn tile(id: &str, resolved: bool) -> (MockRequest, Rc<Cell<bool>>) {
+        let aborted = Rc::new(Cell::new(false));
+        let tile = MockTile {
+            id: id.to_string(),
+            resolved,
+            aborted: aborted.clone(),
+        };
+
+        (MockRequest::Tile(tile), aborted)
+    }
+
+    #[test]
+    fn cancel_tiles() {
+        let (kept, kept_aborted) = tile("kept", false);
+        let (cancelled, cancelled_aborted) = tile("cancelled", false);
+        let (resolved, resolved_aborted) = tile("resolved", true);
+        let mut requests = vec![
+            kept,
+            cancelled,
+            resolved,
+            MockRequest::Allsky("allsky".to_string()),
+        ];
+        let mut queried_list = ["kept", "cancelled", "resolved", "allsky"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<HashSet<_>>();
+
+        // only the tile of id "kept" is still needed
+        cancel_tile_requests(&mut requests, &mut queried_list, as_tile, |tile| {
+            tile.id == "kept"
+        });
+
+        let ids = requests
+            .iter()
+            .map(|request| match request {
+                MockRequest::Tile(tile) => tile.id.as_str(),
+                MockRequest::Allsky(id) => id.as_str(),
+            })
+            .collect::<Vec<_>>();
+        // the resolved tiles are given back later, the other requests are not tiles
+        assert_eq!(ids, vec!["kept", "resolved", "allsky"]);
+        assert!(cancelled_aborted.get());
+        assert!(!kept_aborted.get());
+        assert!(!resolved_aborted.get());
+
+        // the cancelled tile can be queried again
+        assert!(!queried_list.contains("cancelled"));
+        assert_eq!(queried_list.len(), 3);
     }
 }
@@ -1,5 +1,12 @@
 pub type Url = String;
 
+/// The host of an url with its port, e.g. alasky.cds.unistra.fr for https://alasky.cds.unistra.fr/DSS
+pub fn host(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, url)| url);
+
+    url.split(['/', '?', '#']).next().unwrap_or(url)
+}
+
 use super::request::RequestType;
 pub trait Query: Sized {
     type Request: From<Self> + Into<RequestType>;
@@ -10,6 +17,7 @@ pub trait Query: Sized {
 
 pub type QueryId = String;
 
+use al_api::hips::ImageExt;
 use al_core::image::format::ImageFormatType;
 use al_core::log::console_log;
 #[derive(Eq, Hash, PartialEq, Clone)]
@@ -21,6 +29,8 @@ pub struct Tile {
     // The total url of the query
     pub url: Url,
     pub id: QueryId,
+    // The slice of a HiPS cube the tile belongs to, 0 for a plain HiPS
+    pub cube_frame: u32,
 }
 
 use crate::renderable::CreatorDid;
@@ -31,6 +41,7 @@ impl Tile {
         hips_cdid: String,
         hips_url: String,
         format: ImageFormatType,
+        cube_frame: u32,
     ) -> Self {
         let ext = format.get_ext_file();
 
@@ -39,11 +50,23 @@ impl Tile {
         let dir_idx = (idx / 10000) * 10000;
 
         let url = format!(
-            "{}/Norder{}/Dir{}/Npix{}.{}",
-            hips_url, depth, dir_idx, idx, ext
+            "{}/Norder{}/Dir{}/Npix{}{}.{}",
+            hips_url,
+            depth,
+            dir_idx,
+            idx,
+            cube_frame_suffix(cube_frame),
+            ext
         );
 
-        let id = format!("{}{}{}{}", hips_cdid, depth, idx, ext);
+        let id = format!(
+            "{}{}{}{}{}",
+            hips_cdid,
+            depth,
+            idx,
+            cube_frame_suffix(cube_frame),
+            ext
+        );
 
         Tile {
             hips_cdid,
@@ -51,10 +74,21 @@ impl Tile {
             cell: *cell,
             format,
             id,
+            cube_frame,
         }
     }
 }
 
+// The tiles of the first slice of a HiPS cube are named as the ones of a plain HiPS,
+// the others are suffixed by their slice index, e.g. Npix845_12.fits
+fn cube_frame_suffix(cube_frame: u32) -> String {
+    if cube_frame > 0 {
+        format!("_{}", cube_frame)
+    } else {
+        String::new()
+    }
+}
+
 use super::request::tile::TileRequest;
 impl Query for Tile {
     type Request = TileRequest;
@@ -78,6 +112,7 @@ pub struct Allsky {
     // The total url of the query
     pub url: Url,
     pub id: QueryId,
+    pub cube_frame: u32,
 }
 
 impl Allsky {
@@ -87,10 +122,15 @@ impl Allsky {
         let texture_size = cfg.get_texture_size();
         let format = cfg.get_format();
         let ext = format.get_ext_file();
+        let cube_frame = cfg.get_cube_frame();
 
-        let url = format!("{}/Norder3/Allsky.{}", cfg.get_root_url(), ext);
+        let url = format!(
+            "{}/Norder3/{}",
+            cfg.get_root_url(),
+            allsky_file(cube_frame, ext)
+        );
 
-        let id = format!("{}Allsky{}", cfg.get_creator_did(), ext);
+        let id = format!("{}{}", cfg.get_creator_did(), allsky_file(cube_frame, ext));
 
         Allsky {
             tile_size,
@@ -99,10 +139,16 @@ impl Allsky {
             url,
             format,
             id,
+            cube_frame,
         }
     }
 }
 
+// The allsky file of a HiPS cube slice, e.g. Allsky_12.fits
+fn allsky_file(cube_frame: u32, ext: &ImageExt) -> String {
+    format!("Allsky{}.{}", cube_frame_suffix(cube_frame), ext)
+}
+
 use super::request::allsky::AllskyRequest;
 impl Query for Allsky {
     type Request = AllskyRequest;
@@ -186,3 +232,48 @@ impl Query for Moc {
         &self.url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{allsky_file, Tile};
+    use crate::healpix::cell::HEALPixCell;
+    use al_api::hips::ImageExt;
+    use al_core::image::format::{ChannelType, ImageFormatType};
+
+    const FITS: ImageFormatType = ImageFormatType {
+        ext: ImageExt::Fits,
+        channel: ChannelType::R32F,
+    };
+
+    fn tile(cube_frame: u32) -> Tile {
+        Tile::new(
+            &HEALPixCell(5, 12845),
+            "ivo://CDS/P/cube".to_string(),
+            "https://alasky.cds.unistra.fr/cube".to_string(),
+            FITS,
+            cube_frame,
+        )
+    }
+
+    #[test]
+    fn cube_frame_tiles() {
+        // the first slice is named as a plain HiPS
+        assert_eq!(
+            tile(0).url,
+            "https://alasky.cds.unistra.fr/cube/Norder5/Dir10000/Npix12845.fits"
+        );
+        assert_eq!(
+            tile(12).url,
+            "https://alasky.cds.unistra.fr/cube/Norder5/Dir10000/Npix12845_12.fits"
+        );
+        // the tiles of different slices are distinct queries
+        assert_ne!(tile(0).id, tile(12).id);
+        assert_ne!(tile(1).id, tile(12).id);
+    }
+
+    #[test]
+    fn cube_frame_allsky() {
+        assert_eq!(allsky_file(0, &ImageExt::Fits), "Allsky.fits");
+        assert_eq!(allsky_file(12, &ImageExt::Png), "Allsky_12.png");
+    }
+}
@@ -14,6 +14,7 @@ pub struct AllskyRequest {
     pub url: Url,
     pub depth_tile: u8,
     pub id: QueryId,
+    pub cube_frame: u32,
 
     request: Request<Vec<ImageType>>,
 }
@@ -80,6 +81,7 @@ impl From<query::Allsky> for AllskyRequest {
             hips_cdid,
             texture_size,
             id,
+            cube_frame,
         } = query;
 
         let depth_tile = crate::math::utils::log_2_unchecked(texture_size / tile_size) as u8;
@@ -211,6 +213,7 @@ impl From<query::Allsky> for AllskyRequest {
             hips_cdid,
             depth_tile,
             url,
+            cube_frame,
             request,
         }
     }
@@ -305,13 +308,16 @@ use al_core::image::format::RGBA8U;
 
 use crate::time::Time;
 use std::sync::{Arc, Mutex};
+#[derive(Clone)]
 pub struct Allsky {
     pub image: Arc<Mutex<Option<Vec<ImageType>>>>,
     pub time_req: Time,
     pub depth_tile: u8,
+    pub cube_frame: u32,
 
     pub hips_cdid: CreatorDid,
     url: Url,
+    id: QueryId,
 }
 
 use crate::Abort;
@@ -328,6 +334,10 @@ impl Allsky {
     pub fn get_url(&self) -> &Url {
         &self.url
     }
+
+    pub fn id(&self) -> &QueryId {
+        &self.id
+    }
 }
 
 impl<'a> From<&'a AllskyRequest> for Option<Allsky> {
@@ -337,6 +347,8 @@ impl<'a> From<&'a AllskyRequest> for Option<Allsky> {
             hips_cdid,
             depth_tile,
             url,
+            cube_frame,
+            id,
             ..
         } = request;
         if request.is_resolved() {
@@ -350,6 +362,8 @@ impl<'a> From<&'a AllskyRequest> for Option<Allsky> {
                 hips_cdid: hips_cdid.clone(),
                 url: url.clone(),
                 depth_tile: *depth_tile,
+                cube_frame: *cube_frame,
+                id: id.clone(),
             })
         } else {
             None
@@ -134,11 +134,12 @@ impl From<query::PixelMetadata> for PixelMetadataRequest {
 }
 
 use std::sync::{Arc, Mutex};
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PixelMetadata {
     pub value: Arc<Mutex<Option<Metadata>>>,
     pub hips_cdid: CreatorDid,
     pub url: String,
+    pub id: QueryId,
 }
 use crate::Abort;
 impl<'a> From<&'a PixelMetadataRequest> for Option<PixelMetadata> {
@@ -147,6 +148,7 @@ impl<'a> From<&'a PixelMetadataRequest> for Option<PixelMetadata> {
             request,
             hips_cdid,
             url,
+            id,
             ..
         } = request;
         if request.is_resolved() {
@@ -156,6 +158,7 @@ impl<'a> From<&'a PixelMetadataRequest> for Option<PixelMetadata> {
                 hips_cdid: hips_cdid.clone(),
                 url: url.to_string(),
                 value: data.clone(),
+                id: id.clone(),
             })
         } else {
             None
@@ -4,8 +4,8 @@ use crate::renderable::CreatorDid;
 use super::{Request, RequestType};
 use crate::downloader::QueryId;
 use crate::healpix::coverage::Smoc;
-use moclib::deser::fits::MocType;
-use moclib::qty::Hpx;
+use moclib::deser::fits::{MocType, STMocType};
+use moclib::qty::{Hpx, Time};
 
 pub struct MOCRequest {
     //pub id: QueryId,
@@ -37,14 +37,53 @@ pub fn from_fits_hpx<T: Idx>(moc: MocType<T, Hpx<T>, Cursor<&[u8]>>) -> Smoc {
         }
     }
 }
+
+/// Time ranges of a T-MOC, in microseconds since JD 0
+pub fn from_fits_time<T: Idx>(moc: MocType<T, Time<T>, Cursor<&[u8]>>) -> Vec<Range<u64>> {
+    match moc {
+        MocType::Ranges(moc) => convert_to_u64::<T, Time<T>, _, Time<u64>>(moc).collect(),
+        MocType::Cells(moc) => {
+            convert_to_u64::<T, Time<T>, _, Time<u64>>(moc.into_cell_moc_iter().ranges()).collect()
+        }
+    }
+}
+
+/// Space-time coverage of a ST-MOC
+pub fn from_fits_space_time(
+    moc: STMocType<u64, Cursor<&[u8]>>,
+) -> Result<SpaceTimeCoverage, JsValue> {
+    match moc {
+        STMocType::V2(moc) => {
+            let mut depth = 0;
+            let elems = moc
+                .map(|elem| {
+                    let (tmoc, smoc) = elem.mocs();
+                    depth = depth.max(smoc.depth_max());
+
+                    (
+                        (&tmoc).into_range_moc_iter().collect(),
+                        (&smoc).into_range_moc_iter().collect(),
+                    )
+                })
+                .collect();
+
+            Ok(SpaceTimeCoverage::new(depth, elems))
+        }
+        STMocType::PreV2(_) => Err(JsValue::from_str(
+            "ST-MOCs serialized before the MOC 2.0 standard are not supported",
+        )),
+    }
+}
 use crate::downloader::query::Query;
 use crate::healpix::coverage::HEALPixCoverage;
+use crate::healpix::space_time::SpaceTimeCoverage;
 use crate::Abort;
 use moclib::deser::fits::MocIdxType;
 use moclib::deser::fits::MocQtyType;
 use moclib::idx::Idx;
-use moclib::moc::{CellMOCIntoIterator, CellMOCIterator, RangeMOCIterator};
+use moclib::moc::{CellMOCIntoIterator, CellMOCIterator, RangeMOCIntoIterator, RangeMOCIterator};
 use std::io::Cursor;
+use std::ops::Range;
 use wasm_bindgen::JsValue;
 impl From<query::Moc> for MOCRequest {
     // Create a tile request associated to a HiPS
@@ -102,6 +141,7 @@ impl From<query::Moc> for MOCRequest {
 }
 
 use std::sync::{Arc, Mutex};
+#[derive(Clone)]
 pub struct Moc {
     pub moc: Arc<Mutex<Option<HEALPixCoverage>>>,
     pub params: al_api::moc::MOC,
@@ -18,6 +18,10 @@ pub struct Request<R> {
     // the HtmlImageElement can be reused to download another tile
     //ready: bool,
     resolved: Rc<Cell<ResolvedStatus>>,
+    // The reason of the failure
+    error: Rc<Cell<Option<FetchError>>>,
+    // Cancel the request while it is in flight
+    abort_controller: Option<AbortController>,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -26,9 +30,11 @@ pub enum ResolvedStatus {
     Failed,
     Found,
 }
+use crate::downloader::retry::FetchError;
 use crate::Abort;
 use std::future::Future;
 use wasm_bindgen::JsValue;
+use web_sys::{AbortController, AbortSignal};
 impl<R> Request<R>
 where
     R: 'static,
@@ -36,9 +42,38 @@ where
     pub fn new<F>(f: F) -> Self
     where
         F: Future<Output = Result<R, JsValue>> + 'static,
+    {
+        Self::with_fetch_error(async move { f.await.map_err(FetchError::from) })
+    }
+
+    // Same as `new` but keeping the reason of a failure
+    pub fn with_fetch_error<F>(f: F) -> Self
+    where
+        F: Future<Output = Result<R, FetchError>> + 'static,
+    {
+        Self::spawn(f, None)
+    }
+
+    // Same as `with_fetch_error` but the request can be aborted.
+    // The future is built from the signal to give to the fetch.
+    pub fn with_abort<F, Fut>(f: F) -> Self
+    where
+        F: FnOnce(AbortSignal) -> Fut,
+        Fut: Future<Output = Result<R, FetchError>> + 'static,
+    {
+        let abort_controller = AbortController::new().unwrap_abort();
+        let fut = f(abort_controller.signal());
+
+        Self::spawn(fut, Some(abort_controller))
+    }
+
+    fn spawn<F>(f: F, abort_controller: Option<AbortController>) -> Self
+    where
+        F: Future<Output = Result<R, FetchError>> + 'static,
     {
         // By default, we say the tile is available to be reused
         let resolved = Rc::new(Cell::new(ResolvedStatus::NotResolved));
+        let error = Rc::new(Cell::new(None));
         let time_request = Time::now();
 
         let data = Arc::new(Mutex::new(None));
@@ -46,14 +81,18 @@ where
         {
             let data_cloned = data.clone();
             let resolved_cloned = resolved.clone();
+            let error_cloned = error.clone();
 
             let fut = async move {
-                let resp = f.await;
-                if let Ok(resp) = resp {
-                    *(data_cloned.lock().unwrap_abort()) = Some(resp);
-                    resolved_cloned.set(ResolvedStatus::Found);
-                } else {
-                    resolved_cloned.set(ResolvedStatus::Failed);
+                match f.await {
+                    Ok(resp) => {
+                        *(data_cloned.lock().unwrap_abort()) = Some(resp);
+                        resolved_cloned.set(ResolvedStatus::Found);
+                    }
+                    Err(err) => {
+                        error_cloned.set(Some(err));
+                        resolved_cloned.set(ResolvedStatus::Failed);
+                    }
                 }
             };
 
@@ -63,7 +102,19 @@ where
         Self {
             data,
             resolved,
+            error,
             time_request,
+            abort_controller,
+        }
+    }
+
+    // Cancel the request if it is still in flight.
+    // Its future then resolves to a `FetchError::Aborted`.
+    pub fn abort(&self) {
+        if let Some(abort_controller) = &self.abort_controller {
+            if !self.is_resolved() {
+                abort_controller.abort();
+            }
         }
     }
 
@@ -75,6 +126,10 @@ where
     pub fn resolve_status(&self) -> ResolvedStatus {
         self.resolved.get()
     }
+
+    pub fn get_error(&self) -> Option<FetchError> {
+        self.error.get()
+    }
 }
 
 use allsky::AllskyRequest;
@@ -117,6 +172,7 @@ use allsky::Allsky;
 use blank::PixelMetadata;
 use moc::Moc;
 use tile::Tile;
+#[derive(Clone)]
 pub enum Resource {
     Tile(Tile),
     Allsky(Allsky),
@@ -125,12 +181,36 @@ pub enum Resource {
 }
 
 impl Resource {
-    pub fn id(&self) -> &String {
+    pub fn id(&self) -> &QueryId {
         match self {
-            Resource::Tile(tile) => tile.get_hips_cdid(),
-            Resource::Allsky(allsky) => allsky.get_hips_cdid(),
-            Resource::PixelMetadata(PixelMetadata { hips_cdid, .. }) => hips_cdid,
+            Resource::Tile(tile) => tile.id(),
+            Resource::Allsky(allsky) => allsky.id(),
+            Resource::PixelMetadata(PixelMetadata { id, .. }) => id,
             Resource::Moc(moc) => moc.get_hips_cdid(),
         }
     }
+
+    // Estimation of the memory taken by the resource, in bytes
+    pub fn byte_size(&self) -> usize {
+        match self {
+            Resource::Tile(tile) => tile
+                .image
+                .lock()
+                .unwrap_abort()
+                .as_ref()
+                .map_or(0, |image| image.byte_size()),
+            Resource::Allsky(allsky) => allsky
+                .image
+                .lock()
+                .unwrap_abort()
+                .as_ref()
+                .map_or(0, |images| {
+                    images.iter().map(|image| image.byte_size()).sum()
+                }),
+            Resource::PixelMetadata(_) => std::mem::size_of::<blank::Metadata>(),
+            Resource::Moc(moc) => moc.moc.lock().unwrap_abort().as_ref().map_or(0, |moc| {
+                moc.0.len() * std::mem::size_of::<std::ops::Range<u64>>()
+            }),
+        }
+    }
 }
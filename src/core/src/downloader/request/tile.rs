@@ -16,10 +16,38 @@ pub struct TileRequest {
     hips_cdid: CreatorDid,
     url: Url,
     format: ImageFormatType,
+    cube_frame: u32,
 
     request: Request<ImageType>,
 }
 
+impl TileRequest {
+    #[inline(always)]
+    pub fn cell(&self) -> &HEALPixCell {
+        &self.cell
+    }
+
+    #[inline(always)]
+    pub fn get_hips_cdid(&self) -> &CreatorDid {
+        &self.hips_cdid
+    }
+
+    #[inline(always)]
+    pub fn get_url(&self) -> &Url {
+        &self.url
+    }
+
+    #[inline(always)]
+    pub fn is_resolved(&self) -> bool {
+        self.request.is_resolved()
+    }
+
+    // Cancel the download of the tile
+    pub fn abort(&self) {
+        self.request.abort();
+    }
+}
+
 impl From<TileRequest> for RequestType {
     fn from(request: TileRequest) -> Self {
         RequestType::Tile(request)
@@ -45,12 +73,98 @@ async fn query_html_image(url: &str) -> Result<HtmlImageElement, JsValue> {
     Ok(image)
 }
 
+// Resolve after a delay given in ms
+async fn sleep(delay: f32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = web_sys::window()
+            .unwrap_abort()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, delay as i32);
+    });
+
+    let _ = JsFuture::from(promise).await;
+}
+
+// Send a GET request whose response must come before a timeout given in ms.
+// The request is aborted on timeout so that it does not hold a connection
+// while the next attempt is sent, or when `signal` is aborted.
+async fn fetch(url: &str, timeout: f32, signal: &AbortSignal) -> Result<Response, FetchError> {
+    if signal.aborted() {
+        return Err(FetchError::Aborted);
+    }
+
+    // Each attempt has its own controller, aborted with the one of the tile request
+    let attempt = AbortController::new()?;
+
+    let mut opts = RequestInit::new();
+    opts.method("GET");
+    opts.mode(RequestMode::Cors);
+    opts.signal(Some(&attempt.signal()));
+
+    let window = web_sys::window().unwrap_abort();
+    let request = web_sys::Request::new_with_str_and_init(url, &opts)?;
+
+    let attempt_cloned = attempt.clone();
+    let on_abort: Closure<dyn FnMut()> = Closure::once(move || attempt_cloned.abort());
+    signal.set_onabort(Some(on_abort.as_ref().unchecked_ref()));
+
+    let resp_promise = JsFuture::from(window.fetch_with_request(&request));
+
+    let resp_value = future::select(resp_promise, Box::pin(sleep(timeout))).await;
+    signal.set_onabort(None);
+
+    let resp_value = match resp_value {
+        Either::Left((Ok(resp_value), _)) => resp_value,
+        // The fetch promise is rejected when the request is aborted
+        Either::Left((Err(_), _)) if signal.aborted() => return Err(FetchError::Aborted),
+        Either::Left((Err(err), _)) => return Err(err.into()),
+        Either::Right(_) => {
+            attempt.abort();
+            return Err(FetchError::Timeout);
+        }
+    };
+    // `resp_value` is a `Response` object.
+    debug_assert!(resp_value.is_instance_of::<Response>());
+    let resp: Response = resp_value.dyn_into()?;
+
+    if resp.ok() {
+        Ok(resp)
+    } else {
+        Err(FetchError::Status(resp.status()))
+    }
+}
+
+// Fetch a tile, the request is sent again after transient failures
+async fn fetch_tile(url: &str, signal: &AbortSignal) -> Result<Response, FetchError> {
+    fetch_with_retry(
+        &TILE_RETRY_POLICY,
+        || fetch(url, TILE_RETRY_POLICY.timeout, signal),
+        sleep,
+    )
+    .await
+}
+
+// Fetch a tile and decode it into an image element
+async fn query_tile_image(url: &str, signal: &AbortSignal) -> Result<HtmlImageElement, FetchError> {
+    let resp = fetch_tile(url, signal).await?;
+    let blob = JsFuture::from(resp.blob()?).await?.into();
+
+    // The image is loaded from the fetched data so that the errors of the server are known
+    let object_url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let image = query_html_image(&object_url).await;
+    web_sys::Url::revoke_object_url(&object_url)?;
+
+    Ok(image?)
+}
+
+use crate::downloader::retry::{fetch_with_retry, FetchError, TILE_RETRY_POLICY};
 use crate::renderable::Url;
 use al_core::image::html::HTMLImage;
+use futures::future::{self, Either};
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{HtmlImageElement, RequestInit, RequestMode, Response};
+use web_sys::{AbortController, AbortSignal, HtmlImageElement, RequestInit, RequestMode, Response};
 impl From<query::Tile> for TileRequest {
     // Create a tile request associated to a HiPS
     fn from(query: query::Tile) -> Self {
@@ -60,24 +174,14 @@ impl From<query::Tile> for TileRequest {
             url,
             hips_cdid,
             id,
+            cube_frame,
         } = query;
 
         let url_clone = url.clone();
         let channel = format.get_channel();
 
-        let window = web_sys::window().unwrap_abort();
         let request = match channel {
-            ChannelType::RGB8U => Request::new(async move {
-                /*let mut opts = RequestInit::new();
-                opts.method("GET");
-                opts.mode(RequestMode::Cors);
-
-                let request = web_sys::Request::new_with_str_and_init(&url_clone, &opts).unwrap_abort();
-                let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-                // `resp_value` is a `Response` object.
-                debug_assert!(resp_value.is_instance_of::<Response>());
-                let resp: Response = resp_value.dyn_into()?;*/
-
+            ChannelType::RGB8U => Request::with_abort(|signal| async move {
                 /*/// Bitmap version
                 let blob = JsFuture::from(resp.blob()?).await?.into();
                 let image = JsFuture::from(window.create_image_bitmap_with_blob(&blob)?)
@@ -96,23 +200,13 @@ impl From<query::Tile> for TileRequest {
                 Ok(ImageType::RawRgb8u { image })
                 */
                 // HTMLImageElement
-                let image = query_html_image(&url_clone).await?;
+                let image = query_tile_image(&url_clone, &signal).await?;
                 // The image has been resolved
                 Ok(ImageType::HTMLImageRgb8u {
                     image: HTMLImage::<RGB8U>::new(image),
                 })
             }),
-            ChannelType::RGBA8U => Request::new(async move {
-                /*let mut opts = RequestInit::new();
-                opts.method("GET");
-                opts.mode(RequestMode::Cors);
-
-                let request = web_sys::Request::new_with_str_and_init(&url_clone, &opts).unwrap_abort();
-                let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-                // `resp_value` is a `Response` object.
-                debug_assert!(resp_value.is_instance_of::<Response>());
-                let resp: Response = resp_value.dyn_into()?;*/
-
+            ChannelType::RGBA8U => Request::with_abort(|signal| async move {
                 /*/// Bitmap version
                 let blob = JsFuture::from(resp.blob()?).await?.into();
                 let image = JsFuture::from(window.create_image_bitmap_with_blob(&blob)?)
@@ -131,7 +225,7 @@ impl From<query::Tile> for TileRequest {
                 Ok(ImageType::RawRgba8u { image })
                 */
                 // HTMLImageElement
-                let image = query_html_image(&url_clone).await?;
+                let image = query_tile_image(&url_clone, &signal).await?;
                 // The image has been resolved
                 Ok(ImageType::HTMLImageRgba8u {
                     image: HTMLImage::<RGBA8U>::new(image),
@@ -141,17 +235,8 @@ impl From<query::Tile> for TileRequest {
             | ChannelType::R64F
             | ChannelType::R32I
             | ChannelType::R16I
-            | ChannelType::R8UI => Request::new(async move {
-                let mut opts = RequestInit::new();
-                opts.method("GET");
-                opts.mode(RequestMode::Cors);
-
-                let request =
-                    web_sys::Request::new_with_str_and_init(&url_clone, &opts).unwrap_abort();
-                let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-                // `resp_value` is a `Response` object.
-                debug_assert!(resp_value.is_instance_of::<Response>());
-                let resp: Response = resp_value.dyn_into()?;
+            | ChannelType::R8UI => Request::with_abort(|signal| async move {
+                let resp = fetch_tile(&url_clone, &signal).await?;
                 // See https://github.com/MattiasBuelens/wasm-streams/blob/f6dacf58a8826dc67923ab4a3bae87635690ca64/examples/fetch_as_stream.rs#L25-L33
                 /*let raw_body = resp.body().ok_or(JsValue::from_str("Cannot extract readable stream"))?;
                 let body = ReadableStream::from_raw(raw_body.dyn_into()?);
@@ -160,16 +245,10 @@ impl From<query::Tile> for TileRequest {
                 let mut reader = body.try_into_async_read().map_err(|_| JsValue::from_str("readable stream locked"))?;
                 let image = Fits::new(reader).await?;
                 */
-                if resp.ok() {
-                    let array_buffer = JsFuture::from(resp.array_buffer()?).await?;
-                    let raw_bytes = js_sys::Uint8Array::new(&array_buffer);
-
-                    Ok(ImageType::FitsImage { raw_bytes })
-                } else {
-                    Err(JsValue::from_str(
-                        "Response status code not between 200-299.",
-                    ))
-                }
+                let array_buffer = JsFuture::from(resp.array_buffer()?).await?;
+                let raw_bytes = js_sys::Uint8Array::new(&array_buffer);
+
+                Ok(ImageType::FitsImage { raw_bytes })
             }),
             _ => todo!(),
         };
@@ -180,6 +259,7 @@ impl From<query::Tile> for TileRequest {
             id,
             hips_cdid,
             url,
+            cube_frame,
             request,
         }
     }
@@ -187,13 +267,17 @@ impl From<query::Tile> for TileRequest {
 
 use crate::time::Time;
 use std::sync::{Arc, Mutex};
+#[derive(Clone)]
 pub struct Tile {
     pub image: Arc<Mutex<Option<ImageType>>>,
     pub time_req: Time,
     pub cell: HEALPixCell,
     pub format: ImageFormatType,
+    pub cube_frame: u32,
     hips_cdid: CreatorDid,
     url: Url,
+    id: QueryId,
+    error: Option<FetchError>,
 }
 
 use crate::Abort;
@@ -213,11 +297,22 @@ impl Tile {
         &self.url
     }
 
+    #[inline(always)]
+    pub fn id(&self) -> &QueryId {
+        &self.id
+    }
+
     #[inline(always)]
     pub fn cell(&self) -> &HEALPixCell {
         &self.cell
     }
 
+    // The reason why the tile is missing
+    #[inline(always)]
+    pub fn get_error(&self) -> Option<FetchError> {
+        self.error
+    }
+
     /*#[inline(always)]
     pub fn query(&self) -> query::Tile {
         query::Tile::new(&self.cell, self.hips_url.clone(), self.format)
@@ -232,6 +327,8 @@ impl<'a> From<&'a TileRequest> for Option<Tile> {
             hips_cdid,
             url,
             format,
+            cube_frame,
+            id,
             ..
         } = request;
         if request.is_resolved() {
@@ -246,6 +343,9 @@ impl<'a> From<&'a TileRequest> for Option<Tile> {
                 hips_cdid: hips_cdid.clone(),
                 url: url.clone(),
                 format: *format,
+                cube_frame: *cube_frame,
+                id: id.clone(),
+                error: request.get_error(),
             })
         } else {
             None
@@ -0,0 +1,180 @@
+use std::future::Future;
+
+use wasm_bindgen::JsValue;
+
+/// The reason why a resource could not be fetched
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FetchError {
+    /// The server could not be reached or the request has been rejected by the browser
+    Network,
+    /// The server did not answer in time
+    Timeout,
+    /// The server answered with a status code outside of 200-299
+    Status(u16),
+    /// The request has been cancelled, e.g. its tile has left the view
+    Aborted,
+}
+
+impl FetchError {
+    /// Whether fetching the resource again may succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Network | FetchError::Timeout => true,
+            FetchError::Status(status) => *status >= 500 || *status == 408 || *status == 429,
+            FetchError::Aborted => false,
+        }
+    }
+
+    /// Whether the resource does not exist on the server
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, FetchError::Status(404) | FetchError::Status(410))
+    }
+}
+
+// A JS exception, e.g. a rejected fetch promise, comes from a failure of the network
+impl From<JsValue> for FetchError {
+    fn from(_: JsValue) -> Self {
+        FetchError::Network
+    }
+}
+
+/// How many times a request is sent again after transient failures
+/// and how long to wait between two attempts
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, the first one included
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled for the next ones, in ms
+    pub base_delay: f32,
+    /// Upper bound of the delay between two attempts, in ms
+    pub max_delay: f32,
+    /// Time after which an attempt is considered to have failed, in ms
+    pub timeout: f32,
+}
+
+pub const TILE_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    base_delay: 500.0,
+    max_delay: 4000.0,
+    timeout: 10000.0,
+};
+
+impl RetryPolicy {
+    /// Delay to wait after the failure of an attempt, starting from 0, in ms
+    pub fn delay(&self, attempt: u32) -> f32 {
+        (self.base_delay * 2_f32.powi(attempt.min(31) as i32)).min(self.max_delay)
+    }
+}
+
+/// Fetch a resource, sending the request again after transient failures
+///
+/// # Arguments
+///
+/// * `policy` - The number of attempts and the delays between them
+/// * `fetch` - Send the request and resolve to the resource, this is where the network layer can be mocked
+/// * `sleep` - Resolve after a delay given in ms
+///
+/// The error of the last attempt is returned if none succeeded
+pub async fn fetch_with_retry<T, F, Fut, S, SFut>(
+    policy: &RetryPolicy,
+    mut fetch: F,
+    mut sleep: S,
+) -> Result<T, FetchError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+    S: FnMut(f32) -> SFut,
+    SFut: Future<Output = ()>,
+{
+    let mut attempt = 0;
+    loop {
+        match fetch().await {
+            Err(err) if err.is_transient() && attempt + 1 < policy.max_attempts => {
+                sleep(policy.delay(attempt)).await;
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch_with_retry, FetchError, RetryPolicy};
+    use futures::executor::block_on;
+    use std::cell::RefCell;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_attempts: 4,
+        base_delay: 100.0,
+        max_delay: 300.0,
+        timeout: 1000.0,
+    };
+
+    // Fetch the given responses in order and record the delays waited
+    fn fetch(responses: Vec<Result<u32, FetchError>>) -> (Result<u32, FetchError>, Vec<f32>) {
+        let responses = RefCell::new(responses.into_iter());
+        let delays = RefCell::new(vec![]);
+
+        let res = block_on(fetch_with_retry(
+            &POLICY,
+            || {
+                let res = responses.borrow_mut().next().unwrap();
+                async move { res }
+            },
+            |delay| {
+                delays.borrow_mut().push(delay);
+                async {}
+            },
+        ));
+
+        (res, delays.into_inner())
+    }
+
+    #[test]
+    fn errors() {
+        assert!(FetchError::Network.is_transient());
+        assert!(FetchError::Timeout.is_transient());
+        assert!(FetchError::Status(503).is_transient());
+        assert!(FetchError::Status(429).is_transient());
+        assert!(!FetchError::Status(404).is_transient());
+        assert!(!FetchError::Aborted.is_transient());
+        assert!(FetchError::Status(404).is_not_found());
+        assert!(!FetchError::Status(403).is_not_found());
+    }
+
+    #[test]
+    fn retry_with_backoff() {
+        assert_eq!(
+            fetch(vec![
+                Err(FetchError::Network),
+                Err(FetchError::Status(502)),
+                Ok(3)
+            ]),
+            (Ok(3), vec![100.0, 200.0])
+        );
+
+        // the delay is bounded and the last error is given back
+        assert_eq!(
+            fetch(vec![Err(FetchError::Timeout); 4]),
+            (Err(FetchError::Timeout), vec![100.0, 200.0, 300.0])
+        );
+    }
+
+    #[test]
+    fn no_retry_on_permanent_errors() {
+        assert_eq!(
+            fetch(vec![Err(FetchError::Status(404))]),
+            (Err(FetchError::Status(404)), vec![])
+        );
+        assert_eq!(
+            fetch(vec![Err(FetchError::Network), Err(FetchError::Status(403))]),
+            (Err(FetchError::Status(403)), vec![100.0])
+        );
+        // a cancelled request is not sent again
+        assert_eq!(
+            fetch(vec![Err(FetchError::Timeout), Err(FetchError::Aborted)]),
+            (Err(FetchError::Aborted), vec![100.0])
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+
+use al_api::coo_system::CooSystem;
+
+use crate::altaz::HorizontalFrame;
+use crate::math::lonlat::{LonLat, LonLatEpochT, LonLatT};
+
+/// Number of days in a Julian year
+const JULIAN_YEAR: f64 = 365.25;
+/// Number of days in a tropical year (at B1900)
+const TROPICAL_YEAR: f64 = 365.242198781;
+/// Julian date of the J2000.0 epoch
+const JD_J2000: f64 = 2451545.0;
+/// Julian date of the B1900.0 epoch
+const JD_B1900: f64 = 2415020.31352;
+/// Julian date of the Unix epoch (1970-01-01T00:00:00Z)
+const JD_UNIX_EPOCH: f64 = 2440587.5;
+/// Number of seconds in a day
+pub const SECONDS_PER_DAY: f64 = 86400.0;
+/// 1 au/yr expressed in km/s
+const AU_PER_YEAR_IN_KM_PER_S: f64 = 4.740470446;
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+const MAS_TO_RAD: f64 = ARCSEC_TO_RAD * 1e-3;
+
+/// An epoch stored as a Julian date
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Epoch(pub f64);
+
+impl Epoch {
+    pub const J2000: Self = Epoch(JD_J2000);
+    pub const B1950: Self = Epoch(2433282.4235);
+
+    #[inline]
+    pub fn from_jd(jd: f64) -> Self {
+        Epoch(jd)
+    }
+
+    /// Epoch from a number of milliseconds elapsed since the Unix epoch
+    /// (e.g. the value returned by javascript `Date.now()`)
+    #[inline]
+    pub fn from_unix_millis(ms: f64) -> Self {
+        Epoch(JD_UNIX_EPOCH + ms / (SECONDS_PER_DAY * 1e3))
+    }
+
+    /// Epoch from a Julian year (e.g. 2016.0 for Gaia DR3)
+    #[inline]
+    pub fn from_julian_year(year: f64) -> Self {
+        Epoch(JD_J2000 + (year - 2000.0) * JULIAN_YEAR)
+    }
+
+    /// Epoch from a Besselian year (e.g. 1950.0 for FK4)
+    #[inline]
+    pub fn from_besselian_year(year: f64) -> Self {
+        Epoch(JD_B1900 + (year - 1900.0) * TROPICAL_YEAR)
+    }
+
+    /// Epoch from a year expressed in the time scale customary for `frame`,
+    /// i.e. Besselian years for FK4 and Julian years otherwise
+    #[inline]
+    pub fn from_year(frame: CooSystem, year: f64) -> Self {
+        match frame {
+            CooSystem::FK4B1950 => Self::from_besselian_year(year),
+            _ => Self::from_julian_year(year),
+        }
+    }
+
+    #[inline]
+    pub fn jd(&self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    pub fn julian_year(&self) -> f64 {
+        2000.0 + (self.0 - JD_J2000) / JULIAN_YEAR
+    }
+
+    #[inline]
+    pub fn besselian_year(&self) -> f64 {
+        1900.0 + (self.0 - JD_B1900) / TROPICAL_YEAR
+    }
+}
+
+/// Space motion of a source
+///
+/// Proper motions are given in mas/yr (the RA one including the cos(dec) factor),
+/// the parallax in mas and the radial velocity in km/s.
+/// Unknown parallax or radial velocity must be set to 0.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SpaceMotion {
+    pub pm_ra_cosdec: f64,
+    pub pm_dec: f64,
+    pub parallax: f64,
+    pub rv: f64,
+}
+
+impl SpaceMotion {
+    #[inline]
+    pub fn new(pm_ra_cosdec: f64, pm_dec: f64, parallax: f64, rv: f64) -> Self {
+        Self {
+            pm_ra_cosdec,
+            pm_dec,
+            parallax,
+            rv,
+        }
+    }
+
+    /// The source has no motion on the sky
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        self.pm_ra_cosdec == 0.0 && self.pm_dec == 0.0 && self.rv == 0.0
+    }
+}
+
+/// Propagate a source to a target epoch assuming a uniform rectilinear space motion
+/// (see the Hipparcos catalogue, ESA SP-1200, Vol 1, §1.5.5)
+///
+/// The source moves along a straight line in the Cartesian space so that the perspective
+/// acceleration due to its radial velocity is taken into account, contrary to a linear update
+/// of the longitude and the latitude. Without parallax, the radial velocity is ignored.
+/// The light travel time is neglected and the proper motions at the target epoch are not computed.
+///
+/// The position and the target epoch must be expressed in the same reference frame.
+/// No precession is done here.
+pub fn propagate(pos: &LonLatEpochT<f64>, motion: &SpaceMotion, to: Epoch) -> LonLatEpochT<f64> {
+    let t = (to.jd() - pos.epoch.jd()) / JULIAN_YEAR;
+    if t == 0.0 || motion.is_static() {
+        return LonLatEpochT::new(pos.lonlat, to);
+    }
+
+    let lon = pos.lonlat.lon();
+    let lat = pos.lonlat.lat();
+
+    // Normal triad in the model space
+    let r: Vector3<f64> = pos.lonlat.vector();
+    // towards the east
+    let p = Vector3::new(lon.cos(), 0.0, -lon.sin());
+    // towards the north
+    let q = Vector3::new(-lat.sin() * lon.sin(), lat.cos(), -lat.sin() * lon.cos());
+
+    // Radial proper motion in mas/yr
+    let pm_r = motion.rv * motion.parallax / AU_PER_YEAR_IN_KM_PER_S;
+
+    // Cartesian position and velocity in units of the distance of the source at its epoch
+    let b0 = r;
+    let v = (p * motion.pm_ra_cosdec + q * motion.pm_dec + r * pm_r) * MAS_TO_RAD;
+    let b = b0 + v * t;
+
+    LonLatEpochT::new(b.normalize().lonlat(), to)
+}
+
+// Convert a rotation matrix given in the usual (x towards the vernal point, z towards the pole)
+// cartesian frame to the model space one
+fn to_model_space_mat(m: [[f64; 3]; 3]) -> Matrix4<f64> {
+    // x_model = y, y_model = z, z_model = x
+    const PERM: [usize; 3] = [1, 2, 0];
+    let e = |i: usize, j: usize| m[PERM[i]][PERM[j]];
+
+    Matrix4::new(
+        e(0, 0),
+        e(1, 0),
+        e(2, 0),
+        0.0,
+        e(0, 1),
+        e(1, 1),
+        e(2, 1),
+        0.0,
+        e(0, 2),
+        e(1, 2),
+        e(2, 2),
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    )
+}
+
+// Precession matrix from the three equatorial precession angles (in arcsec)
+fn precession_mat(zeta: f64, z: f64, theta: f64) -> Matrix4<f64> {
+    let (s_zeta, c_zeta) = (zeta * ARCSEC_TO_RAD).sin_cos();
+    let (s_z, c_z) = (z * ARCSEC_TO_RAD).sin_cos();
+    let (s_theta, c_theta) = (theta * ARCSEC_TO_RAD).sin_cos();
+
+    to_model_space_mat([
+        [
+            c_zeta * c_theta * c_z - s_zeta * s_z,
+            -s_zeta * c_theta * c_z - c_zeta * s_z,
+            -s_theta * c_z,
+        ],
+        [
+            c_zeta * c_theta * s_z + s_zeta * c_z,
+            -s_zeta * c_theta * s_z + c_zeta * c_z,
+            -s_theta * s_z,
+        ],
+        [c_zeta * s_theta, -s_zeta * s_theta, c_theta],
+    ])
+}
+
+/// FK5 precession matrix between two mean equinoxes (IAU 1976, Lieske et al. 1977)
+pub fn fk5_precession(from: Epoch, to: Epoch) -> Matrix4<f64> {
+    // Julian centuries from J2000 to the starting epoch
+    let t0 = (from.jd() - JD_J2000) / (100.0 * JULIAN_YEAR);
+    // Julian centuries between the two epochs
+    let t = (to.jd() - from.jd()) / (100.0 * JULIAN_YEAR);
+
+    let a = 2306.2181 + 1.39656 * t0 - 0.000139 * t0 * t0;
+    let zeta = a * t + (0.30188 - 0.000344 * t0) * t * t + 0.017998 * t * t * t;
+    let z = a * t + (1.09468 + 0.000066 * t0) * t * t + 0.018203 * t * t * t;
+    let theta = (2004.3109 - 0.85330 * t0 - 0.000217 * t0 * t0) * t
+        - (0.42665 + 0.000217 * t0) * t * t
+        - 0.041833 * t * t * t;
+
+    precession_mat(zeta, z, theta)
+}
+
+/// FK4 precession matrix between two mean equinoxes (Newcomb)
+pub fn fk4_precession(from: Epoch, to: Epoch) -> Matrix4<f64> {
+    // Tropical centuries from B1850 to the starting epoch
+    let t0 = (from.besselian_year() - 1850.0) / 100.0;
+    // Tropical centuries between the two epochs
+    let t = (to.besselian_year() - from.besselian_year()) / 100.0;
+
+    let zeta = (2304.250 + 1.396 * t0) * t + 0.302 * t * t + 0.018 * t * t * t;
+    let z = zeta + 0.791 * t * t;
+    let theta = (2004.682 - 0.853 * t0) * t - 0.426 * t * t - 0.042 * t * t * t;
+
+    precession_mat(zeta, z, theta)
+}
+
+/// Precess a position given in an equatorial frame between two mean equinoxes.
+/// Non equatorial frames are left untouched.
+#[inline]
+pub fn apply_precession(
+    frame: CooSystem,
+    from: Epoch,
+    to: Epoch,
+    v: &Vector4<f64>,
+) -> Vector4<f64> {
+    match frame {
+        CooSystem::FK4B1950 => fk4_precession(from, to) * (*v),
+        CooSystem::ICRS | CooSystem::FK5J2000 => fk5_precession(from, to) * (*v),
+        _ => *v,
+    }
+}
+
+/// Propagate a source observed at `pos.epoch` in `c1` to the epoch `to` and give its
+/// position in `c2`.
+///
+/// Only the space motion depends on the epochs: `c1` and `c2` are taken at their
+/// standard equinoxes (J2000 for FK5, B1950 for FK4), see [`precess`] to change them
+pub fn apply_coo_system_at_epoch(
+    c1: CooSystem,
+    c2: CooSystem,
+    pos: &LonLatEpochT<f64>,
+    motion: &SpaceMotion,
+    to: Epoch,
+    horizontal_frame: &HorizontalFrame,
+) -> LonLatEpochT<f64> {
+    let pos = propagate(pos, motion, to);
+    let v = crate::coosys::apply_coo_system(
+        c1,
+        c2,
+        &pos.lonlat.vector::<Vector4<f64>>(),
+        horizontal_frame,
+    );
+
+    LonLatEpochT::new(v.lonlat(), to)
+}
+
+/// Precess a position between two equinoxes of the same equatorial frame
+#[inline]
+pub fn precess(frame: CooSystem, lonlat: &LonLatT<f64>, from: Epoch, to: Epoch) -> LonLatT<f64> {
+    apply_precession(frame, from, to, &lonlat.vector()).lonlat()
+}
+
+#[cfg(test)]
+mod tests {
+    fn ang_dist_arcsec(lonlat: &crate::LonLatT<f64>, lon_deg: f64, lat_deg: f64) -> f64 {
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+        use cgmath::{InnerSpace, Vector3};
+
+        let expected: LonLatT<f64> = LonLatT::new(ArcDeg(lon_deg).into(), ArcDeg(lat_deg).into());
+        let v1: Vector3<f64> = lonlat.vector();
+        let v2: Vector3<f64> = expected.vector();
+
+        v1.cross(v2).magnitude().atan2(v1.dot(v2)).to_degrees() * 3600.0
+    }
+
+    #[test]
+    fn fk5_pole_precession() {
+        use super::{precess, Epoch};
+        use crate::ArcDeg;
+        use crate::LonLatT;
+        use al_api::coo_system::CooSystem;
+
+        // The J2000 pole is seen at dec = 90° - theta one century later
+        let pole: LonLatT<f64> = LonLatT::new(ArcDeg(0.0).into(), ArcDeg(90.0).into());
+        let pole_j2100 = precess(
+            CooSystem::FK5J2000,
+            &pole,
+            Epoch::J2000,
+            Epoch::from_julian_year(2100.0),
+        );
+
+        assert!((pole_j2100.lat().to_degrees() - 89.44337711).abs() < 1e-7);
+    }
+
+    #[test]
+    fn precession_roundtrip() {
+        use super::{precess, Epoch};
+        use crate::ArcDeg;
+        use crate::LonLatT;
+        use al_api::coo_system::CooSystem;
+
+        let lonlat: LonLatT<f64> = LonLatT::new(ArcDeg(201.3).into(), ArcDeg(-43.0).into());
+        for &frame in [CooSystem::FK5J2000, CooSystem::FK4B1950].iter() {
+            let e1 = Epoch::from_julian_year(1875.0);
+            let e2 = Epoch::from_julian_year(2050.0);
+
+            let p = precess(frame, &lonlat, e1, e2);
+            let p = precess(frame, &p, e2, e1);
+
+            assert!(ang_dist_arcsec(&p, 201.3, -43.0) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn proper_motion_and_precession() {
+        use super::{precess, propagate, Epoch, SpaceMotion};
+        use crate::math::lonlat::LonLatEpochT;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+        use al_api::coo_system::CooSystem;
+
+        // Meeus, Astronomical Algorithms, example 21.b: theta Persei
+        let ra = (2.0 + 44.0 / 60.0 + 11.986 / 3600.0) * 15.0;
+        let dec: f64 = 49.0 + 13.0 / 60.0 + 42.48 / 3600.0;
+        let pos = LonLatEpochT::new(
+            LonLatT::new(ArcDeg(ra).into(), ArcDeg(dec).into()),
+            Epoch::J2000,
+        );
+        let motion = SpaceMotion::new(
+            0.03425 * 15.0 * dec.to_radians().cos() * 1e3,
+            -0.0895 * 1e3,
+            0.0,
+            0.0,
+        );
+
+        let to = Epoch::from_jd(2462088.69);
+        let pos = propagate(&pos, &motion, to);
+        let pos = precess(CooSystem::FK5J2000, &pos.lonlat, Epoch::J2000, to);
+
+        let expected_ra = (2.0 + 46.0 / 60.0 + 11.331 / 3600.0) * 15.0;
+        let expected_dec = 49.0 + 20.0 / 60.0 + 54.54 / 3600.0;
+        assert!(ang_dist_arcsec(&pos, expected_ra, expected_dec) < 0.05);
+    }
+
+    #[test]
+    fn perspective_acceleration() {
+        use super::{propagate, Epoch, SpaceMotion};
+        use crate::math::lonlat::LonLatEpochT;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        // Barnard's star (Hipparcos) two millennia later, the position at J3991.25
+        // being the one of its straight line motion in the Cartesian space
+        let pos = LonLatEpochT::new(
+            LonLatT::new(ArcDeg(269.45402305).into(), ArcDeg(4.66828815).into()),
+            Epoch::from_julian_year(1991.25),
+        );
+        let motion = SpaceMotion::new(-797.84, 10326.93, 549.01, -110.6);
+        let pos = propagate(&pos, &motion, Epoch::from_julian_year(3991.25));
+
+        assert!(ang_dist_arcsec(&pos.lonlat, 268.94146484, 11.19031523) < 1e-3);
+        // A linear update of the coordinates would be off by more than half a degree
+        assert!(ang_dist_arcsec(&pos.lonlat, 269.00930329, 10.40547148) > 1800.0);
+    }
+
+    #[test]
+    fn radial_velocity_only_keeps_direction() {
+        use super::{propagate, Epoch, SpaceMotion};
+        use crate::math::lonlat::LonLatEpochT;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+
+        let pos = LonLatEpochT::new(
+            LonLatT::new(ArcDeg(10.0).into(), ArcDeg(20.0).into()),
+            Epoch::from_julian_year(2016.0),
+        );
+        let motion = SpaceMotion::new(0.0, 0.0, 100.0, -50.0);
+        let pos = propagate(&pos, &motion, Epoch::from_julian_year(2100.0));
+
+        assert!(ang_dist_arcsec(&pos.lonlat, 10.0, 20.0) < 1e-6);
+    }
+}
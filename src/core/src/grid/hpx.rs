@@ -0,0 +1,227 @@
+use crate::camera::CameraViewPort;
+use crate::healpix::cell::HEALPixCell;
+use crate::math::angle::Angle;
+use crate::math::projection::coo_space::XYScreen;
+use crate::renderable::coverage::is_crossing_projection;
+use crate::renderable::line;
+use crate::renderable::line::PathVertices;
+use crate::renderable::line::RasterizedLineRenderer;
+use crate::renderable::text::TextRenderManager;
+use crate::renderable::Renderer;
+use crate::Abort;
+use crate::ProjectionType;
+
+use al_api::color::ColorRGBA;
+use al_api::coo_system::CooSystem;
+use al_api::grid::HEALPixGridCfg;
+
+use wasm_bindgen::JsValue;
+use web_sys::HtmlElement;
+
+/// Cells of a too low order are too curved to have their edges drawn with one segment
+const MIN_NUM_SEGMENTS_BY_SIDE: u32 = 2;
+
+/// Grid of the HEALPix cells in view, drawn at the order of the tiles
+pub struct HEALPixGrid {
+    // Properties
+    pub color: ColorRGBA,
+    pub show_labels: bool,
+    pub enabled: bool,
+    thickness: f32,
+    // Frame of the HEALPix tessellation
+    frame: CooSystem,
+
+    // Render Text Manager
+    text_renderer: TextRenderManager,
+    line_style: line::Style,
+
+    // The labels of the cells drawn during the last frame
+    labels: Vec<(String, XYScreen)>,
+}
+
+impl HEALPixGrid {
+    pub fn new(aladin_div: &HtmlElement) -> Result<Self, JsValue> {
+        let mut text_renderer = TextRenderManager::new(aladin_div)?;
+
+        let color = ColorRGBA {
+            r: 0.59,
+            g: 0.59,
+            b: 0.86,
+            a: 1.0,
+        };
+        text_renderer.set_color(&color);
+
+        Ok(HEALPixGrid {
+            color,
+            show_labels: true,
+            enabled: false,
+            thickness: 1.0,
+            frame: CooSystem::ICRS,
+            text_renderer,
+            line_style: line::Style::None,
+            labels: vec![],
+        })
+    }
+
+    pub fn set_cfg(
+        &mut self,
+        new_cfg: HEALPixGridCfg,
+        camera: &mut CameraViewPort,
+        projection: &ProjectionType,
+    ) -> Result<(), JsValue> {
+        let HEALPixGridCfg {
+            color,
+            thickness,
+            opacity,
+            show_labels,
+            label_size,
+            enabled,
+            frame,
+        } = new_cfg;
+
+        if let Some(color) = color {
+            self.color = ColorRGBA {
+                r: color.r,
+                g: color.g,
+                b: color.b,
+                a: self.color.a,
+            };
+            self.text_renderer.set_color(&self.color);
+        }
+
+        if let Some(opacity) = opacity {
+            self.color.a = opacity;
+            self.text_renderer.set_color(&self.color);
+        }
+
+        if let Some(thickness) = thickness {
+            self.thickness = thickness;
+        }
+
+        if let Some(show_labels) = show_labels {
+            self.show_labels = show_labels;
+        }
+
+        if let Some(label_size) = label_size {
+            self.text_renderer.set_font_size(label_size as u32);
+        }
+
+        // The cells in view are computed by the camera only for the registered frames
+        let (was_enabled, prev_frame) = (self.enabled, self.frame);
+        if let Some(enabled) = enabled {
+            self.enabled = enabled;
+        }
+        if let Some(frame) = frame {
+            self.frame = frame;
+        }
+
+        if was_enabled && (!self.enabled || prev_frame != self.frame) {
+            camera.unregister_view_frame(prev_frame, projection);
+        }
+        if self.enabled && (!was_enabled || prev_frame != self.frame) {
+            camera.register_view_frame(self.frame, projection);
+        }
+
+        if !self.enabled {
+            self.labels.clear();
+        }
+
+        Ok(())
+    }
+
+    pub fn draw(
+        &mut self,
+        camera: &mut CameraViewPort,
+        projection: &ProjectionType,
+        rasterizer: &mut RasterizedLineRenderer,
+    ) -> Result<(), JsValue> {
+        self.labels.clear();
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let depth = camera.get_texture_depth();
+        let n_segments_by_side = (16 >> depth).max(MIN_NUM_SEGMENTS_BY_SIDE);
+        let frame = self.frame;
+
+        let cells = camera.get_hpx_cells(depth, frame).cloned().collect::<Vec<_>>();
+
+        let mut paths = vec![];
+        for cell in cells.iter() {
+            if is_crossing_projection(cell, frame, camera, projection) {
+                continue;
+            }
+
+            let vertices = cell
+                .path_along_cell_edge(n_segments_by_side)
+                .iter()
+                .map(|&(lon, lat)| project(lon, lat, frame, camera, projection))
+                .collect::<Option<Vec<_>>>();
+
+            if let Some(vertices) = vertices {
+                paths.push(PathVertices {
+                    vertices,
+                    closed: true,
+                });
+
+                if self.show_labels {
+                    let (lon, lat) = cell.center();
+                    if let Some(ndc) = project(lon, lat, frame, camera, projection) {
+                        let ndc = cgmath::Vector2::new(ndc[0] as f64, ndc[1] as f64);
+                        let pos = crate::math::projection::ndc_to_screen_space(&ndc, camera);
+
+                        self.labels.push((label(cell), pos));
+                    }
+                }
+            }
+        }
+
+        rasterizer.add_stroke_paths(
+            paths.into_iter(),
+            self.thickness,
+            &self.color,
+            &self.line_style,
+        );
+
+        Ok(())
+    }
+
+    pub fn draw_labels(&mut self) -> Result<(), JsValue> {
+        if self.enabled && self.show_labels {
+            self.text_renderer.begin();
+            for (content, position) in self.labels.iter() {
+                let position = position.cast::<f32>().unwrap_abort();
+                self.text_renderer.add_label(content, &position, cgmath::Rad(0.0))?;
+            }
+            self.text_renderer.end();
+        }
+
+        Ok(())
+    }
+}
+
+fn label(cell: &HEALPixCell) -> String {
+    format!("{}/{}", cell.depth(), cell.idx())
+}
+
+// Project a position given in the HEALPix frame into the normalized device space
+fn project(
+    lon: f64,
+    lat: f64,
+    frame: CooSystem,
+    camera: &CameraViewPort,
+    projection: &ProjectionType,
+) -> Option<[f32; 2]> {
+    let xyzw = crate::math::lonlat::radec_to_xyzw(Angle(lon), Angle(lat));
+    let xyzw = crate::coosys::apply_coo_system(
+        frame,
+        camera.get_coo_system(),
+        &xyzw,
+        camera.get_horizontal_frame(),
+    );
+
+    projection
+        .model_to_normalized_device_space(&xyzw, camera)
+        .map(|v| [v.x as f32, v.y as f32])
+}
@@ -7,9 +7,11 @@ use cgmath::InnerSpace;
 use cgmath::Vector3;
 
 use crate::grid::XYScreen;
+use crate::math::angle;
 use crate::math::angle::SerializeFmt;
 use crate::math::lonlat::LonLat;
 use crate::math::TWICE_PI;
+use al_api::coo_system::CooSystem;
 
 use crate::math::angle::ToAngle;
 use cgmath::Vector2;
@@ -77,7 +79,16 @@ impl Label {
         }
 
         //let content = fmt.to_string(lon.to_angle());
-        let content = al_api::angle_fmt::Format::toSexagesimal(lon.to_degrees() / 15.0, 8, false);
+        // The longitudes are given in hours only for the equatorial frames
+        let content = match camera.get_coo_system() {
+            coo_sys if coo_sys.is_equatorial() => {
+                angle::to_sexagesimal(lon.to_degrees() / 15.0, 8, false)
+            }
+            CooSystem::ALTAZ => {
+                angle::to_sexagesimal(crate::altaz::azimuth(lon.to_degrees()), 7, false)
+            }
+            _ => angle::to_sexagesimal(lon.to_degrees(), 7, false),
+        };
 
         let position = if !fov.is_allsky() {
             d1 + OFF_TANGENT * dt - OFF_BI_TANGENT * db
@@ -131,7 +142,7 @@ impl Label {
         let db = Vector2::new(dt.y.abs(), dt.x.abs());
 
         //let content = SerializeFmt::DMS.to_string(lonlat.lat());
-        let content = al_api::angle_fmt::Format::toSexagesimal(lonlat.lat().to_degrees(), 7, false);
+        let content = angle::to_sexagesimal(lonlat.lat().to_degrees(), 7, false);
 
         let fov = camera.get_field_of_view();
         let position = if !fov.is_allsky() && !fov.contains_pole() {
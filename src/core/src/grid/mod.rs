@@ -1,3 +1,4 @@
+pub mod hpx;
 pub mod label;
 pub mod meridian;
 pub mod parallel;
@@ -14,6 +15,7 @@ use crate::renderable::line::PathVertices;
 use crate::renderable::Renderer;
 use crate::ProjectionType;
 use al_api::color::ColorRGBA;
+use al_api::coo_system::CooSystem;
 
 use al_api::grid::GridCfg;
 use cgmath::InnerSpace;
@@ -25,6 +27,7 @@ pub struct ProjetedGrid {
     pub show_labels: bool,
     pub enabled: bool,
     pub label_scale: f32,
+    pub show_horizon: bool,
     thickness: f32,
 
     // Render Text Manager
@@ -35,6 +38,8 @@ pub struct ProjetedGrid {
 
     meridians: Vec<Meridian>,
     parallels: Vec<Parallel>,
+    // The altitude 0 parallel drawn in the horizontal frame
+    horizon: Option<Parallel>,
 }
 
 use crate::shader::ShaderManager;
@@ -59,11 +64,13 @@ impl ProjetedGrid {
         let show_labels = true;
         let enabled = false;
         let label_scale = 1.0;
+        let show_horizon = true;
         let line_style = line::Style::None;
         let fmt = angle::SerializeFmt::DMS;
         let thickness = 2.0;
         let meridians = Vec::new();
         let parallels = Vec::new();
+        let horizon = None;
 
         let grid = ProjetedGrid {
             color,
@@ -71,11 +78,13 @@ impl ProjetedGrid {
             show_labels,
             enabled,
             label_scale,
+            show_horizon,
             thickness,
 
             text_renderer,
             meridians,
             parallels,
+            horizon,
             fmt,
         };
         // Initialize the vertices & labels
@@ -98,6 +107,7 @@ impl ProjetedGrid {
             label_size,
             enabled,
             fmt,
+            show_horizon,
         } = new_cfg;
 
         if let Some(color) = color {
@@ -128,6 +138,10 @@ impl ProjetedGrid {
             self.fmt = fmt.into();
         }
 
+        if let Some(show_horizon) = show_horizon {
+            self.show_horizon = show_horizon;
+        }
+
         if let Some(label_size) = label_size {
             self.label_scale = label_size;
             self.text_renderer.set_font_size(label_size as u32);
@@ -205,6 +219,12 @@ impl ProjetedGrid {
             parallels
         };
 
+        self.horizon = if self.show_horizon && camera.get_coo_system() == CooSystem::ALTAZ {
+            parallel::get_intersecting_parallel(0.0, camera, projection)
+        } else {
+            None
+        };
+
         // update the line buffers
         let paths = self
             .meridians
@@ -224,6 +244,20 @@ impl ProjetedGrid {
         let m = camera.get_screen_size().magnitude();
         rasterizer.add_stroke_paths(paths, self.thickness, &self.color, &self.line_style);
 
+        // The horizon is drawn thicker and opaque
+        if let Some(horizon) = &self.horizon {
+            let paths = horizon
+                .get_lines_vertices()
+                .into_iter()
+                .map(|vertices| PathVertices {
+                    closed: false,
+                    vertices,
+                });
+            let color = ColorRGBA { a: 1.0, ..self.color };
+
+            rasterizer.add_stroke_paths(paths, 2.0 * self.thickness, &color, &self.line_style);
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use crate::healpix::cell::MAX_HPX_DEPTH;
+use crate::healpix::coverage::HEALPixCoverage;
+use crate::healpix::space_time::{SpaceTimeCoverage, MAX_TIME_DEPTH};
+
+/// A MOC parsed from its ASCII serialization
+pub enum ASCIIMOC {
+    Space(HEALPixCoverage),
+    SpaceTime(SpaceTimeCoverage),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Qty {
+    Space,
+    Time,
+}
+
+impl Qty {
+    fn max_depth(self) -> u8 {
+        match self {
+            Qty::Space => MAX_HPX_DEPTH,
+            Qty::Time => MAX_TIME_DEPTH,
+        }
+    }
+
+    fn num_cells(self, depth: u8) -> u64 {
+        match self {
+            Qty::Space => 12 << (2 * depth),
+            Qty::Time => 2 << depth,
+        }
+    }
+
+    // Shift giving the first index at the max depth of a cell
+    fn shift(self, depth: u8) -> u8 {
+        match self {
+            Qty::Space => 2 * (MAX_HPX_DEPTH - depth),
+            Qty::Time => MAX_TIME_DEPTH - depth,
+        }
+    }
+}
+
+/// Parse a S-MOC, a T-MOC or a ST-MOC from its IVOA ASCII serialization,
+/// e.g. `3/1-5 4/30` or `t61/1-3 s3/1 4/30`
+///
+/// The cells can also be separated by commas as in the former Aladin ASCII format.
+/// A T-MOC is seen as a ST-MOC observing the whole sky.
+///
+/// See the MOC 2.0 recommendation: <https://www.ivoa.net/documents/MOC/>
+pub fn parse(s: &str) -> Result<ASCIIMOC, String> {
+    let mut elems = vec![];
+    let (mut time_ranges, mut z29_ranges) = (vec![], vec![]);
+
+    let (mut time_seen, mut space_seen) = (false, false);
+    let mut space_depth = 0;
+
+    let mut qty = Qty::Space;
+    let mut depth = None;
+    for token in s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let mut token = token;
+        if let Some(rest) = token.strip_prefix('t') {
+            // A new time part closes the previous (time, space) element of a ST-MOC
+            if qty == Qty::Space && space_seen {
+                elems.push((
+                    sorted_ranges(std::mem::take(&mut time_ranges)),
+                    std::mem::take(&mut z29_ranges),
+                ));
+            }
+
+            qty = Qty::Time;
+            time_seen = true;
+            depth = None;
+            token = rest;
+        } else if let Some(rest) = token.strip_prefix('s') {
+            qty = Qty::Space;
+            space_seen = true;
+            depth = None;
+            token = rest;
+        }
+
+        if let Some((d, rest)) = token.split_once('/') {
+            let d = d
+                .parse::<u8>()
+                .ok()
+                .filter(|d| *d <= qty.max_depth())
+                .ok_or_else(|| format!("Invalid depth in {token}"))?;
+
+            if qty == Qty::Space {
+                space_depth = space_depth.max(d);
+            }
+
+            depth = Some(d);
+            token = rest;
+        }
+
+        if token.is_empty() {
+            continue;
+        }
+
+        let depth = depth.ok_or_else(|| format!("No depth given before {token}"))?;
+        let (first, last) = token.split_once('-').unwrap_or((token, token));
+        let (first, last) = first
+            .parse::<u64>()
+            .ok()
+            .zip(last.parse::<u64>().ok())
+            .filter(|(first, last)| first <= last && *last < qty.num_cells(depth))
+            .ok_or_else(|| format!("Invalid cell {token} at depth {depth}"))?;
+
+        let shift = qty.shift(depth);
+        let range = (first << shift)..((last + 1) << shift);
+        match qty {
+            Qty::Space => z29_ranges.push(range),
+            Qty::Time => time_ranges.push(range),
+        }
+    }
+
+    let moc = if !time_seen {
+        ASCIIMOC::Space(HEALPixCoverage::from_z29_ranges(space_depth, z29_ranges))
+    } else if !space_seen {
+        ASCIIMOC::SpaceTime(SpaceTimeCoverage::from_time_ranges(sorted_ranges(
+            time_ranges,
+        )))
+    } else {
+        elems.push((sorted_ranges(time_ranges), z29_ranges));
+        ASCIIMOC::SpaceTime(SpaceTimeCoverage::new(space_depth, elems))
+    };
+
+    Ok(moc)
+}
+
+// Sort and merge overlapping ranges
+fn sorted_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_unstable_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, sorted_ranges, ASCIIMOC};
+
+    fn sky_fraction(moc: &ASCIIMOC) -> f64 {
+        match moc {
+            ASCIIMOC::Space(moc) => moc.sky_fraction(),
+            ASCIIMOC::SpaceTime(moc) => moc.project(&(0.0..f64::INFINITY)).sky_fraction(),
+        }
+    }
+
+    #[test]
+    fn space_moc() {
+        let moc = parse("0/1-2 1/0, 1/3\n2/").unwrap();
+
+        assert!(matches!(moc, ASCIIMOC::Space(_)));
+        assert!((sky_fraction(&moc) - (2.0 / 12.0 + 2.0 / 48.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn time_and_space_time_mocs() {
+        let day: u64 = 86_400_000_000;
+
+        let tmoc = parse("t61/0-9").unwrap();
+        assert_eq!(sky_fraction(&tmoc), 1.0);
+
+        // base cell 0 observed during the first day, base cells 1 and 2 during the 10th
+        let stmoc = parse(&format!(
+            "t61/0-{} s0/0 t61/{}-{} s0/1-2",
+            day - 1,
+            9 * day,
+            10 * day - 1
+        ))
+        .unwrap();
+        match stmoc {
+            ASCIIMOC::SpaceTime(stmoc) => {
+                assert_eq!(stmoc.time_range(), Some(0.0..10.0));
+                assert!((stmoc.project(&(0.5..2.0)).sky_fraction() - 1.0 / 12.0).abs() < 1e-9);
+                assert!((stmoc.project(&(9.0..9.5)).sky_fraction() - 2.0 / 12.0).abs() < 1e-9);
+            }
+            _ => panic!("not a space-time MOC"),
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse("0/12").is_err());
+        assert!(parse("30/1").is_err());
+        assert!(parse("1-2").is_err());
+        assert!(parse("2/5-3").is_err());
+        assert!(parse("2/a").is_err());
+    }
+
+    #[test]
+    fn merge_ranges() {
+        assert_eq!(
+            sorted_ranges(vec![5..6, 0..2, 1..3, 3..4]),
+            vec![0..4, 5..6]
+        );
+    }
+}
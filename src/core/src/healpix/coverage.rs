@@ -1,9 +1,25 @@
 use crate::math::lonlat::LonLatT;
+use crate::math::sph_geom::{bbox::BoundingBox, region::PoleContained};
 use crate::math::PI;
 use crate::math::{self, lonlat::LonLat};
 
-use cgmath::{Vector3, Vector4};
-use moclib::{moc::range::RangeMOC, qty::Hpx, ranges::SNORanges};
+use crate::math::{HALF_PI, TWICE_PI};
+use crate::Abort;
+
+use cgmath::{InnerSpace, Vector3, Vector4};
+use healpix::compass_point::Ordinal;
+use healpix::sph_geom::coo3d::Coo3D;
+use healpix::sph_geom::{ContainsSouthPoleMethod, Polygon};
+use moclib::{
+    elemset::range::MocRanges,
+    moc::{
+        range::{CellAndNeighs, RangeMOC},
+        CellMOCIterator, CellOrCellRangeMOCIterator, RangeMOCIntoIterator, RangeMOCIterator,
+    },
+    qty::Hpx,
+    ranges::SNORanges,
+};
+use std::ops::Range;
 pub type Smoc = RangeMOC<u64, Hpx<u64>>;
 
 use crate::healpix::cell::HEALPixCell;
@@ -54,6 +70,64 @@ impl HEALPixCoverage {
         HEALPixCoverage(moc)
     }
 
+    /// Build a coverage from polygonal rings given by their (lon, lat) vertices in radians
+    ///
+    /// The inside of a ring is on its left, i.e. a ring goes counter-clockwise around its
+    /// inside as seen on the sky from the center of the sphere. A hole is thus given by
+    /// a clockwise ring. Self-intersecting rings follow the even-odd rule.
+    ///
+    /// The coverage is made of the points enclosed by more rings than its outside, which is
+    /// the region enclosed by the fewest rings. If a control point inside the coverage is given,
+    /// the coverage is made of the points enclosed by at least as many rings as it.
+    pub fn from_rings(
+        depth: u8,
+        rings: &[Vec<(f64, f64)>],
+        control_point: Option<(f64, f64)>,
+    ) -> Result<Self, String> {
+        let rings = rings
+            .iter()
+            .map(|vertices| Ring::new(vertices))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Number of rings enclosing the cells of each segment of order 29 indices
+        let insides = rings
+            .iter()
+            .map(|ring| {
+                let moc = RangeMOC::from_polygon_with_control_point(
+                    &ring.vertices[..],
+                    ring.inside,
+                    depth,
+                );
+                (&moc).into_range_moc_iter().collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let segments = count_enclosing_regions(&insides);
+
+        let min_count = if let Some((lon, lat)) = control_point {
+            let coo = Coo3D::from_sph_coo(lon, lat);
+            rings
+                .iter()
+                .filter(|ring| ring.polygon.contains(&coo))
+                .count()
+        } else {
+            segments.iter().map(|(_, count)| *count).min().unwrap_or(0) + 1
+        };
+
+        let z29_ranges = segments
+            .into_iter()
+            .filter(|(_, count)| *count >= min_count)
+            .map(|(z29_rng, _)| z29_rng)
+            .collect();
+
+        Ok(HEALPixCoverage::from_z29_ranges(depth, z29_ranges))
+    }
+
+    /// Build a coverage from ranges of cells given at the order 29
+    pub fn from_z29_ranges(depth: u8, z29_ranges: Vec<Range<u64>>) -> Self {
+        let moc = RangeMOC::new(depth, MocRanges::new_from(z29_ranges));
+        HEALPixCoverage(moc)
+    }
+
     pub fn from_cone(lonlat: &LonLatT<f64>, rad: f64, depth: u8) -> Self {
         if rad >= PI {
             Self::allsky(depth)
@@ -68,6 +142,53 @@ impl HEALPixCoverage {
         }
     }
 
+    /// Elliptical cone of semi-major axis `a`, semi-minor axis `b` and position angle `pa`,
+    /// i.e. the angle between the north and the major axis, east of north. Angles are in radians.
+    pub fn from_elliptical_cone(lonlat: &LonLatT<f64>, a: f64, b: f64, pa: f64, depth: u8) -> Self {
+        HEALPixCoverage(RangeMOC::from_elliptical_cone(
+            lonlat.lon().to_radians(),
+            lonlat.lat().to_radians(),
+            a,
+            b,
+            pa,
+            depth,
+            0,
+        ))
+    }
+
+    /// Box of half widths `a` and `b` rotated by the position angle `pa`. Angles are in radians.
+    pub fn from_box(lonlat: &LonLatT<f64>, a: f64, b: f64, pa: f64, depth: u8) -> Self {
+        HEALPixCoverage(RangeMOC::from_box(
+            lonlat.lon().to_radians(),
+            lonlat.lat().to_radians(),
+            a,
+            b,
+            pa,
+            depth,
+        ))
+    }
+
+    /// Zone delimited by two meridians and two parallels. Angles are in radians.
+    ///
+    /// The zone crosses the meridian 0 if `lon_min > lon_max`
+    pub fn from_zone(lon_min: f64, lat_min: f64, lon_max: f64, lat_max: f64, depth: u8) -> Self {
+        HEALPixCoverage(RangeMOC::from_zone(
+            lon_min, lat_min, lon_max, lat_max, depth,
+        ))
+    }
+
+    /// Annulus between the radii `r_int` and `r_ext` given in radians
+    pub fn from_ring(lonlat: &LonLatT<f64>, r_int: f64, r_ext: f64, depth: u8) -> Self {
+        HEALPixCoverage(RangeMOC::from_ring(
+            lonlat.lon().to_radians(),
+            lonlat.lat().to_radians(),
+            r_int,
+            r_ext,
+            depth,
+            0,
+        ))
+    }
+
     pub fn allsky(depth_max: u8) -> Self {
         let moc = RangeMOC::new_full_domain(depth_max);
         HEALPixCoverage(moc)
@@ -82,6 +203,21 @@ impl HEALPixCoverage {
         self.0.is_in(lonlat.lon().0, lonlat.lat().0)
     }
 
+    /// The cell of the coverage containing a position, i.e. the biggest cell of the
+    /// coverage containing it
+    pub fn cell_containing(&self, lonlat: &LonLatT<f64>) -> Option<HEALPixCell> {
+        let cell = HEALPixCell::new(self.depth(), lonlat.lon().0, lonlat.lat().0);
+        if !self.intersects_cell(&cell) {
+            return None;
+        }
+
+        // look for its first ancestor fully covered, starting from the base cell
+        (0..=cell.depth())
+            .rev()
+            .map(|delta_depth| cell.ancestor(delta_depth))
+            .find(|ancestor| self.0.moc_ranges().contains_range(&ancestor.z_29_rng()))
+    }
+
     // O(log2(N))
     pub fn intersects_cell(&self, cell: &HEALPixCell) -> bool {
         let z29_rng = cell.z_29_rng();
@@ -105,9 +241,338 @@ impl HEALPixCoverage {
         HEALPixCoverage(self.0.not())
     }
 
+    pub fn union(&self, other: &Self) -> Self {
+        HEALPixCoverage(self.0.union(&other.0))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        HEALPixCoverage(self.0.intersection(&other.0))
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        HEALPixCoverage(self.0.minus(&other.0))
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        HEALPixCoverage(self.0.xor(&other.0))
+    }
+
+    /// Express the coverage at another depth
+    ///
+    /// The coverage is degraded if the new depth is lower than its current one,
+    /// otherwise its cells are kept as they are
+    pub fn with_depth(&self, depth: u8) -> Self {
+        if depth < self.depth() {
+            HEALPixCoverage(self.0.degraded(depth))
+        } else {
+            HEALPixCoverage(RangeMOC::new(depth, self.0.moc_ranges().clone()))
+        }
+    }
+
     pub fn empty(depth: u8) -> Self {
         HEALPixCoverage(RangeMOC::new_empty(depth))
     }
+
+    /// Serialize the coverage into the IVOA MOC 2.0 FITS format
+    pub fn to_fits(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![];
+        (&self.0)
+            .into_range_moc_iter()
+            .to_fits_ivoa(None, None, &mut bytes)
+            .map_err(|e| e.to_string())?;
+
+        Ok(bytes)
+    }
+
+    /// Serialize the coverage into the Aladin JSON format
+    pub fn to_json(&self) -> Result<String, String> {
+        let mut bytes = vec![];
+        (&self.0)
+            .into_range_moc_iter()
+            .cells()
+            .to_json_aladin(None, &mut bytes)
+            .map_err(|e| e.to_string())?;
+
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    /// Serialize the coverage into the IVOA MOC 2.0 ASCII format
+    pub fn to_ascii(&self) -> Result<String, String> {
+        let mut bytes = vec![];
+        (&self.0)
+            .into_range_moc_iter()
+            .cells()
+            .cellranges()
+            .to_ascii_ivoa(None, false, &mut bytes)
+            .map_err(|e| e.to_string())?;
+
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    /// Number of cells of each depth, from 0 to the depth of the coverage
+    pub fn num_cells_per_depth(&self) -> Vec<u64> {
+        let mut num_cells = vec![0; self.depth() as usize + 1];
+        for cell in (&self.0).into_range_moc_iter().cells() {
+            num_cells[cell.depth as usize] += 1;
+        }
+
+        num_cells
+    }
+
+    /// A cone containing the coverage, given by its (lon, lat) center and its radius in radians
+    ///
+    /// The cone is centered on the barycenter of the cells. `None` if the coverage is empty.
+    pub fn bounding_cone(&self) -> Option<((f64, f64), f64)> {
+        let barycenter = (&self.0)
+            .into_range_moc_iter()
+            .cells()
+            .map(|cell| {
+                let (lon, lat) = HEALPixCell(cell.depth, cell.idx).center();
+                // the area of a cell is divided by 4 at each depth
+                lonlat_to_xyz(lon, lat) * 0.25_f64.powi(cell.depth as i32)
+            })
+            .reduce(|a, b| a + b)?;
+        // The barycenter of a coverage spread all over the sky is close to the center of the sphere
+        let center = if barycenter.magnitude() > 1e-9 {
+            barycenter.normalize()
+        } else {
+            Vector3::unit_x()
+        };
+
+        let radius = self
+            .cell_vertices()
+            .into_iter()
+            .map(|(lon, lat)| center.angle(lonlat_to_xyz(lon, lat)).0)
+            .fold(0.0, f64::max);
+
+        Some((xyz_to_lonlat(&center), radius))
+    }
+
+    /// The (lon, lat) box containing the coverage. `None` if the coverage is empty.
+    ///
+    /// The box starts at a negative longitude if it crosses the meridian 0
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let (lon, lat): (Vec<_>, Vec<_>) = self.cell_vertices().into_iter().unzip();
+        if lon.is_empty() {
+            return None;
+        }
+
+        let pole_contained = match (self.0.is_in(0.0, -HALF_PI), self.0.is_in(0.0, HALF_PI)) {
+            (false, false) => PoleContained::None,
+            (false, true) => PoleContained::North,
+            (true, false) => PoleContained::South,
+            (true, true) => PoleContained::Both,
+        };
+
+        Some(BoundingBox::from_points(&pole_contained, lon, &lat))
+    }
+
+    /// Split the coverage into its connected components, i.e. the sets of cells linked
+    /// by their edges
+    pub fn connected_components(&self) -> Vec<HEALPixCoverage> {
+        let cells = (&self.0)
+            .into_range_moc_iter()
+            .cells()
+            .map(|cell| HEALPixCell(cell.depth, cell.idx))
+            .collect::<Vec<_>>();
+
+        // Union-find forest over the indices of the cells, sorted by their order 29 index
+        let mut parents = (0..cells.len()).collect::<Vec<_>>();
+        for CellAndNeighs { cell, neigs } in self.0.all_cells_with_unidirectional_neigs() {
+            let idx = cells
+                .binary_search(&HEALPixCell(cell.depth, cell.idx))
+                .unwrap_abort();
+
+            for dir in [Ordinal::NW, Ordinal::NE, Ordinal::SE, Ordinal::SW] {
+                if let Some(&neig_idx) = neigs.get(dir) {
+                    let root = find_root(&mut parents, idx);
+                    parents[root] = find_root(&mut parents, neig_idx);
+                }
+            }
+        }
+
+        let mut component_of_root = vec![None; cells.len()];
+        let mut components: Vec<Vec<HEALPixCell>> = vec![];
+        for (idx, cell) in cells.into_iter().enumerate() {
+            let root = find_root(&mut parents, idx);
+            let component = *component_of_root[root].get_or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+
+            components[component].push(cell);
+        }
+
+        components
+            .into_iter()
+            .map(|cells| Self::from_hpx_cells(self.depth(), cells.iter(), Some(cells.len())))
+            .collect()
+    }
+
+    // Vertices of the cells. The cells of depth lower than VERTICES_MIN_DEPTH are split
+    // so that the vertices follow closely their curved edges.
+    fn cell_vertices(&self) -> Vec<(f64, f64)> {
+        const VERTICES_MIN_DEPTH: u8 = 5;
+        let min_depth = VERTICES_MIN_DEPTH.min(self.depth());
+
+        (&self.0)
+            .into_range_moc_iter()
+            .cells()
+            .flat_map(|cell| {
+                let depth = cell.depth.max(min_depth);
+                let shift = 2 * (depth - cell.depth);
+
+                ((cell.idx << shift)..((cell.idx + 1) << shift))
+                    .flat_map(move |idx| HEALPixCell(depth, idx).vertices())
+            })
+            .collect()
+    }
+}
+
+// Root of a node in a union-find forest, halving its path on the way
+fn find_root(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+
+    idx
+}
+
+// A polygonal ring with a point of its inside
+struct Ring {
+    // (lon, lat) vertices in radians
+    vertices: Vec<(f64, f64)>,
+    inside: (f64, f64),
+    polygon: Polygon,
+}
+
+impl Ring {
+    fn new(vertices: &[(f64, f64)]) -> Result<Self, String> {
+        let mut vertices = vertices
+            .iter()
+            .map(|&(lon, lat)| (lon.rem_euclid(TWICE_PI), lat))
+            .collect::<Vec<_>>();
+        // remove the repeated vertices, including the closing one
+        vertices.dedup();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        if vertices.len() < 3 {
+            return Err("A ring must have at least 3 distinct vertices".to_string());
+        }
+
+        let xyz = vertices
+            .iter()
+            .map(|&(lon, lat)| lonlat_to_xyz(lon, lat))
+            .collect::<Vec<_>>();
+        let edges = xyz
+            .iter()
+            .zip(xyz.iter().cycle().skip(1))
+            .map(|(a, b)| (a, b, a.cross(*b).magnitude()))
+            .collect::<Vec<_>>();
+
+        if edges
+            .iter()
+            .any(|(a, b, sin)| *sin < 1e-12 && a.dot(**b) < 0.0)
+        {
+            return Err("Two consecutive vertices of a ring cannot be antipodal".to_string());
+        }
+
+        // The points at the left and at the right of the longest edge
+        let (a, b, _) = edges
+            .iter()
+            .max_by(|(_, _, s1), (_, _, s2)| s1.total_cmp(s2))
+            .unwrap_abort();
+        let (left, right) = (side_point(a, b, true), side_point(a, b, false));
+
+        let polygon = |inside: &Vector3<f64>| {
+            Polygon::new_custom_vec3(
+                xyz.iter()
+                    .map(|v| Coo3D::from_vec3(v.x, v.y, v.z))
+                    .collect::<Vec<_>>()
+                    .into_boxed_slice(),
+                &ContainsSouthPoleMethod::ControlPointIn(Coo3D::from_vec3(
+                    inside.x, inside.y, inside.z,
+                )),
+            )
+        };
+
+        // For a self-intersecting ring, the longest edge can go clockwise around the inside.
+        // The inside is the side being on the left of the major part of the ring.
+        let left_polygon = polygon(&left);
+        let (mut len_left, mut len_right) = (0.0, 0.0);
+        for (a, b, _) in &edges {
+            let len = a.angle(**b).0;
+            let p = side_point(a, b, true);
+            if left_polygon.contains(&Coo3D::from_vec3(p.x, p.y, p.z)) {
+                len_left += len;
+            } else {
+                len_right += len;
+            }
+        }
+
+        let (inside, polygon) = if len_left >= len_right {
+            (left, left_polygon)
+        } else {
+            (right, polygon(&right))
+        };
+
+        Ok(Ring {
+            vertices,
+            inside: xyz_to_lonlat(&inside),
+            polygon,
+        })
+    }
+}
+
+// A point close to the middle of the edge [a, b], at its left or at its right as seen
+// from the center of the sphere
+fn side_point(a: &Vector3<f64>, b: &Vector3<f64>, left: bool) -> Vector3<f64> {
+    let n = a.cross(*b).normalize();
+    let eps = 1e-6 * (b - a).magnitude();
+
+    let m = (a + b).normalize();
+    if left {
+        (m - n * eps).normalize()
+    } else {
+        (m + n * eps).normalize()
+    }
+}
+
+fn lonlat_to_xyz(lon: f64, lat: f64) -> Vector3<f64> {
+    Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+}
+
+fn xyz_to_lonlat(v: &Vector3<f64>) -> (f64, f64) {
+    (v.y.atan2(v.x).rem_euclid(TWICE_PI), v.z.asin())
+}
+
+// Split the order 29 indices into segments along with the number of regions covering them
+fn count_enclosing_regions(regions: &[Vec<Range<u64>>]) -> Vec<(Range<u64>, usize)> {
+    let mut bounds = regions
+        .iter()
+        .flatten()
+        .flat_map(|rng| [(rng.start, 1), (rng.end, -1)])
+        .collect::<Vec<(u64, i64)>>();
+    bounds.sort_unstable();
+
+    let num_z29_cells = 12 << (2 * 29);
+    let mut segments = vec![];
+    let (mut start, mut count) = (0, 0);
+    for (z29, delta) in bounds
+        .into_iter()
+        .chain(std::iter::once((num_z29_cells, 0)))
+    {
+        if z29 > start {
+            segments.push((start..z29, count as usize));
+            start = z29;
+        }
+        count += delta;
+    }
+
+    segments
 }
 
 use core::ops::Deref;
@@ -118,3 +583,331 @@ impl Deref for HEALPixCoverage {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HEALPixCoverage;
+    use crate::healpix::cell::HEALPixCell;
+    use crate::math::{angle::ToAngle, lonlat::LonLatT};
+
+    const DEPTH: u8 = 8;
+
+    fn ring(vertices: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        vertices
+            .iter()
+            .map(|(lon, lat)| (lon.to_radians(), lat.to_radians()))
+            .collect()
+    }
+
+    // A square of half width `hw` degrees centered on (lon, lat = 0),
+    // counter-clockwise if seen on the sky
+    fn square(lon: f64, hw: f64) -> Vec<(f64, f64)> {
+        ring(&[
+            (lon + hw, -hw),
+            (lon - hw, -hw),
+            (lon - hw, hw),
+            (lon + hw, hw),
+        ])
+    }
+
+    fn reversed(mut ring: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+        ring.reverse();
+        ring
+    }
+
+    fn contains(moc: &HEALPixCoverage, lon: f64, lat: f64) -> bool {
+        moc.contains_lonlat(&LonLatT::new(
+            lon.to_radians().to_angle(),
+            lat.to_radians().to_angle(),
+        ))
+    }
+
+    fn lonlat(lon: f64, lat: f64) -> LonLatT<f64> {
+        LonLatT::new(lon.to_radians().to_angle(), lat.to_radians().to_angle())
+    }
+
+    #[test]
+    fn elliptical_cone() {
+        // the major axis goes towards the north
+        let moc = HEALPixCoverage::from_elliptical_cone(
+            &lonlat(30.0, 20.0),
+            10_f64.to_radians(),
+            2_f64.to_radians(),
+            0.0,
+            DEPTH,
+        );
+        assert!(contains(&moc, 30.0, 20.0));
+        assert!(contains(&moc, 30.0, 27.0));
+        // 7 degrees towards the east
+        assert!(!contains(&moc, 37.45, 20.0));
+
+        // the major axis goes towards the east
+        let moc = HEALPixCoverage::from_elliptical_cone(
+            &lonlat(30.0, 20.0),
+            10_f64.to_radians(),
+            2_f64.to_radians(),
+            90_f64.to_radians(),
+            DEPTH,
+        );
+        assert!(!contains(&moc, 30.0, 27.0));
+        assert!(contains(&moc, 37.45, 20.0));
+    }
+
+    #[test]
+    fn box_straddling_zero_meridian() {
+        let moc = HEALPixCoverage::from_box(
+            &lonlat(0.0, 0.0),
+            10_f64.to_radians(),
+            2_f64.to_radians(),
+            0.0,
+            DEPTH,
+        );
+        assert!(contains(&moc, 0.0, 8.0));
+        assert!(contains(&moc, 359.0, -1.5));
+        assert!(contains(&moc, 1.0, 1.5));
+        assert!(!contains(&moc, 0.0, 12.0));
+        assert!(!contains(&moc, 5.0, 0.0));
+        assert!(!contains(&moc, 355.0, 0.0));
+    }
+
+    #[test]
+    fn zone() {
+        // crossing the meridian 0
+        let moc = HEALPixCoverage::from_zone(
+            350_f64.to_radians(),
+            0.0,
+            10_f64.to_radians(),
+            20_f64.to_radians(),
+            DEPTH,
+        );
+        assert!(contains(&moc, 355.0, 10.0));
+        assert!(contains(&moc, 5.0, 10.0));
+        assert!(!contains(&moc, 180.0, 10.0));
+        assert!(!contains(&moc, 5.0, 30.0));
+        assert!(!contains(&moc, 5.0, -5.0));
+
+        // the complementary longitudes
+        let moc = HEALPixCoverage::from_zone(
+            10_f64.to_radians(),
+            0.0,
+            350_f64.to_radians(),
+            20_f64.to_radians(),
+            DEPTH,
+        );
+        assert!(!contains(&moc, 355.0, 10.0));
+        assert!(!contains(&moc, 5.0, 10.0));
+        assert!(contains(&moc, 180.0, 10.0));
+    }
+
+    #[test]
+    fn annulus() {
+        let moc = HEALPixCoverage::from_ring(
+            &lonlat(100.0, -40.0),
+            5_f64.to_radians(),
+            10_f64.to_radians(),
+            DEPTH,
+        );
+        assert!(!contains(&moc, 100.0, -40.0));
+        assert!(contains(&moc, 100.0, -32.5));
+        assert!(contains(&moc, 100.0, -47.5));
+        assert!(!contains(&moc, 100.0, -28.0));
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        use crate::downloader::request::moc::from_fits_hpx;
+        use crate::healpix::ascii::{self, ASCIIMOC};
+        use moclib::deser::fits::{self, MocIdxType, MocQtyType};
+        use moclib::moc::{
+            CellMOCIntoIterator, CellMOCIterator, RangeMOCIntoIterator, RangeMOCIterator,
+        };
+        use moclib::qty::Hpx;
+        use std::io::Cursor;
+
+        // The depth and the ranges of a MOC
+        let ranges = |moc: &HEALPixCoverage| {
+            let ranges = (&moc.0).into_range_moc_iter().collect::<Vec<_>>();
+            (moc.depth(), ranges)
+        };
+
+        let center = LonLatT::new(
+            30_f64.to_radians().to_angle(),
+            20_f64.to_radians().to_angle(),
+        );
+        let moc = HEALPixCoverage::from_cone(&center, 10_f64.to_radians(), DEPTH)
+            .union(&HEALPixCoverage::from_cone(&center, 1_f64.to_radians(), 10));
+        // serialized at a chosen depth
+        let moc = moc.with_depth(7);
+        assert_eq!(moc.depth(), 7);
+
+        let bytes = moc.to_fits().unwrap();
+        let from_fits = match fits::from_fits_ivoa(Cursor::new(&bytes[..])).unwrap() {
+            MocIdxType::U64(MocQtyType::<u64, _>::Hpx(moc)) => HEALPixCoverage(from_fits_hpx(moc)),
+            _ => panic!("not a S-MOC of u64"),
+        };
+        assert_eq!(ranges(&from_fits), ranges(&moc));
+
+        let json = moc.to_json().unwrap();
+        let from_json = moclib::deser::json::from_json_aladin::<u64, Hpx<u64>>(&json)
+            .unwrap()
+            .into_cell_moc_iter()
+            .ranges()
+            .into_range_moc();
+        assert_eq!(ranges(&HEALPixCoverage(from_json)), ranges(&moc));
+
+        let ascii = moc.to_ascii().unwrap();
+        match ascii::parse(&ascii).unwrap() {
+            ASCIIMOC::Space(from_ascii) => assert_eq!(ranges(&from_ascii), ranges(&moc)),
+            _ => panic!("not a S-MOC"),
+        }
+    }
+
+    #[test]
+    fn ring_straddling_zero_meridian() {
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[square(0.0, 10.0)], None).unwrap();
+        assert!(contains(&moc, 0.0, 0.0));
+        assert!(contains(&moc, 355.0, 5.0));
+        assert!(!contains(&moc, 180.0, 0.0));
+        assert!(moc.sky_fraction() < 0.02);
+
+        // clockwise, the inside is the rest of the sky
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[reversed(square(0.0, 10.0))], None).unwrap();
+        assert!(!contains(&moc, 0.0, 0.0));
+        assert!(contains(&moc, 180.0, 0.0));
+        assert!(moc.sky_fraction() > 0.98);
+    }
+
+    #[test]
+    fn rings_around_poles() {
+        // decreasing longitudes go counter-clockwise around the north pole
+        let cap = ring(&[(270.0, 60.0), (180.0, 60.0), (90.0, 60.0), (0.0, 60.0)]);
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[cap], None).unwrap();
+        assert!(contains(&moc, 0.0, 90.0));
+        assert!(contains(&moc, 123.0, 80.0));
+        assert!(!contains(&moc, 0.0, 50.0));
+        assert!(!contains(&moc, 0.0, -90.0));
+
+        // larger than a hemisphere, going clockwise around the south pole
+        let ring = ring(&[(270.0, -30.0), (180.0, -30.0), (90.0, -30.0), (0.0, -30.0)]);
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[ring], None).unwrap();
+        assert!(contains(&moc, 0.0, 0.0));
+        assert!(contains(&moc, 0.0, 90.0));
+        assert!(!contains(&moc, 0.0, -90.0));
+        assert!(moc.sky_fraction() > 0.5);
+    }
+
+    #[test]
+    fn rings_with_holes() {
+        let rings = [square(0.0, 20.0), reversed(square(0.0, 5.0))];
+
+        for control_point in [None, Some((12_f64.to_radians(), 0.0))] {
+            let moc = HEALPixCoverage::from_rings(DEPTH, &rings, control_point).unwrap();
+            assert!(contains(&moc, 12.0, 0.0));
+            assert!(contains(&moc, 348.0, 0.0));
+            assert!(!contains(&moc, 0.0, 0.0));
+            assert!(!contains(&moc, 0.0, 40.0));
+        }
+    }
+
+    #[test]
+    fn multiple_rings() {
+        let rings = [square(0.0, 10.0), square(15.0, 10.0), square(90.0, 10.0)];
+
+        // the union of the rings
+        let moc = HEALPixCoverage::from_rings(DEPTH, &rings, None).unwrap();
+        assert!(contains(&moc, 355.0, 0.0));
+        assert!(contains(&moc, 20.0, 0.0));
+        assert!(contains(&moc, 90.0, 0.0));
+        assert!(!contains(&moc, 180.0, 0.0));
+
+        // the overlap of the first two rings, enclosed by as many rings as the control point
+        let moc =
+            HEALPixCoverage::from_rings(DEPTH, &rings, Some((7.5_f64.to_radians(), 0.0))).unwrap();
+        assert!(contains(&moc, 7.5, 0.0));
+        assert!(!contains(&moc, 355.0, 0.0));
+        assert!(!contains(&moc, 90.0, 0.0));
+    }
+
+    #[test]
+    fn closed_and_invalid_rings() {
+        let mut closed = square(0.0, 10.0);
+        closed.push(closed[0]);
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[closed], None).unwrap();
+        assert!(contains(&moc, 0.0, 0.0));
+
+        assert!(
+            HEALPixCoverage::from_rings(DEPTH, &[ring(&[(0.0, 0.0), (10.0, 0.0)])], None).is_err()
+        );
+        assert!(HEALPixCoverage::from_rings(
+            DEPTH,
+            &[ring(&[(0.0, 0.0), (180.0, 0.0), (90.0, 45.0)])],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn cell_containing() {
+        // the base cell 0 and the cell 4 of depth 1, in the base cell 1
+        let moc = HEALPixCoverage::from_z29_ranges(2, vec![0..(1 << 58), (4 << 56)..(5 << 56)]);
+
+        let cell = |depth: u8, idx: u64| {
+            let (lon, lat) = HEALPixCell(depth, idx).center();
+            moc.cell_containing(&LonLatT::new(lon.to_angle(), lat.to_angle()))
+        };
+        assert_eq!(cell(2, 3), Some(HEALPixCell(0, 0)));
+        assert_eq!(cell(2, 17), Some(HEALPixCell(1, 4)));
+        assert_eq!(cell(2, 21), None);
+    }
+
+    #[test]
+    fn num_cells_per_depth() {
+        // the base cell 0 and the cell 4 of depth 1, in the base cell 1
+        let moc = HEALPixCoverage::from_z29_ranges(2, vec![0..(1 << 58), (4 << 56)..(5 << 56)]);
+        assert_eq!(moc.num_cells_per_depth(), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn bounding_cone_and_box() {
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[square(0.0, 10.0)], None).unwrap();
+
+        let ((lon, lat), radius) = moc.bounding_cone().unwrap();
+        assert!(lon.cos() > 0.9999 && lat.abs() < 1e-3);
+        // the distance from the center to the corners of the square
+        assert!((radius.to_degrees() - 14.1).abs() < 0.5);
+
+        let bbox = moc.bounding_box().unwrap();
+        assert!((bbox.lon_min().to_degrees() + 10.0).abs() < 0.5);
+        assert!((bbox.lon_max().to_degrees() - 10.0).abs() < 0.5);
+        assert!((bbox.lat_min().to_degrees() + 10.2).abs() < 0.5);
+        assert!((bbox.lat_max().to_degrees() - 10.2).abs() < 0.5);
+
+        let cap = ring(&[(270.0, 60.0), (180.0, 60.0), (90.0, 60.0), (0.0, 60.0)]);
+        let moc = HEALPixCoverage::from_rings(DEPTH, &[cap], None).unwrap();
+        let bbox = moc.bounding_box().unwrap();
+        assert!(bbox.all_lon());
+        assert_eq!(bbox.lat_max(), std::f64::consts::FRAC_PI_2);
+        assert!(bbox.lat_min().to_degrees() > 55.0);
+
+        assert!(HEALPixCoverage::empty(DEPTH).bounding_cone().is_none());
+        assert!(HEALPixCoverage::empty(DEPTH).bounding_box().is_none());
+    }
+
+    #[test]
+    fn connected_components() {
+        let rings = [square(0.0, 10.0), square(15.0, 10.0), square(90.0, 10.0)];
+        let moc = HEALPixCoverage::from_rings(DEPTH, &rings, None).unwrap();
+
+        let components = moc.connected_components();
+        assert_eq!(components.len(), 2);
+        let sky_fraction = components.iter().map(|c| c.sky_fraction()).sum::<f64>();
+        assert!((sky_fraction - moc.sky_fraction()).abs() < 1e-12);
+        assert!(components
+            .iter()
+            .any(|c| contains(c, 0.0, 0.0) && contains(c, 20.0, 0.0)));
+
+        assert!(HEALPixCoverage::empty(DEPTH)
+            .connected_components()
+            .is_empty());
+    }
+}
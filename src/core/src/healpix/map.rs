@@ -0,0 +1,759 @@
+use std::fmt::Debug;
+use std::io::Cursor;
+use std::ops::Range;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use al_api::coo_system::CooSystem;
+use fitsrs::card::Value;
+use fitsrs::fits::{AsyncFits, Fits};
+use fitsrs::hdu::data::InMemData;
+use fitsrs::hdu::extension::{AsyncXtensionHDU, XtensionHDU};
+use fitsrs::hdu::header::{extension::bintable::BinTable, Header};
+use futures::io::{AsyncRead, BufReader};
+use moclib::deser::fits::multiordermap::from_fits_multiordermap;
+
+use crate::healpix::cell::{HEALPixCell, MAX_HPX_DEPTH};
+use crate::healpix::coverage::HEALPixCoverage;
+use crate::renderable::image::compute_automatic_cuts;
+use crate::renderable::image::native_wcs::key;
+
+/// Value given by the HEALPix softwares to the unobserved pixels
+const UNSEEN: f64 = -1.6375e30;
+/// Max number of pixels sampled for computing the automatic cuts
+const MAX_NUM_SAMPLES_FOR_CUTS: usize = 1 << 20;
+
+/// A HEALPix map stored in a FITS binary table
+///
+/// It can be either a single resolution map, e.g. the all sky maps of Planck or WMAP,
+/// or a multi order map indexed by UNIQ cells, e.g. the probability sky maps
+/// of gravitational wave events.
+///
+/// See the HEALPix FITS convention: <https://healpix.sourceforge.io/data/examples/healpix_fits_specs.pdf>
+#[derive(Debug)]
+pub struct HEALPixMap {
+    // The order of the map, i.e. the order of its smallest cells
+    depth: u8,
+    frame: CooSystem,
+    values: Values,
+}
+
+#[derive(Debug)]
+enum Values {
+    // The values of all the cells of the map order in the NESTED scheme, NaN for the unseen ones
+    Fixed(Vec<f32>),
+    // The cells of a multi order map given as sorted ranges of order 29 indices,
+    // with their values being densities
+    MultiOrder {
+        ranges: Vec<Range<u64>>,
+        values: Vec<f32>,
+        // The FITS file of the map
+        fits: Vec<u8>,
+    },
+}
+
+impl HEALPixMap {
+    /// Parse a HEALPix map from the bytes of a FITS file
+    ///
+    /// The map is read from the first column of the first extension which
+    /// must be a binary table with `PIXTYPE = 'HEALPIX'`
+    pub fn from_fits(bytes: Vec<u8>) -> Result<Self, String> {
+        let mut map = {
+            let mut reader = Cursor::new(&bytes[..]);
+            let Fits { hdu } = Fits::from_reader(&mut reader).map_err(|e| e.to_string())?;
+
+            let table = match hdu.next() {
+                Ok(Some(XtensionHDU::BinTable(table))) => table,
+                _ => return Err("The first extension is not a binary table".to_string()),
+            };
+            let header = table.get_header();
+            if !is_healpix_table(header) {
+                return Err("The first extension is not a HEALPix binary table".to_string());
+            }
+
+            let data = match *table.get_data() {
+                InMemData::U8(data) => data,
+                _ => return Err("The binary table data could not be read".to_string()),
+            };
+
+            Self::from_table(header, data)?
+        };
+
+        // The moc crate reads the FITS file again for computing the credible regions
+        if let Values::MultiOrder { fits, .. } = &mut map.values {
+            *fits = bytes;
+        }
+
+        Ok(map)
+    }
+
+    // Read the map from the header and the rows of its binary table
+    fn from_table(header: &Header<BinTable>, data: &[u8]) -> Result<Self, String> {
+        let frame = match get_str(header, "COORDSYS").as_deref() {
+            Some("G") | Some("GALACTIC") => CooSystem::GAL,
+            Some("E") | Some("ECLIPTIC") => CooSystem::ECL,
+            _ => CooSystem::ICRS,
+        };
+
+        let columns = Column::parse_all(header)?;
+        let row_num_bytes = get_int(header, "NAXIS1").unwrap_or(0) as usize;
+        let num_rows = get_int(header, "NAXIS2").unwrap_or(0) as usize;
+
+        let data = data
+            .get(..(row_num_bytes * num_rows))
+            .ok_or("The binary table is truncated")?;
+        if columns.iter().any(|col| col.end() > row_num_bytes) {
+            return Err("The columns do not fit in the rows of the table".to_string());
+        }
+
+        // Partial and multi order maps store the index of the cells in a dedicated column
+        let index_col = |name: &str| {
+            columns
+                .iter()
+                .find(|col| col.name.eq_ignore_ascii_case(name))
+                .ok_or(format!("No {name} column found"))
+        };
+        let values_col = columns
+            .iter()
+            .find(|col| {
+                !col.name.eq_ignore_ascii_case("PIXEL") && !col.name.eq_ignore_ascii_case("UNIQ")
+            })
+            .ok_or("No column of values found")?;
+
+        let bad_data = get_float(header, "BAD_DATA").unwrap_or(UNSEEN);
+        let read_value = |row: &[u8], k: usize| {
+            let value = values_col.read(row, k);
+            if (value - bad_data).abs() <= 1e-5 * bad_data.abs() {
+                f32::NAN
+            } else {
+                value as f32
+            }
+        };
+
+        let ordering = get_str(header, "ORDERING");
+        let rows = data.chunks_exact(row_num_bytes.max(1));
+        if ordering.as_deref() == Some("NUNIQ") {
+            let uniq_col = index_col("UNIQ")?;
+
+            let mut cells = rows
+                .filter_map(|row| {
+                    // The smallest valid uniq index is 4 (order 0, index 0)
+                    let uniq = uniq_col.read_int(row, 0).filter(|&uniq| uniq >= 4)?;
+                    let (depth, idx) = healpix::nested::from_uniq(uniq as u64);
+
+                    if depth <= MAX_HPX_DEPTH {
+                        Some((HEALPixCell(depth, idx), read_value(row, 0)))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            cells.sort_unstable_by_key(|(cell, _)| cell.z_29_rng().start);
+
+            let depth = cells
+                .iter()
+                .map(|(cell, _)| cell.depth())
+                .max()
+                .unwrap_or(0);
+            let (ranges, values) = cells
+                .into_iter()
+                .map(|(cell, value)| (cell.z_29_rng(), value))
+                .unzip();
+
+            return Ok(Self {
+                depth,
+                frame,
+                values: Values::MultiOrder {
+                    ranges,
+                    values,
+                    fits: vec![],
+                },
+            });
+        }
+
+        let nside = get_int(header, "NSIDE").ok_or("NSIDE keyword not found")?;
+        if nside <= 0 || (nside & (nside - 1)) != 0 || nside > (1 << 29) {
+            return Err(format!("NSIDE = {nside} is not a valid power of two"));
+        }
+        let depth = crate::math::utils::log_2_unchecked(nside) as u8;
+        let num_pixels = 12 * (nside as usize) * (nside as usize);
+
+        let nested = match ordering.as_deref() {
+            Some("NESTED") | Some("NEST") => true,
+            Some("RING") => false,
+            _ => return Err("ORDERING keyword must be either RING, NESTED or NUNIQ".to_string()),
+        };
+
+        let mut values = vec![f32::NAN; num_pixels];
+        let mut set_value = |ipix: u64, value: f32| {
+            if (ipix as usize) < num_pixels {
+                let ipix = if nested {
+                    ipix
+                } else {
+                    healpix::nested::from_ring(depth, ipix)
+                };
+                values[ipix as usize] = value;
+            }
+        };
+
+        if get_str(header, "INDXSCHM").as_deref() == Some("EXPLICIT") {
+            let pixel_col = index_col("PIXEL")?;
+
+            for row in rows {
+                for k in 0..pixel_col.repeat.min(values_col.repeat) {
+                    if let Some(ipix) = pixel_col.read_int(row, k).filter(|&ipix| ipix >= 0) {
+                        set_value(ipix as u64, read_value(row, k));
+                    }
+                }
+            }
+        } else {
+            let first_pixel = get_int(header, "FIRSTPIX").unwrap_or(0).max(0) as u64;
+
+            let mut ipix = first_pixel;
+            for row in rows {
+                for k in 0..values_col.repeat {
+                    set_value(ipix, read_value(row, k));
+                    ipix += 1;
+                }
+            }
+        }
+
+        Ok(Self {
+            depth,
+            frame,
+            values: Values::Fixed(values),
+        })
+    }
+
+    #[inline]
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    #[inline]
+    pub fn frame(&self) -> CooSystem {
+        self.frame
+    }
+
+    /// Cut values computed from the 1% and 99% percentiles of the map
+    pub fn cuts(&self) -> Range<f32> {
+        let values = match &self.values {
+            Values::Fixed(values) | Values::MultiOrder { values, .. } => values,
+        };
+
+        let step = (values.len() / MAX_NUM_SAMPLES_FOR_CUTS).max(1);
+        let mut samples = values
+            .iter()
+            .step_by(step)
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+
+        if samples.is_empty() {
+            0.0..1.0
+        } else {
+            compute_automatic_cuts(&mut samples, 1, 99)
+        }
+    }
+
+    /// Generate the pixels of the tile covering a cell
+    ///
+    /// The pixels are stored as in the FITS tiles of a HiPS. The map is
+    /// averaged when the tile pixels are larger than the map ones.
+    ///
+    /// # Arguments
+    ///
+    /// * ``cell`` - The HEALPix cell of the tile
+    /// * ``tile_size`` - The width of the tile in pixels, a power of two
+    pub fn tile(&self, cell: &HEALPixCell, tile_size: i32) -> Vec<f32> {
+        let size = tile_size as usize;
+        let tile_depth = crate::math::utils::log_2_unchecked(size) as u8;
+        let pixel_depth = cell.depth() + tile_depth;
+
+        let mut data = vec![f32::NAN; size * size];
+        for z in 0..((size * size) as u64) {
+            let ipix = (cell.idx() << (2 * tile_depth)) | z;
+
+            let value = match &self.values {
+                Values::Fixed(values) if pixel_depth <= self.depth => {
+                    let shift = 2 * (self.depth - pixel_depth);
+                    mean(&values[((ipix << shift) as usize)..(((ipix + 1) << shift) as usize)])
+                }
+                Values::Fixed(values) => {
+                    values[(ipix >> (2 * (pixel_depth - self.depth))) as usize]
+                }
+                Values::MultiOrder { ranges, values, .. } => {
+                    let rng = HEALPixCell(pixel_depth, ipix).z_29_rng();
+                    weighted_mean(ranges, values, &rng)
+                }
+            };
+
+            // The rows of the FITS tiles are stored bottom to top
+            let (x, y) = crate::utils::unmortonize(z);
+            data[(size - 1 - x as usize) * size + y as usize] = value;
+        }
+
+        data
+    }
+
+    /// Compute the credible region of a probability map, i.e. the smallest region
+    /// containing a fraction `level` of the total probability
+    ///
+    /// The region is made of the cells of highest probability density.
+    pub fn credible_region(&self, level: f64) -> Result<HEALPixCoverage, String> {
+        match &self.values {
+            Values::Fixed(values) => {
+                let is_valid = |density: &f32| density.is_finite() && *density > 0.0;
+
+                // All the cells have the same area
+                let mut densities = values.iter().copied().filter(is_valid).collect::<Vec<_>>();
+                densities.sort_unstable_by(|d1, d2| {
+                    d2.partial_cmp(d1).unwrap_or(std::cmp::Ordering::Equal)
+                });
+
+                let total = densities.iter().map(|&d| d as f64).sum::<f64>();
+                let threshold =
+                    density_threshold(densities.into_iter().map(|d| (d, d as f64)), level * total);
+
+                let region = values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, density)| is_valid(*density) && **density >= threshold)
+                    .map(|(idx, _)| HEALPixCell(self.depth, idx as u64))
+                    .collect::<Vec<_>>();
+
+                Ok(HEALPixCoverage::from_hpx_cells(
+                    self.depth,
+                    region.iter(),
+                    None,
+                ))
+            }
+            Values::MultiOrder { fits, .. } => from_fits_multiordermap(
+                Cursor::new(&fits[..]),
+                0.0,
+                level,
+                // The cells of highest density first
+                false,
+                // Only the cells whose cumulated probability is below the level
+                true,
+                // The cells are kept whole
+                true,
+                false,
+            )
+            .map(HEALPixCoverage)
+            .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+// Density of the cell from which the cumulated probability reaches `target`,
+// the cells being sorted by decreasing density
+fn density_threshold(cells: impl Iterator<Item = (f32, f64)>, target: f64) -> f32 {
+    let mut cumul = 0.0;
+    let mut threshold = f32::INFINITY;
+    for (density, probability) in cells {
+        if cumul >= target {
+            break;
+        }
+
+        cumul += probability;
+        threshold = density;
+    }
+
+    threshold
+}
+
+/// Whether a FITS file stores a HEALPix map, i.e. its primary HDU has no data
+/// and its first extension is a HEALPix binary table
+///
+/// The bytes read from `reader` are appended to `bytes` so that the file can be parsed again.
+pub async fn is_healpix_map<R>(reader: R, bytes: &mut Vec<u8>) -> bool
+where
+    R: AsyncRead + Unpin + Debug,
+{
+    let mut reader = BufReader::new(RecordReader { reader, bytes });
+    let hdu = match AsyncFits::from_reader(&mut reader).await {
+        Ok(AsyncFits { hdu }) => hdu,
+        Err(_) => return false,
+    };
+
+    if hdu.0.get_header().get_xtension().get_naxis() != 0 {
+        return false;
+    }
+
+    match hdu.next().await {
+        Ok(Some(AsyncXtensionHDU::BinTable(table))) => is_healpix_table(table.get_header()),
+        _ => false,
+    }
+}
+
+fn is_healpix_table(header: &Header<BinTable>) -> bool {
+    get_str(header, "PIXTYPE").as_deref() == Some("HEALPIX")
+}
+
+fn get_str(header: &Header<BinTable>, name: &str) -> Option<String> {
+    header
+        .get_parsed::<String>(&key(name))
+        .and_then(|v| v.ok())
+        .map(|v| v.trim().to_uppercase())
+}
+
+fn get_int(header: &Header<BinTable>, name: &str) -> Option<i64> {
+    match header.get(&key(name)) {
+        Some(Value::Integer(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn get_float(header: &Header<BinTable>, name: &str) -> Option<f64> {
+    match header.get(&key(name)) {
+        Some(Value::Float(v)) => Some(*v),
+        Some(Value::Integer(v)) => Some(*v as f64),
+        _ => None,
+    }
+}
+
+// A reader keeping a copy of the bytes read
+#[derive(Debug)]
+struct RecordReader<'a, R> {
+    reader: R,
+    bytes: &'a mut Vec<u8>,
+}
+
+impl<R> AsyncRead for RecordReader<'_, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(num_bytes)) = poll {
+            this.bytes.extend_from_slice(&buf[..num_bytes]);
+        }
+
+        poll
+    }
+}
+
+fn mean(values: &[f32]) -> f32 {
+    let (sum, n) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((0.0_f64, 0_usize), |(sum, n), v| (sum + *v as f64, n + 1));
+
+    if n == 0 {
+        f32::NAN
+    } else {
+        (sum / n as f64) as f32
+    }
+}
+
+// Mean of the values of a multi order map over a range of order 29 indices,
+// weighted by the overlap of the cells with the range
+fn weighted_mean(ranges: &[Range<u64>], values: &[f32], rng: &Range<u64>) -> f32 {
+    let first = ranges.partition_point(|r| r.end <= rng.start);
+
+    let (sum, weight) = ranges[first..]
+        .iter()
+        .zip(values[first..].iter())
+        .take_while(|(r, _)| r.start < rng.end)
+        .filter(|(_, v)| v.is_finite())
+        .fold((0.0_f64, 0_u64), |(sum, weight), (r, v)| {
+            let overlap = r.end.min(rng.end) - r.start.max(rng.start);
+            (sum + (*v as f64) * (overlap as f64), weight + overlap)
+        });
+
+    if weight == 0 {
+        f32::NAN
+    } else {
+        (sum / weight as f64) as f32
+    }
+}
+
+/// A column of a binary table
+struct Column {
+    name: String,
+    // Byte offset of the column in a row
+    offset: usize,
+    // Number of values in a row
+    repeat: usize,
+    num_bytes: usize,
+    // Data type as given by the TFORM keyword
+    ty: char,
+    scale: f64,
+    zero: f64,
+    null: Option<i64>,
+}
+
+impl Column {
+    fn parse_all(header: &Header<BinTable>) -> Result<Vec<Self>, String> {
+        let num_fields = get_int(header, "TFIELDS").unwrap_or(0);
+
+        let mut offset = 0;
+        let mut columns = vec![];
+        for n in 1..=num_fields {
+            let tform = get_str(header, &format!("TFORM{n}"))
+                .ok_or(format!("TFORM{n} keyword not found"))?;
+            let digits = tform.chars().take_while(|c| c.is_ascii_digit()).count();
+            let repeat = if digits == 0 {
+                1
+            } else {
+                tform[..digits]
+                    .parse::<usize>()
+                    .map_err(|e| e.to_string())?
+            };
+            let ty = tform[digits..]
+                .chars()
+                .next()
+                .ok_or(format!("TFORM{n} has no data type"))?;
+
+            let num_bytes = match ty {
+                'L' | 'B' | 'A' => repeat,
+                'X' => (repeat + 7) / 8,
+                'I' => 2 * repeat,
+                'J' | 'E' => 4 * repeat,
+                'K' | 'D' | 'C' | 'P' => 8 * repeat,
+                'M' | 'Q' => 16 * repeat,
+                _ => return Err(format!("TFORM{n} = {tform} is not a valid data type")),
+            };
+
+            columns.push(Column {
+                name: get_str(header, &format!("TTYPE{n}")).unwrap_or_default(),
+                offset,
+                repeat,
+                num_bytes,
+                ty,
+                scale: get_float(header, &format!("TSCAL{n}")).unwrap_or(1.0),
+                zero: get_float(header, &format!("TZERO{n}")).unwrap_or(0.0),
+                null: get_int(header, &format!("TNULL{n}")),
+            });
+
+            offset += num_bytes;
+        }
+
+        Ok(columns)
+    }
+
+    /// Byte offset of the end of the column in a row
+    fn end(&self) -> usize {
+        self.offset + self.num_bytes
+    }
+
+    /// Read the k-th raw value of an integer column in a row, None if it is null
+    /// or if the column does not store integers
+    fn read_int(&self, row: &[u8], k: usize) -> Option<i64> {
+        let b = &row[self.offset..];
+        let int = match self.ty {
+            'B' => b[k] as i64,
+            'I' => i16::from_be_bytes(be(&b[2 * k..])) as i64,
+            'J' => i32::from_be_bytes(be(&b[4 * k..])) as i64,
+            'K' => i64::from_be_bytes(be(&b[8 * k..])),
+            _ => return None,
+        };
+
+        if Some(int) == self.null {
+            None
+        } else {
+            Some(int)
+        }
+    }
+
+    /// Read the k-th value of the column in a row, NaN if it is null or not numeric
+    fn read(&self, row: &[u8], k: usize) -> f64 {
+        let b = &row[self.offset..];
+        match self.ty {
+            'E' => f32::from_be_bytes(be(&b[4 * k..])) as f64 * self.scale + self.zero,
+            'D' => f64::from_be_bytes(be(&b[8 * k..])) * self.scale + self.zero,
+            _ => self
+                .read_int(row, k)
+                .map_or(f64::NAN, |int| int as f64 * self.scale + self.zero),
+        }
+    }
+}
+
+// Big endian bytes of a value
+fn be<const N: usize>(b: &[u8]) -> [u8; N] {
+    let mut a = [0; N];
+    a.copy_from_slice(&b[..N]);
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HEALPixMap, Values, UNSEEN};
+    use crate::healpix::cell::HEALPixCell;
+
+    const BLOCK_SIZE: usize = 2880;
+
+    fn header(cards: &[&str]) -> Vec<u8> {
+        let mut bytes = cards
+            .iter()
+            .chain(std::iter::once(&"END"))
+            .flat_map(|card| format!("{card:<80}").into_bytes())
+            .collect::<Vec<_>>();
+        bytes.resize(
+            ((bytes.len() + BLOCK_SIZE - 1) / BLOCK_SIZE) * BLOCK_SIZE,
+            b' ',
+        );
+        bytes
+    }
+
+    fn fits(cards: &[&str], mut data: Vec<u8>) -> Vec<u8> {
+        let mut bytes = header(&[
+            "SIMPLE  =                    T",
+            "BITPIX  =                    8",
+            "NAXIS   =                    0",
+        ]);
+        bytes.extend(header(cards));
+
+        data.resize(((data.len() / BLOCK_SIZE) + 1) * BLOCK_SIZE, 0);
+        bytes.extend(data);
+
+        bytes
+    }
+
+    // A nside = 1 map of 12 float values stored in 3 rows of 4 values
+    fn fixed_fits(ordering: &str, values: &[f32]) -> Vec<u8> {
+        fits(
+            &[
+                "XTENSION= 'BINTABLE'",
+                "BITPIX  =                    8",
+                "NAXIS   =                    2",
+                "NAXIS1  =                   16",
+                "NAXIS2  =                    3",
+                "PCOUNT  =                    0",
+                "GCOUNT  =                    1",
+                "TFIELDS =                    1",
+                "TTYPE1  = 'TEMPERATURE'",
+                "TFORM1  = '4E      '",
+                "PIXTYPE = 'HEALPIX '",
+                &format!("ORDERING= '{ordering}' / Pixel ordering scheme"),
+                "NSIDE   =                    1",
+                "COORDSYS= 'G       '",
+            ],
+            values.iter().flat_map(|v| v.to_be_bytes()).collect(),
+        )
+    }
+
+    // A multi order map given by its (uniq, probability density) cells
+    fn multi_order_fits(cells: &[(i64, f64)]) -> Vec<u8> {
+        fits(
+            &[
+                "XTENSION= 'BINTABLE'",
+                "BITPIX  =                    8",
+                "NAXIS   =                    2",
+                "NAXIS1  =                   16",
+                &format!("NAXIS2  = {:>20}", cells.len()),
+                "PCOUNT  =                    0",
+                "GCOUNT  =                    1",
+                "TFIELDS =                    2",
+                "TTYPE1  = 'UNIQ    '",
+                "TFORM1  = 'K       '",
+                "TTYPE2  = 'PROBDENSITY'",
+                "TFORM2  = 'D       '",
+                "PIXTYPE = 'HEALPIX '",
+                "ORDERING= 'NUNIQ   '",
+                "COORDSYS= 'C       '",
+                "MOCORDER=                    1",
+            ],
+            cells
+                .iter()
+                .flat_map(|(uniq, density)| {
+                    IntoIterator::into_iter(uniq.to_be_bytes())
+                        .chain(IntoIterator::into_iter(density.to_be_bytes()))
+                })
+                .collect(),
+        )
+    }
+
+    fn fixed_values(map: &HEALPixMap) -> &[f32] {
+        match &map.values {
+            Values::Fixed(values) => values,
+            _ => panic!("not a single resolution map"),
+        }
+    }
+
+    #[test]
+    fn nested_map() {
+        let mut values = (0..12).map(|v| v as f32).collect::<Vec<_>>();
+        values[5] = UNSEEN as f32;
+
+        let map = HEALPixMap::from_fits(fixed_fits("NESTED", &values)).unwrap();
+        assert_eq!(map.depth(), 0);
+        assert_eq!(map.frame(), al_api::coo_system::CooSystem::GAL);
+        assert!(fixed_values(&map)[5].is_nan());
+        assert_eq!(fixed_values(&map)[7], 7.0);
+    }
+
+    #[test]
+    fn ring_map() {
+        let values = (0..12).map(|v| v as f32).collect::<Vec<_>>();
+
+        let map = HEALPixMap::from_fits(fixed_fits("RING", &values)).unwrap();
+        for ring in 0..12 {
+            let nested = healpix::nested::from_ring(0, ring);
+            assert_eq!(fixed_values(&map)[nested as usize], ring as f32);
+        }
+    }
+
+    #[test]
+    fn tile() {
+        let values = (0..12).map(|v| v as f32).collect::<Vec<_>>();
+        let map = HEALPixMap::from_fits(fixed_fits("NESTED", &values)).unwrap();
+
+        // A tile deeper than the map is filled with the value of its ancestor
+        let data = map.tile(&HEALPixCell(0, 4), 2);
+        assert_eq!(data, vec![4.0; 4]);
+    }
+
+    #[test]
+    fn not_a_map() {
+        let bytes = fits(
+            &["XTENSION= 'BINTABLE'", "TFIELDS =                    0"],
+            vec![],
+        );
+
+        assert!(HEALPixMap::from_fits(bytes).is_err());
+    }
+
+    #[test]
+    fn multi_order_map() {
+        // The base cell 0 is split into its 4 children, the others are of order 0
+        let mut cells = (1..12).map(|idx| (4 + idx, 1.0)).collect::<Vec<_>>();
+        cells.extend((0..4).map(|idx| (16 + idx, 2.0 * idx as f64)));
+
+        let map = HEALPixMap::from_fits(multi_order_fits(&cells)).unwrap();
+        assert_eq!(map.depth(), 1);
+        assert_eq!(map.frame(), al_api::coo_system::CooSystem::ICRS);
+
+        // The tile of the base cell 0 averages its children densities
+        let data = map.tile(&HEALPixCell(0, 0), 1);
+        assert_eq!(data, vec![3.0]);
+        // Its pixels of order 1 are its children
+        let mut data = map.tile(&HEALPixCell(0, 0), 2);
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(data, vec![0.0, 2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn credible_region() {
+        // A single cell of order 1 holds half of the probability
+        let mut cells = (1..12).map(|idx| (4 + idx, 1.0)).collect::<Vec<_>>();
+        cells.extend((0..4).map(|idx| (16 + idx, if idx == 2 { 44.0 } else { 0.0 })));
+
+        let map = HEALPixMap::from_fits(multi_order_fits(&cells)).unwrap();
+
+        let region = map.credible_region(0.5).unwrap();
+        assert!((region.sky_fraction() - 1.0 / 48.0).abs() < 1e-12);
+        assert!(region.intersects_cell(&HEALPixCell(1, 2)));
+
+        let region = map.credible_region(0.9).unwrap();
+        assert!(region.intersects_cell(&HEALPixCell(1, 2)));
+        assert!(!region.intersects_cell(&HEALPixCell(1, 0)));
+        assert!(region.sky_fraction() > 1.0 / 48.0);
+    }
+}
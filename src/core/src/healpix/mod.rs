@@ -1,4 +1,7 @@
 pub mod cell;
 pub mod coverage;
 pub mod utils;
-pub mod index_vector;
\ No newline at end of file
+pub mod index_vector;
+pub mod map;
+pub mod ascii;
+pub mod space_time;
@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use crate::healpix::coverage::HEALPixCoverage;
+
+/// Max depth of the time MOCs, at which one cell lasts one microsecond
+pub const MAX_TIME_DEPTH: u8 = 61;
+const MICROSECONDS_PER_DAY: f64 = 86_400_000_000.0;
+
+/// A space-time coverage, i.e. the spatial coverages observed during given time ranges
+///
+/// It is built either from a ST-MOC or from a T-MOC, the latter being seen as observing
+/// the whole sky during its time ranges. Only its spatial projection over a time interval
+/// can be drawn.
+#[derive(Clone, Debug)]
+pub struct SpaceTimeCoverage {
+    // The depth of the spatial coverages
+    depth: u8,
+    // Sorted time ranges given in microseconds since JD 0, i.e. at the max time depth,
+    // each associated to the spatial coverage observed during them, given as
+    // sorted ranges of order 29 indices
+    elems: Vec<(Vec<Range<u64>>, Vec<Range<u64>>)>,
+}
+
+impl SpaceTimeCoverage {
+    pub fn new(depth: u8, elems: Vec<(Vec<Range<u64>>, Vec<Range<u64>>)>) -> Self {
+        Self { depth, elems }
+    }
+
+    /// A space-time coverage observing the whole sky during the time ranges of a T-MOC
+    pub fn from_time_ranges(time_ranges: Vec<Range<u64>>) -> Self {
+        let num_z29_cells = 12 << (2 * 29);
+        Self::new(0, vec![(time_ranges, vec![0..num_z29_cells])])
+    }
+
+    /// The time interval covered, in julian days
+    pub fn time_range(&self) -> Option<Range<f64>> {
+        let start = self
+            .elems
+            .iter()
+            .filter_map(|(time_ranges, _)| time_ranges.first())
+            .map(|r| r.start)
+            .min()?;
+        let end = self
+            .elems
+            .iter()
+            .filter_map(|(time_ranges, _)| time_ranges.last())
+            .map(|r| r.end)
+            .max()?;
+
+        Some(microseconds_to_jd(start)..microseconds_to_jd(end))
+    }
+
+    /// The spatial coverage observed during a time interval given in julian days
+    pub fn project(&self, jd: &Range<f64>) -> HEALPixCoverage {
+        let (start, end) = (jd_to_microseconds(jd.start), jd_to_microseconds(jd.end));
+
+        let z29_ranges = self
+            .elems
+            .iter()
+            .filter(|(time_ranges, _)| time_ranges.iter().any(|r| r.start < end && start < r.end))
+            .flat_map(|(_, z29_ranges)| z29_ranges.iter().cloned())
+            .collect();
+
+        HEALPixCoverage::from_z29_ranges(self.depth, z29_ranges)
+    }
+}
+
+fn jd_to_microseconds(jd: f64) -> u64 {
+    // the cast saturates, i.e. negative and infinite julian days are clamped
+    ((jd * MICROSECONDS_PER_DAY) as u64).min(2 << MAX_TIME_DEPTH)
+}
+
+fn microseconds_to_jd(t: u64) -> f64 {
+    (t as f64) / MICROSECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jd_to_microseconds, SpaceTimeCoverage};
+
+    #[test]
+    fn project() {
+        let day = jd_to_microseconds(1.0);
+        // the first base cell observed during the day 10, the second one during the day 20
+        let stmoc = SpaceTimeCoverage::new(
+            0,
+            vec![
+                (vec![10 * day..11 * day], vec![0..(1 << 58)]),
+                (vec![20 * day..21 * day], vec![(1 << 58)..(2 << 58)]),
+            ],
+        );
+
+        assert_eq!(stmoc.time_range(), Some(10.0..21.0));
+        assert!((stmoc.project(&(0.0..f64::INFINITY)).sky_fraction() - 2.0 / 12.0).abs() < 1e-9);
+        assert!((stmoc.project(&(10.5..15.0)).sky_fraction() - 1.0 / 12.0).abs() < 1e-9);
+        assert_eq!(stmoc.project(&(12.0..19.0)).sky_fraction(), 0.0);
+    }
+
+    #[test]
+    fn time_moc() {
+        let tmoc = SpaceTimeCoverage::from_time_ranges(vec![0..jd_to_microseconds(1.0)]);
+
+        assert_eq!(tmoc.project(&(0.5..2.0)).sky_fraction(), 1.0);
+        assert_eq!(tmoc.project(&(1.0..2.0)).sky_fraction(), 0.0);
+    }
+}
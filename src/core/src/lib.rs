@@ -82,16 +82,18 @@ use web_sys::HtmlElement;
 
 use crate::math::angle::ToAngle;
 
+mod altaz;
 mod app;
 pub mod async_task;
 mod camera;
 
 mod coosys;
 mod downloader;
-mod fifo_cache;
+mod epoch;
 mod grid;
 mod healpix;
 mod inertia;
+mod lru_cache;
 pub mod math;
 pub mod renderable;
 mod shader;
@@ -99,7 +101,11 @@ mod survey;
 mod tile_fetcher;
 mod time;
 
-use crate::downloader::request::moc::from_fits_hpx;
+use crate::altaz::Observer;
+use crate::downloader::request::moc::{from_fits_hpx, from_fits_space_time, from_fits_time};
+use crate::epoch::{Epoch, SpaceMotion};
+use crate::healpix::{ascii::ASCIIMOC, space_time::SpaceTimeCoverage};
+use crate::math::lonlat::LonLatEpochT;
 use crate::{
     camera::CameraViewPort, healpix::coverage::HEALPixCoverage, math::lonlat::LonLatT,
     shader::ShaderManager, time::DeltaTime,
@@ -114,6 +120,7 @@ use al_api::color::{Color, ColorRGBA};
 use al_api::coo_system::CooSystem;
 use al_api::hips::FITSCfg;
 use al_api::hips::HiPSProperties;
+use al_api::projection::ProjectionParams;
 
 use al_core::colormap::Colormaps;
 use al_core::Colormap;
@@ -125,10 +132,30 @@ use cgmath::{Vector2, Vector4};
 use crate::healpix::cell::HEALPixCell;
 use math::angle::ArcDeg;
 use moclib::{
-    moc::{CellMOCIntoIterator, CellMOCIterator, RangeMOCIterator},
-    qty::Hpx,
+    moc::{
+        CellMOCIntoIterator, CellMOCIterator, CellOrCellRangeMOCIterator, RangeMOCIntoIterator,
+        RangeMOCIterator,
+    },
+    qty::{Hpx, MocQty},
 };
 
+// Tell which of the interleaved 2D positions are defined
+fn fill_projection_mask(mask: &js_sys::Uint8Array, pos: &[f64]) -> Result<(), JsValue> {
+    if (mask.length() as usize) * 2 != pos.len() {
+        return Err(JsValue::from_str(
+            "The mask must have one element per position",
+        ));
+    }
+
+    let values = pos
+        .chunks(2)
+        .map(|p| (!p[0].is_nan()) as u8)
+        .collect::<Vec<_>>();
+    mask.copy_from(&values);
+
+    Ok(())
+}
+
 #[wasm_bindgen]
 pub struct WebClient {
     // The app
@@ -184,6 +211,15 @@ impl WebClient {
         self.app.set_callback_position_changed(callback);
     }*/
 
+    /// Set the function called when the tiles of a HiPS are fetched from another mirror
+    ///
+    /// It is called with the creator did of the HiPS and the url of its new mirror.
+    /// The mirrors are changed after repeated failures of the tile requests.
+    #[wasm_bindgen(js_name = setCallbackHiPSMirrorChanged)]
+    pub fn set_callback_hips_mirror_changed(&mut self, callback: js_sys::Function) {
+        self.app.set_callback_mirror_changed(callback);
+    }
+
     #[wasm_bindgen(js_name = isInerting)]
     pub fn is_inerting(&self) -> bool {
         return self.app.is_inerting();
@@ -226,80 +262,20 @@ impl WebClient {
     ///
     /// # Arguments
     ///
-    /// * `name` - Can be aitoff, mollweide, arc, sinus, tan or mercator
+    /// * `name` - The FITS/WCS projection code among TAN, STG, SIN, ZEA, FEYE, AIR, AZP, ARC,
+    ///   NCP, ZPN, MER, CAR, CEA, CYP, AIT, PAR, SFL, MOL, COD and HPX
+    /// * `params` - The projection parameters (PV2_m FITS keywords, angles in degrees).
+    ///   Undefined parameters take the default values of the WCS standard
     #[wasm_bindgen(js_name = setProjection)]
-    pub fn set_projection(&mut self, projection: &str) -> Result<(), JsValue> {
-        match projection {
-            // Zenithal
-            "TAN" => self
-                .app
-                .set_projection(ProjectionType::Tan(mapproj::zenithal::tan::Tan::new())), /* Gnomonic projection      */
-            "STG" => self
-                .app
-                .set_projection(ProjectionType::Stg(mapproj::zenithal::stg::Stg::new())), /* Stereographic projection */
-            "SIN" => self
-                .app
-                .set_projection(ProjectionType::Sin(mapproj::zenithal::sin::Sin::new())), /* Orthographic		         */
-            "ZEA" => self
-                .app
-                .set_projection(ProjectionType::Zea(mapproj::zenithal::zea::Zea::new())), /* Equal-area 		         */
-            /*"FEYE" => self
-                .app
-                .set_projection(ProjectionType::Feye(mapproj::zenithal::feye::Feye::new())),
-            "AIR" => {
-                let air_proj = mapproj::zenithal::air::Air::new();
-                //air_proj.set_n_iter(10);
-                //air_proj.set_eps(1e-12);
-                self.app.set_projection(ProjectionType::Air(air_proj))
-            }*/
-            //"AZP",
-            /*"ARC" => self
-                .app
-                .set_projection(ProjectionType::Arc(mapproj::zenithal::arc::Arc::new())),
-            "NCP" => self
-                .app
-                .set_projection(ProjectionType::Ncp(mapproj::zenithal::ncp::Ncp::new())),*/
-            // Cylindrical
-            "MER" => self
-                .app
-                .set_projection(ProjectionType::Mer(mapproj::cylindrical::mer::Mer::new())),
-            /*"CAR" => self
-                .app
-                .set_projection(ProjectionType::Car(mapproj::cylindrical::car::Car::new())),
-            "CEA" => self
-                .app
-                .set_projection(ProjectionType::Cea(mapproj::cylindrical::cea::Cea::new())),
-            "CYP" => self
-                .app
-                .set_projection(ProjectionType::Cyp(mapproj::cylindrical::cyp::Cyp::new())),*/
-            // Pseudo-cylindrical
-            "AIT" => self
-                .app
-                .set_projection(ProjectionType::Ait(mapproj::pseudocyl::ait::Ait::new())),
-            /*"PAR" => self
-                .app
-                .set_projection(ProjectionType::Par(mapproj::pseudocyl::par::Par::new())),
-            "SFL" => self
-                .app
-                .set_projection(ProjectionType::Sfl(mapproj::pseudocyl::sfl::Sfl::new())),*/
-            "MOL" => {
-                let mut mol_proj = mapproj::pseudocyl::mol::Mol::new();
-                mol_proj.set_n_iter(10);
-                mol_proj.set_epsilon(1e-12);
-
-                self.app.set_projection(ProjectionType::Mol(mol_proj))
-            } // Conic
-            /*"COD" => self
-                .app
-                .set_projection(ProjectionType::Cod(mapproj::conic::cod::Cod::new())),
-            // Hybrid
-            "HPX" => self
-                .app
-                .set_projection(ProjectionType::Hpx(mapproj::hybrid::hpx::Hpx::new())),*/
-            _ => Err(JsValue::from_str(
-                "Not a valid projection name. AIT, ZEA, SIN, STG, TAN, MOL and MER are accepted",
-            )),
-        }
+    pub fn set_projection(&mut self, projection: &str, params: JsValue) -> Result<(), JsValue> {
+        let params: ProjectionParams = if params.is_undefined() || params.is_null() {
+            ProjectionParams::default()
+        } else {
+            serde_wasm_bindgen::from_value(params)?
+        };
+        let projection = ProjectionType::from_name(projection, &params)?;
+
+        self.app.set_projection(projection)
     }
 
     /*
@@ -375,6 +351,29 @@ impl WebClient {
         Ok(())
     }
 
+    /// Add a HiPS given by its url
+    ///
+    /// Contrary to `addImageHiPS`, the `properties` file of the HiPS is fetched and parsed by the core
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer of the HiPS
+    /// * `url` - The base url of the HiPS, or the url of its `properties` file
+    /// * `meta` - Its display parameters. `imgFormat` is optional, a format provided
+    ///   by the HiPS being chosen if not given: jpeg, then png, then fits
+    ///
+    /// Returns a promise resolving to the key/value pairs of the `properties` file,
+    /// or rejected if it cannot be fetched, is malformed or describes a HiPS that cannot be displayed
+    #[wasm_bindgen(js_name = addImageHiPSFromURL)]
+    pub fn add_image_hips_from_url(
+        &mut self,
+        layer: String,
+        url: String,
+        meta: JsValue,
+    ) -> Result<js_sys::Promise, JsValue> {
+        self.app.add_image_hips_from_url(layer, url, meta)
+    }
+
     #[wasm_bindgen(js_name = addImageFITS)]
     pub fn add_image_fits(&mut self, fits_cfg: JsValue) -> Result<js_sys::Promise, JsValue> {
         let fits_cfg: FITSCfg = serde_wasm_bindgen::from_value(fits_cfg)?;
@@ -424,6 +423,101 @@ impl WebClient {
         self.app.set_image_survey_color_cfg(layer, meta)
     }
 
+    /// Display a slice of a HiPS cube
+    ///
+    /// The tiles of the slices displayed last are kept in the GPU so that going
+    /// back to one of them does not download them again. Each of the 3 slices kept
+    /// has its own texture array, so that a HiPS cube can use up to 4 times the
+    /// GPU memory of a plain HiPS.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The name of the HiPS cube layer
+    /// * `index` - The index of the slice, starting from 0
+    #[wasm_bindgen(js_name = setHiPSCubeFrame)]
+    pub fn set_hips_cube_frame(&mut self, layer: &str, index: u32) -> Result<(), JsValue> {
+        self.app.set_hips_cube_frame(layer, index)
+    }
+
+    /// Get the slices of a HiPS cube
+    ///
+    /// Its number of slices, the one displayed and, if given by its properties,
+    /// the world coordinate of each slice along the third axis with its unit are returned.
+    /// Null is returned for a HiPS which is not a cube.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The name of the HiPS layer
+    #[wasm_bindgen(js_name = getHiPSCube)]
+    pub fn get_hips_cube(&self, layer: &str) -> Result<JsValue, JsValue> {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct HiPSCube<'a> {
+            depth: u32,
+            frame: u32,
+            frame_coos: Option<Vec<f64>>,
+            unit: Option<&'a str>,
+        }
+
+        let cfg = self.app.get_hips_config(layer)?;
+        if let Some(depth) = cfg.get_cube_depth() {
+            let axis = cfg.get_cube_axis();
+            let cube = HiPSCube {
+                depth,
+                frame: cfg.get_cube_frame(),
+                frame_coos: axis.map(|axis| (0..depth).map(|f| axis.get_frame_coo(f)).collect()),
+                unit: axis.and_then(|axis| axis.get_unit()),
+            };
+
+            Ok(serde_wasm_bindgen::to_value(&cube)?)
+        } else {
+            Ok(JsValue::NULL)
+        }
+    }
+
+    /// Set the memory budget of the cache keeping the downloaded tiles
+    /// that could not be displayed yet
+    ///
+    /// The least recently used resources are evicted once the budget is exceeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - The budget in bytes
+    #[wasm_bindgen(js_name = setResourceCacheBudget)]
+    pub fn set_resource_cache_budget(&mut self, budget: usize) {
+        self.app.set_resource_cache_budget(budget);
+    }
+
+    /// Get the memory budget and use of the cache of downloaded resources
+    /// with its number of hits and misses
+    #[wasm_bindgen(js_name = getResourceCacheStats)]
+    pub fn get_resource_cache_stats(&self) -> Result<JsValue, JsValue> {
+        let cache = self.app.get_resource_cache();
+        let stats = ResourceCacheStats {
+            budget: cache.budget(),
+            size: cache.size(),
+            num_entries: cache.len(),
+            hits: cache.hits(),
+            misses: cache.misses(),
+        };
+
+        Ok(serde_wasm_bindgen::to_value(&stats)?)
+    }
+
+    /// Set the number of tile requests that can be in flight to a host
+    ///
+    /// The tiles waiting for a request are fetched from the center of the view.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The number of requests, at least 1
+    /// * `host` - The host, e.g. alasky.cds.unistra.fr. If not given, the number is set
+    ///   for all the hosts that have not been given one
+    #[wasm_bindgen(js_name = setTileRequestConcurrency)]
+    pub fn set_tile_request_concurrency(&mut self, max: usize, host: Option<String>) {
+        self.app.set_max_tile_requests(max, host);
+    }
+
     #[wasm_bindgen(js_name = setImageSurveyUrl)]
     pub fn set_survey_url(&mut self, cdid: String, new_url: String) -> Result<(), JsValue> {
         self.app.set_survey_url(&cdid, new_url)
@@ -452,6 +546,50 @@ impl WebClient {
         self.app.set_grid_cfg(cfg)
     }
 
+    /// Set the options of the HEALPix grid
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - The options (color, opacity, thickness, showLabels, labelSize, enabled, frame)
+    #[wasm_bindgen(js_name = setHEALPixGridOptions)]
+    pub fn set_hpx_grid_cfg(&mut self, cfg: JsValue) -> Result<(), JsValue> {
+        let cfg = serde_wasm_bindgen::from_value(cfg)?;
+
+        self.app.set_hpx_grid_cfg(cfg)
+    }
+
+    /// Set the options of the Tissot indicatrices overlay
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - The options (color, opacity, thickness, enabled, num)
+    #[wasm_bindgen(js_name = setTissotOptions)]
+    pub fn set_tissot_cfg(&mut self, cfg: JsValue) -> Result<(), JsValue> {
+        let cfg = serde_wasm_bindgen::from_value(cfg)?;
+        self.app.set_tissot_cfg(cfg);
+
+        Ok(())
+    }
+
+    /// Get the local distortion of the projection at a screen position
+    ///
+    /// Returns the pixel scales along x and y in arcsec/px, the area scale factor,
+    /// the angles of the local north and east and the Tissot indicatrix axes.
+    /// Returns null if the position is out of the projection
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x pixel coordinate
+    /// * `y` - The y pixel coordinate
+    #[wasm_bindgen(js_name = getDistortion)]
+    pub fn get_distortion(&self, x: f64, y: f64) -> Result<JsValue, JsValue> {
+        if let Some(distortion) = self.app.get_distortion(x, y) {
+            Ok(serde_wasm_bindgen::to_value(&distortion)?)
+        } else {
+            Ok(JsValue::null())
+        }
+    }
+
     /// Set the coordinate system for the view
     ///
     /// # Arguments
@@ -464,6 +602,42 @@ impl WebClient {
         Ok(())
     }
 
+    /// Make the view adopt the WCS of a FITS image layer
+    ///
+    /// The view takes the projection, the frame, the reference point and the orientation of
+    /// the image WCS and is zoomed so that an image pixel covers a screen pixel.
+    /// The projection code, its parameters and the frame of the view are returned
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The name of the FITS image layer
+    #[wasm_bindgen(js_name = setNativeWCS)]
+    pub fn set_native_wcs(&mut self, layer: &str) -> Result<JsValue, JsValue> {
+        #[derive(serde::Serialize)]
+        struct ViewWCS<'a> {
+            projection: &'a str,
+            params: &'a ProjectionParams,
+            frame: &'static str,
+        }
+
+        let native_wcs = self.app.set_native_wcs(layer)?;
+        let frame = match native_wcs.get_coo_system() {
+            CooSystem::ICRS => "icrs",
+            CooSystem::GAL => "galactic",
+            CooSystem::ECL => "ecliptic",
+            CooSystem::SGAL => "supergalactic",
+            CooSystem::FK5J2000 => "fk5",
+            CooSystem::FK4B1950 => "fk4",
+            CooSystem::ALTAZ => "altaz",
+        };
+
+        Ok(serde_wasm_bindgen::to_value(&ViewWCS {
+            projection: native_wcs.get_projection_name(),
+            params: native_wcs.get_projection_params(),
+            frame,
+        })?)
+    }
+
     /// Get the field of the view in degrees
     #[wasm_bindgen(js_name = getFieldOfView)]
     pub fn get_fov(&self) -> Result<f64, JsValue> {
@@ -563,16 +737,20 @@ impl WebClient {
     ///
     /// This returns a javascript array of size 2.
     /// The first component is the longitude, the second one is the latitude.
-    /// The angles are given in degrees.
+    /// The angles are given in degrees. In the horizontal frame, the longitude is the azimuth.
     #[wasm_bindgen(js_name = getCenter)]
     pub fn get_center(&self) -> Result<Box<[f64]>, JsValue> {
         let center = self.app.get_center();
 
         let (lon, lat) = (center.lon(), center.lat());
 
-        let lon_deg: ArcDeg<f64> = lon.into();
+        let mut lon_deg: ArcDeg<f64> = lon.into();
         let lat_deg: ArcDeg<f64> = lat.into();
 
+        if self.app.get_coo_system() == CooSystem::ALTAZ {
+            lon_deg.0 = altaz::azimuth(lon_deg.0);
+        }
+
         Ok(Box::new([lon_deg.0, lat_deg.0]))
     }
 
@@ -606,7 +784,11 @@ impl WebClient {
     /// * `lon` - A longitude in degrees
     /// * `lat` - A latitude in degrees
     #[wasm_bindgen(js_name = viewToICRSCooSys)]
-    pub fn view_to_icrs_coosys(&self, lon: f64, lat: f64) -> Box<[f64]> {
+    pub fn view_to_icrs_coosys(&self, mut lon: f64, lat: f64) -> Box<[f64]> {
+        if self.app.get_coo_system() == CooSystem::ALTAZ {
+            lon = altaz::azimuth(lon);
+        }
+
         let lonlat = LonLatT::new(ArcDeg(lon).into(), ArcDeg(lat).into());
 
         let res = self.app.view_to_icrs_coosys(&lonlat);
@@ -633,11 +815,21 @@ impl WebClient {
         frame: Option<CooSystem>,
     ) -> Option<Box<[f64]>> {
         if let Some(frame) = frame {
+            if frame == CooSystem::ALTAZ {
+                lon = altaz::azimuth(lon);
+            }
+
             // first convert the coo to the view frame
             use crate::math::lonlat::LonLat;
             let xyz =
                 LonLatT::new(lon.to_radians().to_angle(), lat.to_radians().to_angle()).vector();
-            let lonlat = coosys::apply_coo_system(frame, CooSystem::ICRS, &xyz).lonlat();
+            let lonlat = coosys::apply_coo_system(
+                frame,
+                CooSystem::ICRS,
+                &xyz,
+                self.app.get_horizontal_frame(),
+            )
+            .lonlat();
             lon = lonlat.lon().to_degrees();
             lat = lonlat.lat().to_degrees();
         }
@@ -647,6 +839,148 @@ impl WebClient {
             .map(|v| Box::new([v.x, v.y]) as Box<[f64]>)
     }
 
+    /// Precess equatorial coordinates between two mean equinoxes
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - A longitude in degrees
+    /// * `lat` - A latitude in degrees
+    /// * `frame` - The equatorial frame of the coordinates (ICRS, FK5J2000 or FK4B1950)
+    /// * `from` - The equinox of the input coordinates given as a year (Besselian for FK4, Julian otherwise)
+    /// * `to` - The equinox of the output coordinates given as a year (Besselian for FK4, Julian otherwise)
+    #[wasm_bindgen(js_name = precessCoordinates)]
+    pub fn precess_coordinates(
+        &self,
+        lon: f64,
+        lat: f64,
+        frame: CooSystem,
+        from: f64,
+        to: f64,
+    ) -> Result<Box<[f64]>, JsValue> {
+        if !frame.is_equatorial() {
+            return Err(JsValue::from_str(
+                "Precession is only supported for equatorial frames",
+            ));
+        }
+
+        let lonlat = LonLatT::new(ArcDeg(lon).into(), ArcDeg(lat).into());
+        let lonlat = epoch::precess(
+            frame,
+            &lonlat,
+            Epoch::from_year(frame, from),
+            Epoch::from_year(frame, to),
+        );
+
+        Ok(Box::new([lonlat.lon().to_degrees(), lonlat.lat().to_degrees()]))
+    }
+
+    /// Propagate the position of a source to another epoch given its space motion
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - The longitude at `epoch` in degrees
+    /// * `lat` - The latitude at `epoch` in degrees
+    /// * `frame` - The frame in which the coordinates are given
+    /// * `epoch` - The epoch of the position as a year (Besselian for FK4, Julian otherwise)
+    /// * `pm_ra_cosdec` - The proper motion in longitude (including the cos(lat) factor) in mas/yr
+    /// * `pm_dec` - The proper motion in latitude in mas/yr
+    /// * `parallax` - The parallax in mas (0 if unknown)
+    /// * `rv` - The radial velocity in km/s (0 if unknown)
+    /// * `to` - The target epoch as a year (Besselian for FK4, Julian otherwise)
+    /// * `to_frame` - The frame of the output coordinates. If not given, `frame` is used
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = propagateCoordinates)]
+    pub fn propagate_coordinates(
+        &self,
+        lon: f64,
+        lat: f64,
+        frame: CooSystem,
+        epoch: f64,
+        pm_ra_cosdec: f64,
+        pm_dec: f64,
+        parallax: f64,
+        rv: f64,
+        to: f64,
+        to_frame: Option<CooSystem>,
+    ) -> Box<[f64]> {
+        let pos = LonLatEpochT::new(
+            LonLatT::new(ArcDeg(lon).into(), ArcDeg(lat).into()),
+            Epoch::from_year(frame, epoch),
+        );
+        let motion = SpaceMotion::new(pm_ra_cosdec, pm_dec, parallax, rv);
+
+        let pos = epoch::apply_coo_system_at_epoch(
+            frame,
+            to_frame.unwrap_or(frame),
+            &pos,
+            &motion,
+            Epoch::from_year(frame, to),
+            self.app.get_horizontal_frame(),
+        );
+
+        Box::new([pos.lonlat.lon().to_degrees(), pos.lonlat.lat().to_degrees()])
+    }
+
+    /// Set the epoch at which the catalog sources are drawn
+    ///
+    /// Sources having a proper motion are propagated to that epoch
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - A Julian year (e.g. 2024.5). If not given, sources are drawn at their catalog epoch
+    #[wasm_bindgen(js_name = setCatalogEpoch)]
+    pub fn set_catalog_epoch(&mut self, epoch: Option<f64>) -> Result<(), JsValue> {
+        self.app.set_catalog_epoch(epoch.map(Epoch::from_julian_year));
+
+        Ok(())
+    }
+
+    /// Set the location of the observer defining the horizontal (Alt-Az) frame
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - Geodetic longitude in degrees, positive towards the East
+    /// * `lat` - Geodetic latitude in degrees
+    /// * `elevation` - Elevation above the sea level in meters. It is not used as the
+    ///   atmospheric refraction is not taken into account
+    #[wasm_bindgen(js_name = setObserver)]
+    pub fn set_observer(&mut self, lon: f64, lat: f64, elevation: f64) -> Result<(), JsValue> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(JsValue::from_str("The observer latitude must be in [-90, 90]"));
+        }
+
+        self.app.set_observer(Observer::new(lon, lat, elevation));
+
+        Ok(())
+    }
+
+    /// Set the UTC time defining the horizontal (Alt-Az) frame
+    ///
+    /// # Arguments
+    ///
+    /// * `ms` - Number of milliseconds since the Unix epoch (e.g. `Date.now()`)
+    #[wasm_bindgen(js_name = setObservationTime)]
+    pub fn set_observation_time(&mut self, ms: f64) -> Result<(), JsValue> {
+        self.app.set_observation_time(Epoch::from_unix_millis(ms));
+
+        Ok(())
+    }
+
+    /// Get the UTC time defining the horizontal (Alt-Az) frame as a Julian date
+    #[wasm_bindgen(js_name = getObservationTime)]
+    pub fn get_observation_time(&self) -> f64 {
+        self.app.get_horizontal_frame().get_time().jd()
+    }
+
+    /// Get the local mean sidereal time of the observer in degrees
+    #[wasm_bindgen(js_name = getLocalSiderealTime)]
+    pub fn get_local_sidereal_time(&self) -> f64 {
+        self.app
+            .get_horizontal_frame()
+            .local_sidereal_time()
+            .to_degrees()
+    }
+
     #[wasm_bindgen(js_name = angularDist)]
     pub fn ang_dist(&self, lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
         crate::math::lonlat::ang_between_lonlat(
@@ -663,24 +997,147 @@ impl WebClient {
         Box::new([v.x, v.y]) as Box<[f64]>
     }
 
+    /// Project ICRS positions onto the screen
+    ///
+    /// Positions that cannot be projected are given as NaN
     #[wasm_bindgen(js_name = worldToScreenVec)]
     pub fn world_to_screen_vec(&self, lon: &[f64], lat: &[f64]) -> Box<[f64]> {
-        let vertices = lon
+        self.app
+            .world_to_screen_vec(lon, lat, CooSystem::ICRS)
+            .into_boxed_slice()
+    }
+
+    /// Project ICRS positions of catalog sources onto the screen at the epoch set by `setCatalogEpoch`
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - The right ascensions at `epoch` in degrees
+    /// * `lat` - The declinations at `epoch` in degrees
+    /// * `epoch` - The epoch of the positions as a Julian year (e.g. 2016.0 for Gaia DR3)
+    /// * `pm_ra_cosdec` - The proper motions in right ascension (including the cos(dec) factor) in mas/yr
+    /// * `pm_dec` - The proper motions in declination in mas/yr
+    /// * `parallax` - The parallaxes in mas. If not given, they are unknown
+    /// * `rv` - The radial velocities in km/s. If not given, they are unknown
+    ///
+    /// Unknown (NaN) motions are taken as null. Positions that cannot be projected are given as NaN
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = worldToScreenVecAtEpoch)]
+    pub fn world_to_screen_vec_at_epoch(
+        &self,
+        lon: &[f64],
+        lat: &[f64],
+        epoch: f64,
+        pm_ra_cosdec: &[f64],
+        pm_dec: &[f64],
+        parallax: Option<Box<[f64]>>,
+        rv: Option<Box<[f64]>>,
+    ) -> Box<[f64]> {
+        let from = Epoch::from_julian_year(epoch);
+        let to = match self.app.get_catalog_epoch() {
+            Some(to) if to != from => to,
+            // The sources are drawn at their catalog epoch
+            _ => return self.world_to_screen_vec(lon, lat),
+        };
+
+        let value = |values: Option<&[f64]>, idx: usize| {
+            values
+                .and_then(|values| values.get(idx))
+                .copied()
+                .filter(|v| v.is_finite())
+                .unwrap_or(0.0)
+        };
+        let (lon, lat): (Vec<_>, Vec<_>) = lon
             .iter()
             .zip(lat.iter())
-            .map(|(&lon, &lat)| {
-                let xy = self
-                    .app
-                    .world_to_screen(lon, lat)
-                    .map(|v| [v.x, v.y])
-                    .unwrap_or([0.0, 0.0]);
-
-                xy
+            .enumerate()
+            .map(|(idx, (&lon, &lat))| {
+                let pos =
+                    LonLatEpochT::new(LonLatT::new(ArcDeg(lon).into(), ArcDeg(lat).into()), from);
+                let motion = SpaceMotion::new(
+                    value(Some(pm_ra_cosdec), idx),
+                    value(Some(pm_dec), idx),
+                    value(parallax.as_deref(), idx),
+                    value(rv.as_deref(), idx),
+                );
+
+                let lonlat = epoch::propagate(&pos, &motion, to).lonlat;
+                (lonlat.lon().to_degrees(), lonlat.lat().to_degrees())
             })
-            .flatten()
-            .collect::<Vec<_>>();
+            .unzip();
 
-        vertices.into_boxed_slice()
+        self.world_to_screen_vec(&lon, &lat)
+    }
+
+    /// Batch world to screen projection
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - The longitudes in degrees
+    /// * `lat` - The latitudes in degrees
+    /// * `frame` - The frame of the positions. If not given, ICRS is assumed
+    /// * `mask` - An optional array of the size of `lon` filled with 1 for the projected positions
+    ///   and 0 for the ones lying behind the boundary of the projection
+    ///
+    /// Returns the interleaved (x, y) screen coordinates in pixels. Positions that cannot be
+    /// projected are given as NaN
+    #[wasm_bindgen(js_name = worldToScreen)]
+    pub fn world_to_screen(
+        &self,
+        lon: &[f64],
+        lat: &[f64],
+        frame: Option<CooSystem>,
+        mask: Option<js_sys::Uint8Array>,
+    ) -> Result<Box<[f64]>, JsValue> {
+        if lon.len() != lat.len() {
+            return Err(JsValue::from_str(
+                "lon and lat arrays must have the same length",
+            ));
+        }
+
+        let xy = self
+            .app
+            .world_to_screen_vec(lon, lat, frame.unwrap_or(CooSystem::ICRS));
+        if let Some(mask) = mask {
+            fill_projection_mask(&mask, &xy)?;
+        }
+
+        Ok(xy.into_boxed_slice())
+    }
+
+    /// Batch screen to world unprojection
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x screen coordinates in pixels
+    /// * `y` - The y screen coordinates in pixels
+    /// * `frame` - The frame of the returned positions. If not given, the frame of the view is used
+    /// * `mask` - An optional array of the size of `x` filled with 1 for the unprojected positions
+    ///   and 0 for the ones lying out of the projection definition domain
+    ///
+    /// Returns the interleaved (lon, lat) positions in degrees. Screen positions that cannot be
+    /// unprojected are given as NaN
+    #[wasm_bindgen(js_name = screenToWorld)]
+    pub fn screen_to_world(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        frame: Option<CooSystem>,
+        mask: Option<js_sys::Uint8Array>,
+    ) -> Result<Box<[f64]>, JsValue> {
+        if x.len() != y.len() {
+            return Err(JsValue::from_str("x and y arrays must have the same length"));
+        }
+
+        let lonlat = self.app.screen_to_world_vec(
+            x,
+            y,
+            frame.unwrap_or_else(|| self.app.get_coo_system()),
+        );
+        if let Some(mask) = mask {
+            fill_projection_mask(&mask, &lonlat)?;
+        }
+
+        Ok(lonlat.into_boxed_slice())
     }
 
     /*#[wasm_bindgen(js_name = drawCatalog)]
@@ -729,8 +1186,13 @@ impl WebClient {
                 if let Some(frame) = frame {
                     use crate::math::lonlat::LonLat;
                     let xyz = lonlat.vector();
-                    lonlat =
-                        coosys::apply_coo_system(self.app.get_coo_system(), frame, &xyz).lonlat();
+                    lonlat = coosys::apply_coo_system(
+                        self.app.get_coo_system(),
+                        frame,
+                        &xyz,
+                        self.app.get_horizontal_frame(),
+                    )
+                    .lonlat();
                 }
 
                 let lon_deg: ArcDeg<f64> = lonlat.lon().into();
@@ -1013,7 +1475,23 @@ impl WebClient {
             }
             MocIdxType::U32(MocQtyType::<u32, _>::Hpx(moc)) => Ok(from_fits_hpx(moc)),
             MocIdxType::U64(MocQtyType::<u64, _>::Hpx(moc)) => Ok(from_fits_hpx(moc)),
-            _ => Err(JsValue::from_str("MOC not supported. Must be a HPX MOC")),
+            // T-MOCs and ST-MOCs are drawn through their spatial projection
+            MocIdxType::U16(MocQtyType::<u16, _>::Time(moc)) => {
+                return self.add_time_moc(params, from_fits_time(moc))
+            }
+            MocIdxType::U32(MocQtyType::<u32, _>::Time(moc)) => {
+                return self.add_time_moc(params, from_fits_time(moc))
+            }
+            MocIdxType::U64(MocQtyType::<u64, _>::Time(moc)) => {
+                return self.add_time_moc(params, from_fits_time(moc))
+            }
+            MocIdxType::U64(MocQtyType::<u64, _>::TimeHpx(moc)) => {
+                let moc = from_fits_space_time(moc)?;
+                return self.app.add_space_time_moc(params.clone(), moc);
+            }
+            _ => Err(JsValue::from_str(
+                "MOC not supported. Must be a S-MOC, a T-MOC or a ST-MOC",
+            )),
         }?;
 
         self.app.add_moc(params.clone(), HEALPixCoverage(moc))?;
@@ -1021,6 +1499,86 @@ impl WebClient {
         Ok(())
     }
 
+    /// Add a S-MOC, a T-MOC or a ST-MOC given in the IVOA ASCII serialization,
+    /// e.g. `3/1-5 4/30` or `t61/1-3 s3/1 4/30`
+    ///
+    /// Only the spatial projection of a T-MOC or of a ST-MOC is drawn, see `setMOCTimeRange`
+    #[wasm_bindgen(js_name = addASCIIMoc)]
+    pub fn add_ascii_moc(&mut self, params: &al_api::moc::MOC, data: &str) -> Result<(), JsValue> {
+        match healpix::ascii::parse(data).map_err(|e| JsValue::from_str(&e))? {
+            ASCIIMOC::Space(moc) => self.app.add_moc(params.clone(), moc),
+            ASCIIMOC::SpaceTime(moc) => self.app.add_space_time_moc(params.clone(), moc),
+        }
+    }
+
+    /// Get the time interval covered by a T-MOC or a ST-MOC
+    ///
+    /// Returns the julian days of its start and its end, or null if it is empty
+    #[wasm_bindgen(js_name = getMOCTimeRange)]
+    pub fn get_moc_time_range(
+        &self,
+        params: &al_api::moc::MOC,
+    ) -> Result<Option<Box<[f64]>>, JsValue> {
+        let time_range = self.app.get_moc_time_range(params)?;
+
+        Ok(time_range.map(|jd| vec![jd.start, jd.end].into_boxed_slice()))
+    }
+
+    /// Draw the spatial projection of a T-MOC or a ST-MOC over a time interval
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The T-MOC or ST-MOC
+    /// * `jd_min` - The julian day of the start of the interval
+    /// * `jd_max` - The julian day of the end of the interval
+    #[wasm_bindgen(js_name = setMOCTimeRange)]
+    pub fn set_moc_time_range(
+        &mut self,
+        params: &al_api::moc::MOC,
+        jd_min: f64,
+        jd_max: f64,
+    ) -> Result<(), JsValue> {
+        if jd_min > jd_max {
+            return Err(JsValue::from_str(
+                "The start of the time interval must precede its end",
+            ));
+        }
+
+        self.app.set_moc_time_range(params, jd_min..jd_max)
+    }
+
+    /// Add the credible region of a HEALPix probability map layer as a MOC
+    ///
+    /// Returns the area of the region in square degrees
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `layer` - The layer of the probability map
+    /// * `level` - The fraction of the total probability contained in the region, e.g. 0.9
+    #[wasm_bindgen(js_name = addCredibleRegionMOC)]
+    pub fn add_credible_region_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        layer: &str,
+        level: f64,
+    ) -> Result<f64, JsValue> {
+        let moc = self.app.get_credible_region(layer, level)?;
+        let area = moc.sky_fraction() * FULL_SKY_AREA_DEG2;
+
+        self.app.add_moc(params.clone(), moc)?;
+
+        Ok(area)
+    }
+
+    /// Add the MOC of a cone
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The center of the cone in degrees
+    /// * `rad_deg` - The radius of the cone in degrees
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
     #[wasm_bindgen(js_name = addConeMOC)]
     pub fn add_cone_moc(
         &mut self,
@@ -1028,16 +1586,16 @@ impl WebClient {
         ra_deg: f64,
         dec_deg: f64,
         rad_deg: f64,
+        depth: Option<u8>,
     ) -> Result<(), JsValue> {
-        let tile_d = self.app.get_norder();
-        let pixel_d = tile_d + 9;
+        let depth = self.get_moc_depth(depth)?;
         let moc = HEALPixCoverage::from_cone(
             &LonLatT::new(
                 ra_deg.to_radians().to_angle(),
                 dec_deg.to_radians().to_angle(),
             ),
             rad_deg.to_radians(),
-            pixel_d as u8 - 1,
+            depth,
         );
 
         self.app.add_moc(params.clone(), moc)?;
@@ -1045,16 +1603,23 @@ impl WebClient {
         Ok(())
     }
 
+    /// Add the MOC of a polygon
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The vertices of the polygon in degrees
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
     #[wasm_bindgen(js_name = addPolyMOC)]
     pub fn add_poly_moc(
         &mut self,
         params: &al_api::moc::MOC,
         ra_deg: &[f64],
         dec_deg: &[f64],
+        depth: Option<u8>,
     ) -> Result<(), JsValue> {
         use cgmath::InnerSpace;
-        let tile_d = self.app.get_norder();
-        let pixel_d = tile_d + 9;
+        let depth = self.get_moc_depth(depth)?;
 
         let vertex_it = ra_deg
             .iter()
@@ -1066,7 +1631,7 @@ impl WebClient {
 
         let v_in = &Vector4::new(1.0, 0.0, 0.0, 1.0);
 
-        let mut moc = HEALPixCoverage::from_3d_coos(pixel_d as u8 - 1, vertex_it, &v_in);
+        let mut moc = HEALPixCoverage::from_3d_coos(depth, vertex_it, &v_in);
         if moc.sky_fraction() > 0.5 {
             moc = moc.not();
         }
@@ -1076,25 +1641,250 @@ impl WebClient {
         Ok(())
     }
 
-    #[wasm_bindgen(js_name = removeMoc)]
-    pub fn remove_moc(&mut self, params: &al_api::moc::MOC) -> Result<(), JsValue> {
-        self.app.remove_moc(params)?;
+    /// Add the MOC of polygonal rings, e.g. a footprint with holes or made of several parts
+    ///
+    /// The inside of a ring is on its left, i.e. a ring goes counter-clockwise around its
+    /// inside as seen on the sky, and a hole is given by a clockwise ring.
+    /// The MOC is made of the points enclosed by more rings than the outside,
+    /// or by at least as many rings as the control point if one is given.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The vertices of all the rings in degrees, one ring after the other
+    /// * `ring_lengths` - The number of vertices of each ring
+    /// * `control_ra_deg`, `control_dec_deg` - An optional point inside the MOC in degrees
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addMultiPolygonMOC)]
+    pub fn add_multi_polygon_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        ra_deg: &[f64],
+        dec_deg: &[f64],
+        ring_lengths: &[u32],
+        control_ra_deg: Option<f64>,
+        control_dec_deg: Option<f64>,
+        depth: Option<u8>,
+    ) -> Result<(), JsValue> {
+        if ra_deg.len() != dec_deg.len()
+            || ring_lengths.iter().map(|len| *len as usize).sum::<usize>() != ra_deg.len()
+        {
+            return Err(JsValue::from_str(
+                "The ring lengths must sum up to the number of vertices",
+            ));
+        }
 
-        Ok(())
-    }
+        let mut vertices = ra_deg
+            .iter()
+            .zip(dec_deg.iter())
+            .map(|(ra, dec)| (ra.to_radians(), dec.to_radians()));
+        let rings = ring_lengths
+            .iter()
+            .map(|len| vertices.by_ref().take(*len as usize).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
 
-    #[wasm_bindgen(js_name = setMocParams)]
-    pub fn set_moc_cfg(&mut self, cfg: &al_api::moc::MOC) -> Result<(), JsValue> {
-        self.app.set_moc_cfg(cfg.clone())?;
+        let control_point = control_ra_deg
+            .zip(control_dec_deg)
+            .map(|(ra, dec)| (ra.to_radians(), dec.to_radians()));
 
-        Ok(())
+        let depth = self.get_moc_depth(depth)?;
+        let moc = HEALPixCoverage::from_rings(depth, &rings, control_point)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        self.app.add_moc(params.clone(), moc)
     }
 
-    #[wasm_bindgen(js_name = mocContains)]
-    pub fn moc_contains(
+    /// Add the MOC of an elliptical cone
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The center of the ellipse in degrees
+    /// * `a_deg` - The semi-major axis in degrees
+    /// * `b_deg` - The semi-minor axis in degrees
+    /// * `pa_deg` - The position angle of the major axis in degrees, east of north
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addEllipseMOC)]
+    pub fn add_ellipse_moc(
         &mut self,
         params: &al_api::moc::MOC,
-        lon: f64,
+        ra_deg: f64,
+        dec_deg: f64,
+        a_deg: f64,
+        b_deg: f64,
+        pa_deg: f64,
+        depth: Option<u8>,
+    ) -> Result<(), JsValue> {
+        if !(b_deg > 0.0 && b_deg <= a_deg && a_deg < 90.0) {
+            return Err(JsValue::from_str(
+                "The axes of the ellipse must verify 0 < b <= a < 90 degrees",
+            ));
+        }
+
+        let depth = self.get_moc_depth(depth)?;
+        let moc = HEALPixCoverage::from_elliptical_cone(
+            &LonLatT::new(
+                ra_deg.to_radians().to_angle(),
+                dec_deg.to_radians().to_angle(),
+            ),
+            a_deg.to_radians(),
+            b_deg.to_radians(),
+            pa_deg.to_radians(),
+            depth,
+        );
+
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the MOC of a box rotated by a position angle
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The center of the box in degrees
+    /// * `a_deg` - The half width of the box along its first axis in degrees
+    /// * `b_deg` - The half width of the box along its second axis in degrees
+    /// * `pa_deg` - The position angle of the first axis in degrees, east of north
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = addBoxMOC)]
+    pub fn add_box_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        ra_deg: f64,
+        dec_deg: f64,
+        a_deg: f64,
+        b_deg: f64,
+        pa_deg: f64,
+        depth: Option<u8>,
+    ) -> Result<(), JsValue> {
+        if !(a_deg > 0.0 && a_deg <= 90.0 && b_deg > 0.0 && b_deg <= 90.0) {
+            return Err(JsValue::from_str(
+                "The half widths of the box must be in ]0, 90] degrees",
+            ));
+        }
+
+        let depth = self.get_moc_depth(depth)?;
+        let moc = HEALPixCoverage::from_box(
+            &LonLatT::new(
+                ra_deg.to_radians().to_angle(),
+                dec_deg.to_radians().to_angle(),
+            ),
+            a_deg.to_radians(),
+            b_deg.to_radians(),
+            pa_deg.to_radians(),
+            depth,
+        );
+
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the MOC of a zone delimited by two meridians and two parallels
+    ///
+    /// The zone crosses the meridian 0 if `ra_min_deg > ra_max_deg`
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_min_deg`, `ra_max_deg` - The right ascensions of the meridians in degrees
+    /// * `dec_min_deg`, `dec_max_deg` - The declinations of the parallels in degrees
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
+    #[wasm_bindgen(js_name = addZoneMOC)]
+    pub fn add_zone_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        ra_min_deg: f64,
+        dec_min_deg: f64,
+        ra_max_deg: f64,
+        dec_max_deg: f64,
+        depth: Option<u8>,
+    ) -> Result<(), JsValue> {
+        if !(-90.0 <= dec_min_deg && dec_min_deg < dec_max_deg && dec_max_deg <= 90.0) {
+            return Err(JsValue::from_str(
+                "The declinations of the zone must verify -90 <= dec_min < dec_max <= 90",
+            ));
+        }
+
+        // a zone ending at 360 degrees ends at the meridian 0
+        let ra_min = ra_min_deg.rem_euclid(360.0);
+        let ra_max = if ra_max_deg == 360.0 {
+            360.0
+        } else {
+            ra_max_deg.rem_euclid(360.0)
+        };
+
+        let depth = self.get_moc_depth(depth)?;
+        let moc = HEALPixCoverage::from_zone(
+            ra_min.to_radians(),
+            dec_min_deg.to_radians(),
+            ra_max.to_radians(),
+            dec_max_deg.to_radians(),
+            depth,
+        );
+
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the MOC of an annulus
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC display parameters
+    /// * `ra_deg`, `dec_deg` - The center of the annulus in degrees
+    /// * `r_int_deg` - The internal radius in degrees
+    /// * `r_ext_deg` - The external radius in degrees
+    /// * `depth` - The depth of the MOC. Defaults to a depth finer than the current view
+    #[wasm_bindgen(js_name = addRingMOC)]
+    pub fn add_ring_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        ra_deg: f64,
+        dec_deg: f64,
+        r_int_deg: f64,
+        r_ext_deg: f64,
+        depth: Option<u8>,
+    ) -> Result<(), JsValue> {
+        if !(0.0 <= r_int_deg && r_int_deg < r_ext_deg && r_ext_deg < 180.0) {
+            return Err(JsValue::from_str(
+                "The radii of the ring must verify 0 <= r_int < r_ext < 180 degrees",
+            ));
+        }
+
+        let depth = self.get_moc_depth(depth)?;
+        let moc = HEALPixCoverage::from_ring(
+            &LonLatT::new(
+                ra_deg.to_radians().to_angle(),
+                dec_deg.to_radians().to_angle(),
+            ),
+            r_int_deg.to_radians(),
+            r_ext_deg.to_radians(),
+            depth,
+        );
+
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    #[wasm_bindgen(js_name = removeMoc)]
+    pub fn remove_moc(&mut self, params: &al_api::moc::MOC) -> Result<(), JsValue> {
+        self.app.remove_moc(params)?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = setMocParams)]
+    pub fn set_moc_cfg(&mut self, cfg: &al_api::moc::MOC) -> Result<(), JsValue> {
+        self.app.set_moc_cfg(cfg.clone())?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = mocContains)]
+    pub fn moc_contains(
+        &mut self,
+        params: &al_api::moc::MOC,
+        lon: f64,
         lat: f64,
     ) -> Result<bool, JsValue> {
         let moc = self
@@ -1106,6 +1896,230 @@ impl WebClient {
         Ok(moc.contains_lonlat(&location))
     }
 
+    /// Get the MOCs covering a screen position
+    ///
+    /// # Arguments
+    ///
+    /// * `pos_x` - The x screen coordinate in pixels
+    /// * `pos_y` - The y screen coordinate in pixels
+    ///
+    /// Returns the uuid of the shown MOCs containing the position, from the one drawn on top,
+    /// along with the order and the index of their cell containing it and the sky fraction of that cell
+    #[wasm_bindgen(js_name = pickMOCs)]
+    pub fn pick_mocs(&self, pos_x: f64, pos_y: f64) -> Result<JsValue, JsValue> {
+        let picks = self
+            .app
+            .pick_mocs(&Vector2::new(pos_x, pos_y))
+            .into_iter()
+            .map(|(cfg, HEALPixCell(order, ipix))| MOCPick {
+                uuid: cfg.get_uuid().clone(),
+                order,
+                ipix,
+                // a base cell covers 1/12 of the sky and is divided into 4 cells at each order
+                sky_fraction: 1.0 / ((12_u64 << (2 * order)) as f64),
+            })
+            .collect::<Vec<_>>();
+
+        serde_wasm_bindgen::to_value(&picks).map_err(|e| e.into())
+    }
+
+    /// Add the union of two MOCs as a new MOC
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The display parameters of the new MOC
+    /// * `first`, `second` - The MOCs to combine
+    #[wasm_bindgen(js_name = addMOCUnion)]
+    pub fn add_moc_union(
+        &mut self,
+        params: &al_api::moc::MOC,
+        first: &al_api::moc::MOC,
+        second: &al_api::moc::MOC,
+    ) -> Result<(), JsValue> {
+        let moc = self.get_moc(first)?.union(self.get_moc(second)?);
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the intersection of two MOCs as a new MOC
+    #[wasm_bindgen(js_name = addMOCIntersection)]
+    pub fn add_moc_intersection(
+        &mut self,
+        params: &al_api::moc::MOC,
+        first: &al_api::moc::MOC,
+        second: &al_api::moc::MOC,
+    ) -> Result<(), JsValue> {
+        let moc = self.get_moc(first)?.intersection(self.get_moc(second)?);
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the part of the first MOC not covered by the second one as a new MOC
+    #[wasm_bindgen(js_name = addMOCDifference)]
+    pub fn add_moc_difference(
+        &mut self,
+        params: &al_api::moc::MOC,
+        first: &al_api::moc::MOC,
+        second: &al_api::moc::MOC,
+    ) -> Result<(), JsValue> {
+        let moc = self.get_moc(first)?.difference(self.get_moc(second)?);
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the part of the sky covered by only one of two MOCs as a new MOC
+    #[wasm_bindgen(js_name = addMOCSymmetricDifference)]
+    pub fn add_moc_symmetric_difference(
+        &mut self,
+        params: &al_api::moc::MOC,
+        first: &al_api::moc::MOC,
+        second: &al_api::moc::MOC,
+    ) -> Result<(), JsValue> {
+        let moc = self
+            .get_moc(first)?
+            .symmetric_difference(self.get_moc(second)?);
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Add the complement of a MOC as a new MOC
+    #[wasm_bindgen(js_name = addMOCComplement)]
+    pub fn add_moc_complement(
+        &mut self,
+        params: &al_api::moc::MOC,
+        moc: &al_api::moc::MOC,
+    ) -> Result<(), JsValue> {
+        let moc = self.get_moc(moc)?.not();
+        self.app.add_moc(params.clone(), moc)
+    }
+
+    /// Get the overlap between two MOCs without adding a new MOC
+    ///
+    /// Returns the sky fraction and the area in square degrees of their intersection,
+    /// as well as the fractions of each MOC it covers
+    #[wasm_bindgen(js_name = getMOCOverlap)]
+    pub fn get_moc_overlap(
+        &self,
+        first: &al_api::moc::MOC,
+        second: &al_api::moc::MOC,
+    ) -> Result<JsValue, JsValue> {
+        let (first, second) = (self.get_moc(first)?, self.get_moc(second)?);
+        let sky_fraction = first.intersection(second).sky_fraction();
+
+        let fraction_of = |moc: &HEALPixCoverage| {
+            let moc_sky_fraction = moc.sky_fraction();
+            if moc_sky_fraction > 0.0 {
+                sky_fraction / moc_sky_fraction
+            } else {
+                0.0
+            }
+        };
+
+        let overlap = MOCOverlap {
+            sky_fraction,
+            area: sky_fraction * FULL_SKY_AREA_DEG2,
+            fraction_of_first: fraction_of(first),
+            fraction_of_second: fraction_of(second),
+        };
+
+        serde_wasm_bindgen::to_value(&overlap).map_err(|e| e.into())
+    }
+
+    /// Get a summary of a MOC
+    ///
+    /// Returns its sky fraction and area in square degrees, its max order, its number of
+    /// cells per order, a cone and a (ra, dec) box containing it, in degrees, and its
+    /// connected components sorted by decreasing area
+    #[wasm_bindgen(js_name = getMOCSummary)]
+    pub fn get_moc_summary(&self, params: &al_api::moc::MOC) -> Result<JsValue, JsValue> {
+        let moc = self.get_moc(params)?;
+
+        let moc_area = |moc: &HEALPixCoverage| MOCArea {
+            sky_fraction: moc.sky_fraction(),
+            area: moc.sky_fraction() * FULL_SKY_AREA_DEG2,
+        };
+
+        let mut components = moc
+            .connected_components()
+            .iter()
+            .map(moc_area)
+            .collect::<Vec<_>>();
+        components.sort_unstable_by(|c1, c2| c2.sky_fraction.total_cmp(&c1.sky_fraction));
+
+        let MOCArea { sky_fraction, area } = moc_area(moc);
+        let summary = MOCSummary {
+            sky_fraction,
+            area,
+            max_order: moc.depth(),
+            num_cells_per_order: moc.num_cells_per_depth(),
+            bounding_cone: moc
+                .bounding_cone()
+                .map(|((lon, lat), radius)| MOCBoundingCone {
+                    ra: lon.to_degrees(),
+                    dec: lat.to_degrees(),
+                    radius: radius.to_degrees(),
+                }),
+            bounding_box: moc.bounding_box().map(|bbox| MOCBoundingBox {
+                ra_min: bbox.lon_min().to_degrees(),
+                ra_max: bbox.lon_max().to_degrees(),
+                dec_min: bbox.lat_min().to_degrees(),
+                dec_max: bbox.lat_max().to_degrees(),
+            }),
+            components,
+        };
+
+        serde_wasm_bindgen::to_value(&summary).map_err(|e| e.into())
+    }
+
+    /// Serialize a MOC into the IVOA MOC 2.0 FITS format
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC to serialize
+    /// * `depth` - The depth of the serialized MOC. Defaults to the depth of the MOC
+    #[wasm_bindgen(js_name = getMOCAsFITS)]
+    pub fn get_moc_as_fits(
+        &self,
+        params: &al_api::moc::MOC,
+        depth: Option<u8>,
+    ) -> Result<Box<[u8]>, JsValue> {
+        let moc = self.get_moc_at_depth(params, depth)?;
+        let bytes = moc.to_fits().map_err(|e| JsValue::from_str(&e))?;
+
+        Ok(bytes.into_boxed_slice())
+    }
+
+    /// Serialize a MOC into the Aladin JSON format, as accepted by `addJSONMoc`
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC to serialize
+    /// * `depth` - The depth of the serialized MOC. Defaults to the depth of the MOC
+    #[wasm_bindgen(js_name = getMOCAsJSON)]
+    pub fn get_moc_as_json(
+        &self,
+        params: &al_api::moc::MOC,
+        depth: Option<u8>,
+    ) -> Result<JsValue, JsValue> {
+        let moc = self.get_moc_at_depth(params, depth)?;
+        let str = moc.to_json().map_err(|e| JsValue::from_str(&e))?;
+
+        js_sys::JSON::parse(&str)
+    }
+
+    /// Serialize a MOC into the IVOA MOC 2.0 ASCII format
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The MOC to serialize
+    /// * `depth` - The depth of the serialized MOC. Defaults to the depth of the MOC
+    #[wasm_bindgen(js_name = getMOCAsASCII)]
+    pub fn get_moc_as_ascii(
+        &self,
+        params: &al_api::moc::MOC,
+        depth: Option<u8>,
+    ) -> Result<String, JsValue> {
+        let moc = self.get_moc_at_depth(params, depth)?;
+
+        moc.to_ascii().map_err(|e| JsValue::from_str(&e))
+    }
+
     #[wasm_bindgen(js_name = getMOCSkyFraction)]
     pub fn get_moc_sky_fraction(&mut self, params: &al_api::moc::MOC) -> f32 {
         if let Some(moc) = self.app.get_moc(params) {
@@ -1199,3 +2213,384 @@ pub fn hpx_ang2pix(nside: u32, lon: &[f64], lat: &[f64]) -> Result<Box<[u64]>, J
 
     Ok(vertices)
 }
+
+impl WebClient {
+    /// Depth of the MOCs built from shapes, by default finer than the depth of the current view
+    fn get_moc_depth(&self, depth: Option<u8>) -> Result<u8, JsValue> {
+        match depth {
+            Some(depth) if depth <= Hpx::<u64>::MAX_DEPTH => Ok(depth),
+            Some(depth) => Err(JsValue::from_str(&format!(
+                "Depth {} is greater than the maximum depth of a MOC ({})",
+                depth,
+                Hpx::<u64>::MAX_DEPTH
+            ))),
+            None => {
+                let tile_d = self.app.get_norder();
+                let pixel_d = tile_d + 9;
+                Ok((pixel_d as u8 - 1).min(Hpx::<u64>::MAX_DEPTH))
+            }
+        }
+    }
+
+    fn add_time_moc(
+        &mut self,
+        params: &al_api::moc::MOC,
+        time_ranges: Vec<std::ops::Range<u64>>,
+    ) -> Result<(), JsValue> {
+        let moc = SpaceTimeCoverage::from_time_ranges(time_ranges);
+        self.app.add_space_time_moc(params.clone(), moc)
+    }
+
+    fn get_moc(&self, params: &al_api::moc::MOC) -> Result<&HEALPixCoverage, JsValue> {
+        self.app
+            .get_moc(params)
+            .ok_or_else(|| JsValue::from(js_sys::Error::new("MOC not found")))
+    }
+
+    /// Get a MOC expressed at the given depth, or at its own depth if none is given
+    fn get_moc_at_depth(
+        &self,
+        params: &al_api::moc::MOC,
+        depth: Option<u8>,
+    ) -> Result<HEALPixCoverage, JsValue> {
+        let moc = self.get_moc(params)?;
+
+        match depth {
+            None => Ok(moc.clone()),
+            Some(depth) if depth <= Hpx::<u64>::MAX_DEPTH => Ok(moc.with_depth(depth)),
+            Some(depth) => Err(JsValue::from_str(&format!(
+                "Depth {} is greater than the maximum depth of a MOC ({})",
+                depth,
+                Hpx::<u64>::MAX_DEPTH
+            ))),
+        }
+    }
+}
+
+/// Area of the whole sky, i.e. 4 pi steradians, in square degrees
+const FULL_SKY_AREA_DEG2: f64 =
+    4.0 * std::f64::consts::PI * (180.0 / std::f64::consts::PI) * (180.0 / std::f64::consts::PI);
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MOCOverlap {
+    sky_fraction: f64,
+    area: f64,
+    fraction_of_first: f64,
+    fraction_of_second: f64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceCacheStats {
+    budget: usize,
+    size: usize,
+    num_entries: usize,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MOCPick {
+    uuid: String,
+    order: u8,
+    ipix: u64,
+    sky_fraction: f64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MOCArea {
+    sky_fraction: f64,
+    area: f64,
+}
+
+#[derive(serde::Serialize)]
+struct MOCBoundingCone {
+    ra: f64,
+    dec: f64,
+    radius: f64,
+}
+
+// The box starts at a negative ra if it crosses the meridian 0
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MOCBoundingBox {
+    ra_min: f64,
+    ra_max: f64,
+    dec_min: f64,
+    dec_max: f64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MOCSummary {
+    sky_fraction: f64,
+    area: f64,
+    max_order: u8,
+    num_cells_per_order: Vec<u64>,
+    bounding_cone: Option<MOCBoundingCone>,
+    bounding_box: Option<MOCBoundingBox>,
+    components: Vec<MOCArea>,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+struct HPXWeight {
+    pub ipix: u64,
+    pub weight: f64,
+}
+
+/// The 8 neighbours of the cells in the order S, SE, E, SW, NE, W, NW, N.
+///
+/// A missing neighbour (around the 8 cells having only 7 neighbours) is given as -1
+#[wasm_bindgen(js_name = HEALPixNeighbours)]
+pub fn hpx_neighbours(nside: u32, ipix: &[u64]) -> Result<Box<[i64]>, JsValue> {
+    use ::healpix::compass_point::MainWind;
+
+    const WINDS: [MainWind; 8] = [
+        MainWind::S,
+        MainWind::SE,
+        MainWind::E,
+        MainWind::SW,
+        MainWind::NE,
+        MainWind::W,
+        MainWind::NW,
+        MainWind::N,
+    ];
+
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let neighbours = ipix
+        .iter()
+        .flat_map(|i| {
+            let cell = HEALPixCell(depth, *i);
+            WINDS.iter().map(move |wind| {
+                cell.neighbor(*wind).map(|n| n.idx() as i64).unwrap_or(-1)
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(neighbours)
+}
+
+/// The parent cells, of nside `nside / 2`
+#[wasm_bindgen(js_name = HEALPixParent)]
+pub fn hpx_parent(nside: u32, ipix: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    if depth == 0 {
+        return Err(JsValue::from_str("Cells of nside 1 have no parent"));
+    }
+
+    let parents = ipix
+        .iter()
+        .map(|i| HEALPixCell(depth, *i).parent().idx())
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(parents)
+}
+
+/// The 4 children cells, of nside `2 * nside`
+#[wasm_bindgen(js_name = HEALPixChildren)]
+pub fn hpx_children(nside: u32, ipix: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let children = ipix
+        .iter()
+        .flat_map(|i| HEALPixCell(depth, *i).get_children_cells(1).map(|c| c.idx()))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(children)
+}
+
+#[wasm_bindgen(js_name = HEALPixNestToRing)]
+pub fn hpx_nest2ring(nside: u32, ipix: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let ring = ipix
+        .iter()
+        .map(|i| ::healpix::nested::to_ring(depth, *i))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(ring)
+}
+
+#[wasm_bindgen(js_name = HEALPixRingToNest)]
+pub fn hpx_ring2nest(nside: u32, ipix: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let nested = ipix
+        .iter()
+        .map(|i| ::healpix::nested::from_ring(depth, *i))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(nested)
+}
+
+/// Encode NESTED cells into their UNIQ index, i.e. `4 * nside^2 + ipix`
+#[wasm_bindgen(js_name = HEALPixToUniq)]
+pub fn hpx_to_uniq(nside: u32, ipix: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let uniq = ipix
+        .iter()
+        .map(|i| ::healpix::nested::to_uniq(depth, *i))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(uniq)
+}
+
+/// Decode UNIQ indices into the interleaved [nside0, ipix0, nside1, ipix1, ...] NESTED cells
+#[wasm_bindgen(js_name = HEALPixFromUniq)]
+pub fn hpx_from_uniq(uniq: &[u64]) -> Result<Box<[u64]>, JsValue> {
+    let cells = uniq
+        .iter()
+        .map(|u| {
+            // The smallest valid uniq index is 4 (nside 1, ipix 0)
+            if *u < 4 {
+                Err(JsValue::from_str(&format!("{} is not a valid uniq index", u)))
+            } else {
+                let (depth, ipix) = ::healpix::nested::from_uniq(*u);
+                Ok([1_u64 << depth, ipix])
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(cells)
+}
+
+/// Cells overlapping a cone
+///
+/// The cone center and radius are given in radians, the radius being clamped to [0, π]
+#[wasm_bindgen(js_name = HEALPixQueryCone)]
+pub fn hpx_query_cone(
+    nside: u32,
+    lon: f64,
+    lat: f64,
+    radius: f64,
+) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let radius = radius.clamp(0.0, math::PI);
+    let bmoc = ::healpix::nested::cone_coverage_approx(depth, lon, lat, radius);
+
+    Ok(bmoc.flat_iter().collect::<Vec<_>>().into_boxed_slice())
+}
+
+/// Cells overlapping an elliptical cone
+///
+/// All the angles are given in radians, the position angle being counted from the north towards the east
+#[wasm_bindgen(js_name = HEALPixQueryEllipse)]
+pub fn hpx_query_ellipse(
+    nside: u32,
+    lon: f64,
+    lat: f64,
+    a: f64,
+    b: f64,
+    pa: f64,
+) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let bmoc = ::healpix::nested::elliptical_cone_coverage(depth, lon, lat, a, b, pa);
+
+    Ok(bmoc.flat_iter().collect::<Vec<_>>().into_boxed_slice())
+}
+
+/// Cells overlapping a box of semi-major axis `a` and semi-minor axis `b`
+///
+/// All the angles are given in radians, the position angle being counted from the north towards the east
+#[wasm_bindgen(js_name = HEALPixQueryBox)]
+pub fn hpx_query_box(
+    nside: u32,
+    lon: f64,
+    lat: f64,
+    a: f64,
+    b: f64,
+    pa: f64,
+) -> Result<Box<[u64]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let bmoc = ::healpix::nested::box_coverage(depth, lon, lat, a, b, pa);
+
+    Ok(bmoc.flat_iter().collect::<Vec<_>>().into_boxed_slice())
+}
+
+/// Cells overlapping a polygon whose vertices are given in radians
+#[wasm_bindgen(js_name = HEALPixQueryPolygon)]
+pub fn hpx_query_polygon(nside: u32, lon: &[f64], lat: &[f64]) -> Result<Box<[u64]>, JsValue> {
+    if lon.len() != lat.len() {
+        return Err(JsValue::from_str(
+            "The longitude and latitude arrays must have the same length",
+        ));
+    }
+    if lon.len() < 3 {
+        return Err(JsValue::from_str("A polygon must have at least 3 vertices"));
+    }
+
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let vertices = lon
+        .iter()
+        .zip(lat.iter())
+        .map(|(&lon, &lat)| (lon, lat))
+        .collect::<Vec<_>>();
+    let bmoc = ::healpix::nested::polygon_coverage(depth, &vertices, true);
+
+    Ok(bmoc.flat_iter().collect::<Vec<_>>().into_boxed_slice())
+}
+
+/// The 4 cells and their weights for a bilinear interpolation at each position (given in radians)
+#[wasm_bindgen(js_name = HEALPixBilinearInterpolation)]
+pub fn hpx_bilinear_interpolation(
+    nside: u32,
+    lon: &[f64],
+    lat: &[f64],
+) -> Result<Box<[HPXWeight]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let weights = lon
+        .iter()
+        .zip(lat.iter())
+        .flat_map(|(&lon, &lat)| {
+            IntoIterator::into_iter(::healpix::nested::bilinear_interpolation(depth, lon, lat))
+                .map(|(ipix, weight)| HPXWeight { ipix, weight })
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(weights)
+}
+
+/// Path along the edge of a cell, starting from its south vertex
+///
+/// Returns `4 * n_segments_by_side` vertices given in radians
+#[wasm_bindgen(js_name = HEALPixPathAlongCellEdge)]
+pub fn hpx_path_along_cell_edge(
+    nside: u32,
+    ipix: u64,
+    n_segments_by_side: u32,
+) -> Result<Box<[LonLat]>, JsValue> {
+    let depth = crate::healpix::cell::nside2depth(nside);
+    let path = HEALPixCell(depth, ipix)
+        .path_along_cell_edge(n_segments_by_side.max(1))
+        .iter()
+        .map(|&(lon, lat)| LonLat { lon, lat })
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    Ok(path)
+}
+
+/// Parse a pair of coordinates given in decimal degrees or in sexagesimal
+///
+/// e.g. "05h34m31.94s +22d00m52.2s", "05:34:31.94 +22:00:52.2" or "83.633 22.014"
+///
+/// Returns the [longitude, latitude] in degrees
+#[wasm_bindgen(js_name = parseCoordinates)]
+pub fn parse_coordinates(coo: &str) -> Result<Box<[f64]>, JsValue> {
+    let (lon, lat) =
+        crate::math::angle::parse_coordinates(coo).map_err(|e| JsValue::from_str(&e))?;
+
+    Ok(Box::new([lon, lat]))
+}
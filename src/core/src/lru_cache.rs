@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Default memory budget of the downloaded resources cache, in bytes
+pub const DEFAULT_CACHE_BUDGET: usize = 256 * 1024 * 1024;
+
+struct Entry<V> {
+    val: V,
+    // Estimated size in bytes
+    size: usize,
+    // Stamp of the last access
+    stamp: u64,
+}
+
+/// A cache evicting its least recently used entries once their
+/// estimated sizes sum up over a memory budget
+pub struct Cache<K, V> {
+    data: HashMap<K, Entry<V>>,
+    // The keys from the least to the most recently used
+    order: BTreeMap<u64, K>,
+    next_stamp: u64,
+
+    // Memory budget and memory used by the entries, in bytes
+    budget: usize,
+    size: usize,
+
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Clone + Eq + Hash,
+{
+    pub fn new(budget: usize) -> Self {
+        Cache {
+            data: HashMap::new(),
+            order: BTreeMap::new(),
+            next_stamp: 0,
+            budget,
+            size: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Insert an entry which becomes the most recently used one
+    ///
+    /// The least recently used entries are evicted until the memory used fits
+    /// in the budget. An entry bigger than the whole budget is not kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the entry, replacing a former entry with the same key
+    /// * `val` - The value to cache
+    /// * `size` - The estimated size of the value in bytes
+    pub fn insert(&mut self, key: K, val: V, size: usize) {
+        self.extract(&key);
+
+        if size > self.budget {
+            return;
+        }
+
+        let stamp = self.stamp();
+        self.order.insert(stamp, key.clone());
+        self.data.insert(key, Entry { val, size, stamp });
+        self.size += size;
+
+        self.evict();
+    }
+
+    /// Access an entry which becomes the most recently used one
+    ///
+    /// The access is counted as a hit or a miss.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let stamp = self.stamp();
+
+        if let Some(entry) = self.data.get_mut(key) {
+            self.hits += 1;
+
+            self.order.remove(&entry.stamp);
+            self.order.insert(stamp, key.clone());
+            entry.stamp = stamp;
+
+            Some(&entry.val)
+        } else {
+            self.misses += 1;
+
+            None
+        }
+    }
+
+    /// Access an entry without changing its recency nor counting the access
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.data.get(key).map(|entry| &entry.val)
+    }
+
+    /// Remove an entry and give it back
+    pub fn extract(&mut self, key: &K) -> Option<V> {
+        let Entry { val, size, stamp } = self.data.remove(key)?;
+        self.order.remove(&stamp);
+        self.size -= size;
+
+        Some(val)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.data.contains_key(key)
+    }
+
+    /// Set the memory budget in bytes, evicting the least recently used entries if needed
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+
+        self.evict();
+    }
+
+    #[inline]
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// The estimated memory used by the entries, in bytes
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    fn stamp(&mut self) -> u64 {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+
+        stamp
+    }
+
+    fn evict(&mut self) {
+        while self.size > self.budget {
+            if let Some((_, key)) = self.order.pop_first() {
+                if let Some(entry) = self.data.remove(&key) {
+                    self.size -= entry.size;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+
+    fn keys(cache: &Cache<&'static str, u32>) -> Vec<&'static str> {
+        cache.order.values().copied().collect()
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = Cache::new(100);
+        cache.insert("a", 0, 40);
+        cache.insert("b", 1, 40);
+        // accessing a makes b the least recently used
+        assert_eq!(cache.get(&"a"), Some(&0));
+
+        cache.insert("c", 2, 40);
+        assert_eq!(keys(&cache), ["a", "c"]);
+        assert_eq!(cache.size(), 80);
+
+        // several entries can be evicted for a big one
+        cache.insert("d", 3, 90);
+        assert_eq!(keys(&cache), ["d"]);
+        assert_eq!(cache.size(), 90);
+    }
+
+    #[test]
+    fn replace_and_extract() {
+        let mut cache = Cache::new(100);
+        cache.insert("a", 0, 40);
+        cache.insert("b", 1, 40);
+        cache.insert("a", 2, 10);
+        assert_eq!(keys(&cache), ["b", "a"]);
+        assert_eq!(cache.size(), 50);
+
+        assert_eq!(cache.extract(&"b"), Some(1));
+        assert_eq!(cache.extract(&"b"), None);
+        assert!(!cache.contains(&"b"));
+        assert_eq!(cache.size(), 10);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn budget() {
+        let mut cache = Cache::new(100);
+        // bigger than the budget
+        cache.insert("a", 0, 101);
+        assert!(cache.is_empty());
+
+        cache.insert("b", 1, 30);
+        cache.insert("c", 2, 30);
+        cache.insert("d", 3, 30);
+        cache.set_budget(60);
+        assert_eq!(keys(&cache), ["c", "d"]);
+        assert_eq!(cache.budget(), 60);
+    }
+
+    #[test]
+    fn hits_and_misses() {
+        let mut cache = Cache::new(100);
+        cache.insert("a", 0, 10);
+        assert!(cache.get(&"a").is_some());
+        assert!(cache.get(&"a").is_some());
+        assert!(cache.get(&"b").is_none());
+        // contains, peek and extract are not counted
+        assert!(cache.contains(&"a"));
+        assert_eq!(cache.peek(&"a"), Some(&0));
+        assert_eq!(cache.peek(&"b"), None);
+        assert!(cache.extract(&"a").is_some());
+
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+    }
+}
@@ -6,23 +6,6 @@ pub struct ArcDeg<T: BaseFloat>(pub T);
 //pub const TWICE_PI: f64 = 6.28318530718;
 pub const PI: f64 = std::f64::consts::PI;
 
-impl<T> ArcDeg<T>
-where
-    T: BaseFloat,
-{
-    fn get_frac_minutes(&self) -> ArcMin<T> {
-        let deg = *self;
-
-        let frac = deg.fract();
-        let minutes_per_degree = T::from(60_f32).unwrap_abort();
-        ArcMin(frac * minutes_per_degree)
-    }
-
-    fn truncate(&mut self) {
-        *self = Self((*self).trunc());
-    }
-}
-
 use cgmath::{Deg, Rad};
 // Convert a Rad<T> to an ArcDeg<T>
 impl<T> From<Rad<T>> for ArcDeg<T>
@@ -70,19 +53,6 @@ where
 #[derive(Clone, Copy)]
 pub struct ArcHour<T: BaseFloat>(pub T);
 
-impl<T> ArcHour<T>
-where
-    T: BaseFloat,
-{
-    fn get_frac_minutes(&self) -> ArcMin<T> {
-        let hour = *self;
-
-        let frac = hour.fract();
-        let minutes_per_hour = T::from(60_f64).unwrap_abort();
-        ArcMin(minutes_per_hour * frac)
-    }
-}
-
 impl<T> From<Rad<T>> for ArcHour<T>
 where
     T: BaseFloat,
@@ -121,19 +91,6 @@ where
 #[derive(Clone, Copy)]
 pub struct ArcMin<T: BaseFloat>(pub T);
 
-impl<T> ArcMin<T>
-where
-    T: BaseFloat,
-{
-    fn get_frac_seconds(&self) -> ArcSec<T> {
-        let min: ArcMin<T> = *self;
-
-        let frac = min.fract();
-        let seconds_per_min = T::from(60_f64).unwrap_abort();
-        ArcSec(seconds_per_min * frac)
-    }
-}
-
 // Convert a Rad<T> to an ArcMin<T>
 impl<T> From<Rad<T>> for ArcMin<T>
 where
@@ -293,73 +250,286 @@ pub trait FormatType {
 
 impl FormatType for DD {
     fn to_string<S: BaseFloat + ToString>(angle: Angle<S>) -> String {
-        let angle = Rad(angle.0);
-        let degrees: ArcDeg<S> = angle.into();
+        let degrees = angle.to_degrees().to_f64().unwrap_abort();
 
-        degrees.to_string()
+        to_decimal(degrees, 6)
     }
 }
 impl FormatType for DMM {
     fn to_string<S: BaseFloat + ToString>(angle: Angle<S>) -> String {
-        let angle = Rad(angle.0);
+        let degrees = angle.to_degrees().to_f64().unwrap_abort();
 
-        let mut degrees: ArcDeg<S> = angle.into();
-        let minutes = degrees.get_frac_minutes();
-
-        degrees.truncate();
-
-        let mut result = degrees.to_string() + " ";
-        result += &minutes.to_string();
-
-        result
+        format_sexagesimal(degrees, 4, false, &["°", "\'", ""])
     }
 }
 use crate::Abort;
 impl FormatType for DMS {
     fn to_string<S: BaseFloat + ToString>(angle: Angle<S>) -> String {
-        let angle = Rad(angle.0);
-        let degrees: ArcDeg<S> = angle.into();
-        let minutes = degrees.get_frac_minutes();
-        let seconds = minutes.get_frac_seconds();
+        let degrees = angle.to_degrees().to_f64().unwrap_abort();
 
-        let num_sec_per_minutes = S::from(60).unwrap_abort();
+        format_sexagesimal(degrees, 5, false, &["°", "\'", "\'\'"])
+    }
+}
 
-        let degrees = degrees.trunc();
-        let minutes = minutes.trunc() % num_sec_per_minutes;
-        let seconds = seconds.trunc() % num_sec_per_minutes;
+impl FormatType for HMS {
+    fn to_string<S: BaseFloat + ToString>(angle: Angle<S>) -> String {
+        let hours = angle.to_degrees().to_f64().unwrap_abort() / 15.0;
+
+        format_sexagesimal(hours, 5, false, &["h", "\'", "\'\'"])
+    }
+}
+
+/// Convert a decimal value into a sexagesimal string
+///
+/// The precision follows the codes of the former js formatter:
+/// 1: deg, 2: 1/10th deg, 3: min, 4: 1/10th min, 5: sec, 6: 1/10th sec, 7: 1/100th sec, 8: 1/1000th sec
+///
+/// # Arguments
+///
+/// * `num` - The value to format (in degrees or in hours)
+/// * `prec` - The precision code
+/// * `plus` - Write the '+' sign for positive values
+pub fn to_sexagesimal(num: f64, prec: u8, plus: bool) -> String {
+    format_sexagesimal(num, prec, plus, &[" ", " ", ""])
+}
+
+/// Convert a decimal value into a string with `prec` decimals
+pub fn to_decimal(num: f64, prec: u8) -> String {
+    format!("{:.*}", prec as usize, num)
+}
+
+/// Format `num` in sexagesimal with custom separators written after each field
+///
+/// The value is rounded once at the requested precision before being split into its
+/// fields so that a carry is propagated to the upper ones (i.e. 59.9999s gives 1m00s, never 60s)
+pub fn format_sexagesimal(num: f64, prec: u8, plus: bool, seps: &[&str; 3]) -> String {
+    // number of fields written and number of decimals of the last one
+    let (num_fields, num_decimals): (usize, u32) = match prec {
+        0 | 1 => (1, 0),
+        2 => (1, 1),
+        3 => (2, 0),
+        4 => (2, 1),
+        5 => (3, 0),
+        p => (3, (p as u32 - 5).min(9)),
+    };
+
+    let p10 = 10_u64.pow(num_decimals);
+    let units_per_unit = 60_u64.pow(num_fields as u32 - 1) * p10;
+    let units = (num.abs() * (units_per_unit as f64)).round() as u64;
+
+    // A value rounding to zero is not signed
+    let sign = if num < 0.0 && units > 0 {
+        "-"
+    } else if plus {
+        "+"
+    } else {
+        ""
+    };
+
+    let frac = units % p10;
+    let mut whole = units / p10;
+    let mut fields = [0_u64; 3];
+    for field in fields[1..num_fields].iter_mut().rev() {
+        *field = whole % 60;
+        whole /= 60;
+    }
+    fields[0] = whole;
+
+    let mut result = sign.to_string();
+    for (idx, field) in fields[..num_fields].iter().enumerate() {
+        let last = idx == num_fields - 1;
+
+        if num_fields > 1 {
+            result += &format!("{:02}", field);
+        } else {
+            result += &field.to_string();
+        }
 
-        let mut result = degrees.to_string() + "°";
-        result += &minutes.to_string();
-        result += "\'";
-        result += &seconds.to_string();
-        result += "\'\'";
+        if last && num_decimals > 0 {
+            result += &format!(".{:0width$}", frac, width = num_decimals as usize);
+        }
 
-        result
+        result += seps[idx];
+    }
+
+    result.trim_end().to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CooToken {
+    value: f64,
+    is_integer: bool,
+    // explicit sign given: Some(true) for '-', Some(false) for '+'
+    negative: Option<bool>,
+    unit: Option<char>,
+}
+
+fn tokenize_coo(s: &str) -> Result<(Vec<CooToken>, Option<usize>), String> {
+    let mut tokens = vec![];
+    // Number of tokens found before a comma
+    let mut comma_split = None;
+
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == ',' {
+            if comma_split.is_some() {
+                return Err(format!("Too many commas in '{}'", s));
+            }
+            comma_split = Some(tokens.len());
+            chars.next();
+        } else if c == '+' || c == '-' || c == '.' || c.is_ascii_digit() {
+            let negative = match c {
+                '+' => {
+                    chars.next();
+                    Some(false)
+                }
+                '-' => {
+                    chars.next();
+                    Some(true)
+                }
+                _ => None,
+            };
+
+            let mut num = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_ascii_digit() || d == '.' {
+                    num.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let value = num
+                .parse::<f64>()
+                .map_err(|_| format!("'{}' is not a valid number in '{}'", num, s))?;
+            let is_integer = !num.contains('.');
+
+            // an optional unit
+            let unit = match chars.peek() {
+                Some(&u) if "hHdDmMsS°'\"′″:".contains(u) => {
+                    chars.next();
+                    Some(u.to_ascii_lowercase())
+                }
+                _ => None,
+            };
+
+            tokens.push(CooToken {
+                value,
+                is_integer,
+                negative,
+                unit,
+            });
+        } else {
+            return Err(format!("Unexpected character '{}' in '{}'", c, s));
+        }
     }
+
+    Ok((tokens, comma_split))
 }
 
-impl FormatType for HMS {
-    fn to_string<S: BaseFloat + ToString>(angle: Angle<S>) -> String {
-        let angle = Rad(angle.0);
+// Merge the sexagesimal fields of a coordinate, returns the absolute value
+fn merge_fields(fields: &[CooToken], s: &str) -> Result<f64, String> {
+    if fields.is_empty() || fields.len() > 3 {
+        return Err(format!("Cannot parse a coordinate in '{}'", s));
+    }
 
-        let hours: ArcHour<S> = angle.into();
-        let minutes = hours.get_frac_minutes();
-        let seconds = minutes.get_frac_seconds();
+    let mut value = 0.0;
+    let mut factor = 1.0;
+    for (idx, field) in fields.iter().enumerate() {
+        if idx > 0 {
+            if field.negative.is_some() {
+                return Err(format!("Misplaced sign in '{}'", s));
+            }
 
-        let num_sec_per_minutes = S::from(60).unwrap_abort();
+            if field.value >= 60.0 {
+                return Err(format!(
+                    "Minutes and seconds must be lower than 60 in '{}'",
+                    s
+                ));
+            }
+        }
 
-        let hours = hours.trunc();
-        let minutes = minutes.trunc() % num_sec_per_minutes;
-        let seconds = seconds.trunc() % num_sec_per_minutes;
+        if idx < fields.len() - 1 && !field.is_integer {
+            return Err(format!("Only the last field can be decimal in '{}'", s));
+        }
 
-        let mut result = hours.to_string() + "h";
-        result += &minutes.to_string();
-        result += "\'";
-        result += &seconds.to_string();
-        result += "\'\'";
+        value += field.value * factor;
+        factor /= 60.0;
+    }
+
+    Ok(value)
+}
+
+/// Parse a pair of coordinates
+///
+/// Decimal degrees ("83.633 22.014", "83.633, +22.014") and sexagesimal forms
+/// ("05h34m31.94s +22d00m52.2s", "05:34:31.94 +22:00:52.2", "05 34 31.94 +22 00 52.2")
+/// are accepted. A sexagesimal longitude is read in hours unless its first field
+/// is explicitly given in degrees.
+///
+/// Returns the (longitude, latitude) in degrees
+pub fn parse_coordinates(s: &str) -> Result<(f64, f64), String> {
+    let (tokens, comma_split) = tokenize_coo(s)?;
+
+    let split = if let Some(split) = comma_split {
+        split
+    } else if let Some(idx) = tokens
+        .iter()
+        .skip(1)
+        .position(|t| t.negative.is_some())
+    {
+        // The latitude begins with the first signed field
+        idx + 1
+    } else if let Some(idx) = tokens
+        .iter()
+        .position(|t| t.unit == Some('s') || t.unit == Some('"') || t.unit == Some('″'))
+    {
+        // The longitude finishes with its seconds field
+        idx + 1
+    } else if tokens.len() % 2 == 0 {
+        tokens.len() / 2
+    } else {
+        return Err(format!("Cannot split '{}' into a longitude and a latitude", s));
+    };
+
+    let (lon_fields, lat_fields) = tokens.split_at(split);
+    if lon_fields.is_empty() || lat_fields.is_empty() {
+        return Err(format!("Cannot split '{}' into a longitude and a latitude", s));
+    }
+
+    // Longitude
+    let first = lon_fields[0];
+    if first.negative == Some(true) {
+        return Err(format!("The longitude must be positive in '{}'", s));
+    }
+    let in_hours = match first.unit {
+        Some('h') => true,
+        Some('d') | Some('°') => false,
+        _ => lon_fields.len() > 1,
+    };
+    let mut lon = merge_fields(lon_fields, s)?;
+    if in_hours {
+        if lon >= 24.0 {
+            return Err(format!("Right ascension must be lower than 24h in '{}'", s));
+        }
+        lon *= 15.0;
+    } else if lon >= 360.0 {
+        return Err(format!("Longitude must be lower than 360° in '{}'", s));
+    }
 
-        result
+    // Latitude
+    let mut lat = merge_fields(lat_fields, s)?;
+    if lat_fields[0].negative == Some(true) {
+        lat = -lat;
     }
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!("Latitude must be in [-90°, 90°] in '{}'", s));
+    }
+
+    Ok((lon, lat))
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash)]
@@ -743,3 +913,76 @@ impl UniformType for Angle<f64> {
         gl.uniform1f(location, value.0 as f32);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn sexagesimal_formatting() {
+        use super::to_sexagesimal;
+
+        // 05h34m31.94s
+        let ra = 5.0 + 34.0 / 60.0 + 31.94 / 3600.0;
+        assert_eq!(to_sexagesimal(ra, 8, false), "05 34 31.940");
+        assert_eq!(to_sexagesimal(ra, 6, false), "05 34 31.9");
+        assert_eq!(to_sexagesimal(ra, 5, false), "05 34 32");
+        assert_eq!(to_sexagesimal(ra, 4, false), "05 34.5");
+        assert_eq!(to_sexagesimal(ra, 3, false), "05 35");
+        assert_eq!(to_sexagesimal(ra, 2, false), "5.6");
+        assert_eq!(to_sexagesimal(ra, 1, false), "6");
+
+        // +22d00m52.2s
+        assert_eq!(to_sexagesimal(22.0145, 7, true), "+22 00 52.20");
+        assert_eq!(to_sexagesimal(-22.0145, 7, true), "-22 00 52.20");
+        assert_eq!(to_sexagesimal(-22.0145, 7, false), "-22 00 52.20");
+    }
+
+    #[test]
+    fn sexagesimal_rounding_carry() {
+        use super::to_sexagesimal;
+
+        // 09h59m59.99999s must be carried up to the hours
+        let v = 10.0 - 1e-5 / 3600.0;
+        assert_eq!(to_sexagesimal(v, 5, false), "10 00 00");
+        assert_eq!(to_sexagesimal(v, 8, false), "10 00 00.000");
+        // 12°59.99'
+        assert_eq!(to_sexagesimal(12.0 + 59.99 / 60.0, 4, false), "13 00.0");
+        // A negative value rounded to zero is not signed
+        assert_eq!(to_sexagesimal(-1e-9, 5, false), "00 00 00");
+    }
+
+    #[test]
+    fn parse_coordinates() {
+        use super::parse_coordinates;
+
+        let expected_ra = (5.0 + 34.0 / 60.0 + 31.94 / 3600.0) * 15.0;
+        let expected_dec = 22.0 + 52.2 / 3600.0;
+
+        let check = |s: &str, lon: f64, lat: f64| {
+            let (l, b) = parse_coordinates(s).unwrap();
+            assert!((l - lon).abs() < 1e-9, "{}: {} != {}", s, l, lon);
+            assert!((b - lat).abs() < 1e-9, "{}: {} != {}", s, b, lat);
+        };
+
+        check("05h34m31.94s +22d00m52.2s", expected_ra, expected_dec);
+        check("05h34m31.94s 22d00m52.2s", expected_ra, expected_dec);
+        check("05:34:31.94 +22:00:52.2", expected_ra, expected_dec);
+        check("05 34 31.94 -22 00 52.2", expected_ra, -expected_dec);
+        check("83.633 22.014", 83.633, 22.014);
+        check("83.633, -5.2", 83.633, -5.2);
+        check("83.633d +22.014d", 83.633, 22.014);
+        check("83d37m59.1s -00d30m", 83.0 + 37.0 / 60.0 + 59.1 / 3600.0, -0.5);
+    }
+
+    #[test]
+    fn parse_invalid_coordinates() {
+        use super::parse_coordinates;
+
+        assert!(parse_coordinates("").is_err());
+        assert!(parse_coordinates("83.633").is_err());
+        assert!(parse_coordinates("05h61m00s +22d00m00s").is_err());
+        assert!(parse_coordinates("25h00m00s +22d00m00s").is_err());
+        assert!(parse_coordinates("83.633 95.0").is_err());
+        assert!(parse_coordinates("05.5 34 31.94 +22 00 52.2").is_err());
+        assert!(parse_coordinates("M31").is_err());
+    }
+}
@@ -46,6 +46,49 @@ where
     }
 }
 
+/// A position observed at a given epoch
+#[derive(Clone, Copy, Debug)]
+pub struct LonLatEpochT<S: BaseFloat> {
+    pub lonlat: LonLatT<S>,
+    pub epoch: Epoch,
+}
+
+use crate::epoch::Epoch;
+impl<S> LonLatEpochT<S>
+where
+    S: BaseFloat,
+{
+    pub fn new(lonlat: LonLatT<S>, epoch: Epoch) -> Self {
+        Self { lonlat, epoch }
+    }
+}
+
+impl<S> LonLat<S> for LonLatEpochT<S>
+where
+    S: BaseFloat,
+{
+    #[inline]
+    fn lon(&self) -> Angle<S> {
+        self.lonlat.lon()
+    }
+
+    #[inline]
+    fn lat(&self) -> Angle<S> {
+        self.lonlat.lat()
+    }
+
+    #[inline]
+    fn lonlat(&self) -> LonLatT<S> {
+        self.lonlat
+    }
+
+    /// The epoch defaults to J2000
+    #[inline]
+    fn from_lonlat(lonlat: &LonLatT<S>) -> Self {
+        Self::new(*lonlat, Epoch::J2000)
+    }
+}
+
 use crate::math::angle::ToAngle;
 impl From<wcs::LonLat> for LonLatT<f64> {
     fn from(lonlat: wcs::LonLat) -> Self {
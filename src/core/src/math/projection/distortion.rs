@@ -0,0 +1,227 @@
+use cgmath::{InnerSpace, Matrix2, SquareMatrix, Vector2, Vector3};
+use serde::Serialize;
+
+use super::ProjectionType;
+use crate::camera::CameraViewPort;
+use crate::math::projection::coo_space::XYScreen;
+
+const RAD_TO_ARCSEC: f64 = 180.0 * 3600.0 / std::f64::consts::PI;
+
+/// Tissot indicatrix: the ellipse into which an infinitesimal circle of
+/// the sky is mapped onto the screen
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Tissot {
+    /// Scale factor along the major axis, relative to the scale at the projection center
+    pub semi_major: f64,
+    /// Scale factor along the minor axis, relative to the scale at the projection center
+    pub semi_minor: f64,
+    /// Angle in radians of the major axis, counted counterclockwise from the screen x axis
+    pub angle: f64,
+}
+
+/// How the projection locally distorts the sky at a screen position
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Distortion {
+    /// Pixel scale along the screen x axis in arcsec/px
+    pub scale_x: f64,
+    /// Pixel scale along the screen y axis in arcsec/px
+    pub scale_y: f64,
+    /// Area scale factor, relative to the one at the projection center
+    pub area_scale: f64,
+    /// Angle in radians of the local north of the view frame, counted
+    /// counterclockwise from the screen x axis
+    pub north_angle: f64,
+    /// Angle in radians of the local east of the view frame, counted
+    /// counterclockwise from the screen x axis
+    pub east_angle: f64,
+    pub tissot: Tissot,
+}
+
+impl ProjectionType {
+    /// Compute the local distortion of the projection at a screen position
+    ///
+    /// The jacobian of the projection is estimated by finite differences along
+    /// the local east and north directions of the view frame.
+    /// Returns `None` if the position or its close neighbourhood cannot be unprojected.
+    ///
+    /// # Arguments
+    ///
+    /// * ``pos_screen_space`` - The position in the screen pixel space (top-left of the screen being the origin)
+    /// * ``camera`` - The camera object
+    pub fn distortion_at(
+        &self,
+        pos_screen_space: &XYScreen,
+        camera: &CameraViewPort,
+    ) -> Option<Distortion> {
+        let h = finite_diff_step(camera);
+
+        let center_scale = self.center_scale(camera, h)?;
+
+        let pos = self
+            .screen_to_model_space(pos_screen_space, camera)?
+            .truncate()
+            .normalize();
+        // Local east and north directions of the view frame
+        let pole = Vector3::unit_y();
+        let east = pole.cross(pos);
+        // At the poles, the east is not defined, we take an arbitrary one
+        let east = if east.magnitude2() < 1e-24 {
+            Vector3::unit_x()
+        } else {
+            east.normalize()
+        };
+        let north = pos.cross(east);
+
+        let j = jacobian(
+            |v| self.model_to_screen_space(&v.extend(1.0), camera),
+            &pos,
+            &east,
+            &north,
+            h,
+        )?;
+
+        Some(Distortion::from_jacobian(&j, center_scale))
+    }
+
+    /// Scale of the projection at its center in px/rad
+    fn center_scale(&self, camera: &CameraViewPort, h: f64) -> Option<f64> {
+        let j = jacobian(
+            |v| self.world_to_screen_space(&v.extend(1.0), camera),
+            &Vector3::unit_z(),
+            &Vector3::unit_x(),
+            &Vector3::unit_y(),
+            h,
+        )?;
+
+        Some(j.determinant().abs().sqrt())
+    }
+}
+
+impl Distortion {
+    /// Derive the distortion metrics from the jacobian of the projection
+    ///
+    /// # Arguments
+    ///
+    /// * ``j`` - The derivatives in px/rad of the screen position along the east (first column)
+    ///   and the north (second column) directions
+    /// * ``center_scale`` - The scale at the projection center in px/rad
+    fn from_jacobian(j: &Matrix2<f64>, center_scale: f64) -> Self {
+        // The inverse of the jacobian gives the angular displacement of a pixel step
+        let (scale_x, scale_y) = if let Some(inv) = j.invert() {
+            (
+                inv.x.magnitude() * RAD_TO_ARCSEC,
+                inv.y.magnitude() * RAD_TO_ARCSEC,
+            )
+        } else {
+            (f64::INFINITY, f64::INFINITY)
+        };
+
+        // The screen y axis points downwards
+        let ccw_angle = |v: Vector2<f64>| (-v.y).atan2(v.x);
+
+        let (s1, s2, phi) = svd2(j);
+        let s0 = center_scale;
+
+        Self {
+            scale_x,
+            scale_y,
+            area_scale: (s1 * s2) / (s0 * s0),
+            north_angle: ccw_angle(j.y),
+            east_angle: ccw_angle(j.x),
+            tissot: Tissot {
+                semi_major: s1 / s0,
+                semi_minor: s2 / s0,
+                angle: ccw_angle(Vector2::new(phi.cos(), phi.sin())),
+            },
+        }
+    }
+}
+
+/// Angular step in radians used for the finite differences, a fraction of a pixel
+fn finite_diff_step(camera: &CameraViewPort) -> f64 {
+    let aperture = camera.get_aperture().to_radians();
+    let width = camera.get_width() as f64;
+
+    0.25 * aperture / width
+}
+
+/// Jacobian of a projection function computed by central differences
+/// along two unit directions `u` and `v` tangent to the sphere at `pos`
+fn jacobian<F>(
+    proj: F,
+    pos: &Vector3<f64>,
+    u: &Vector3<f64>,
+    v: &Vector3<f64>,
+    h: f64,
+) -> Option<Matrix2<f64>>
+where
+    F: Fn(Vector3<f64>) -> Option<Vector2<f64>>,
+{
+    let (s, c) = h.sin_cos();
+    let derivative = |d: &Vector3<f64>| -> Option<Vector2<f64>> {
+        let p1 = proj(pos * c + d * s)?;
+        let p2 = proj(pos * c - d * s)?;
+
+        Some((p1 - p2) / (2.0 * h))
+    };
+
+    Some(Matrix2::from_cols(derivative(u)?, derivative(v)?))
+}
+
+/// Singular value decomposition of a 2x2 matrix written as M = R(phi) * diag(s1, ±s2) * R(theta)
+///
+/// Returns the singular values s1 >= s2 >= 0 and the angle phi of the left rotation
+fn svd2(m: &Matrix2<f64>) -> (f64, f64, f64) {
+    // cgmath matrices are column major
+    let (a, b, c, d) = (m.x.x, m.y.x, m.x.y, m.y.y);
+
+    let e = (a + d) * 0.5;
+    let f = (a - d) * 0.5;
+    let g = (c + b) * 0.5;
+    let h = (c - b) * 0.5;
+
+    let q = (e * e + h * h).sqrt();
+    let r = (f * f + g * g).sqrt();
+
+    let a1 = g.atan2(f);
+    let a2 = h.atan2(e);
+
+    (q + r, (q - r).abs(), (a2 + a1) * 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Matrix2, Rad};
+
+    #[test]
+    fn svd2() {
+        let (phi, theta) = (0.3, 0.5);
+        let m = Matrix2::from_angle(Rad(phi))
+            * Matrix2::new(3.0, 0.0, 0.0, 1.0)
+            * Matrix2::from_angle(Rad(theta));
+
+        let (s1, s2, phi_m) = super::svd2(&m);
+        assert!((s1 - 3.0).abs() < 1e-12);
+        assert!((s2 - 1.0).abs() < 1e-12);
+        // The axis direction is defined modulo pi
+        assert!(((phi_m - phi) / std::f64::consts::PI).fract().abs() < 1e-12);
+    }
+
+    #[test]
+    fn conformal_distortion() {
+        // A rotation scaled by 2 px/rad with a center scale of 1 px/rad
+        let j = Matrix2::from_angle(Rad(0.2)) * 2.0;
+        let d = super::Distortion::from_jacobian(&j, 1.0);
+
+        assert!((d.area_scale - 4.0).abs() < 1e-12);
+        assert!((d.tissot.semi_major - 2.0).abs() < 1e-12);
+        assert!((d.tissot.semi_minor - 2.0).abs() < 1e-12);
+        assert!((d.scale_x - 0.5 * super::RAD_TO_ARCSEC).abs() < 1e-6);
+        assert!((d.scale_y - 0.5 * super::RAD_TO_ARCSEC).abs() < 1e-6);
+        // The east and north axes are orthogonal
+        let delta = (d.north_angle - d.east_angle).abs();
+        assert!((delta - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    }
+}
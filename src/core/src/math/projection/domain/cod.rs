@@ -26,6 +26,8 @@ pub struct Cod {
 }
 
 impl Cod {
+    /// Domain of the conic equidistant projection with its default parameters
+    /// i.e. a standard parallel at 45 deg (theta_a = PI / 4, eta = 0)
     pub const fn new() -> Self {
         Self {
             r_min: 0.2146018366,
@@ -41,6 +43,53 @@ impl Cod {
         }
     }
 
+    /// Domain of the conic equidistant projection
+    ///
+    /// # Arguments
+    ///
+    /// * `theta_a` - Mean of the two standard parallels in radians
+    /// * `eta` - Half of the angle between the two standard parallels in radians
+    pub fn from_params(theta_a: f64, eta: f64) -> Self {
+        // eta = 0 is the limit case of the two standard parallels being the same
+        let (c, y0) = if eta == 0.0 {
+            (theta_a.sin(), 1.0 / theta_a.tan())
+        } else {
+            (
+                theta_a.sin() * eta.sin() / eta,
+                eta / (eta.tan() * theta_a.tan()),
+            )
+        };
+
+        // Radius of the north and south poles
+        let r_min = theta_a - HALF_PI + y0;
+        let r_max = theta_a + HALF_PI + y0;
+
+        let c_pi = (c * PI).cos();
+        let x_max = if c >= 0.5 {
+            r_max
+        } else {
+            r_max * (c * PI).sin()
+        };
+        let y_max = if c_pi < 0.0 {
+            y0 - r_max * c_pi
+        } else {
+            y0 - r_min * c_pi
+        };
+
+        Self {
+            r_min,
+            r_max,
+            negative_ta: theta_a < 0.0,
+            c,
+            y0,
+
+            x_min: -x_max,
+            x_max,
+            y_min: y0 - r_max,
+            y_max,
+        }
+    }
+
     fn to_clip(&self, xy: &Vector2<f64>) -> XYClip {
         let x = (xy.x - self.x_min) / (self.x_max - self.x_min);
         let y = (xy.y - self.y_min) / (self.y_max - self.y_min);
@@ -55,13 +104,13 @@ impl ProjDef for Cod {
         let center_ellipse = self.to_clip(&Vector2::new(0.0, self.y0 + y_mean));
 
         // Big frontier ellipse
-        let a = 1.0;
-        let b = 2.0 * (2.356194490192345 + self.y0) / (2.356194490192345 + 3.0328465566001492);
+        let a = 2.0 * self.r_max / (self.x_max - self.x_min);
+        let b = 2.0 * (self.y0 - self.y_min) / (self.y_max - self.y_min);
         let e = b / a;
         let ext_ellipse = Translate { off: center_ellipse, def: Ellipse { a: a, b: b } };
 
         // Small ellipse where projection is not defined
-        let b_int = 2.0 * self.r_min / (2.356194490192345 + 3.0328465566001492);
+        let b_int = 2.0 * self.r_min / (self.y_max - self.y_min);
         let a_int = b_int / e;
         let int_ellipse = Translate { off: center_ellipse, def: Ellipse { a: a_int, b: b_int } };
 
@@ -2,6 +2,7 @@ pub mod cod;
 pub mod full;
 pub mod hpx;
 pub mod par;
+pub mod sfl;
 
 pub mod basic;
 
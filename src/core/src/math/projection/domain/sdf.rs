@@ -54,6 +54,7 @@ use super::{
     full::FullScreen,
     hpx::Hpx,
     par::Par,
+    sfl::Sfl,
     cod::Cod
 };
 
@@ -63,6 +64,7 @@ use super::{
 pub enum ProjDefType {
     Disk,
     Par,
+    Sfl,
     Cod,
     FullScreen,
     Hpx,
@@ -0,0 +1,17 @@
+use crate::math::projection::coo_space::XYClip;
+use crate::math::HALF_PI;
+
+pub struct Sfl;
+
+use super::sdf::ProjDef;
+impl ProjDef for Sfl {
+    fn sdf(&self, xy: &XYClip) -> f64 {
+        // The boundary is made of the two sinusoids x = +/- cos(y * PI/2)
+        // Its distance is estimated by f / |grad(f)|
+        let (s, c) = (xy.y * HALF_PI).sin_cos();
+        let d_sinusoid = (xy.x.abs() - c) / (1.0 + (HALF_PI * s).powi(2)).sqrt();
+        let d_poles = xy.y.abs() - 1.0;
+
+        d_sinusoid.max(d_poles)
+    }
+}
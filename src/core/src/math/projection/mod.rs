@@ -17,9 +17,10 @@ use crate::math::{rotation::Rotation, HALF_PI};
 use cgmath::Vector2;
 
 pub mod coo_space;
+pub mod distortion;
 pub mod domain;
 
-use domain::{basic, cod::Cod, full::FullScreen, hpx::Hpx, par::Par};
+use domain::{basic, cod::Cod, full::FullScreen, hpx::Hpx, par::Par, sfl::Sfl};
 
 /* S <-> NDC space conversion methods */
 pub fn screen_to_ndc_space(
@@ -98,7 +99,9 @@ pub fn screen_to_clip_space(
 }
 
 use al_api::coo_system::CooSystem;
+use al_api::projection::ProjectionParams;
 use cgmath::InnerSpace;
+use wasm_bindgen::JsValue;
 
 use crate::coo_space::{XYClip, XYZWWorld};
 
@@ -113,45 +116,168 @@ pub enum ProjectionType {
     /* ZEA,	     Equal-area 		        */
     Zea(mapproj::zenithal::zea::Zea),
     /* FEYE,     Fish-eyes                  */
-    //Feye(mapproj::zenithal::feye::Feye),
-    /* AIR,                                 */
-    //Air(mapproj::zenithal::air::Air),
-    //AZP: {fov: 180},
-    //Azp(mapproj::zenithal::azp::Azp),
-    /* ARC,                                 */
-    //Arc(mapproj::zenithal::arc::Arc),
-    /* NCP,                                 */
-    //Ncp(mapproj::zenithal::ncp::Ncp),
+    Feye(mapproj::zenithal::feye::Feye),
+    /* AIR,      Airy                       */
+    Air(mapproj::zenithal::air::Air),
+    /* AZP,      Zenithal perspective       */
+    Azp(mapproj::zenithal::azp::Azp),
+    /* ARC,      Zenithal equidistant       */
+    Arc(mapproj::zenithal::arc::Arc),
+    /* NCP,      North celestial pole       */
+    Ncp(mapproj::zenithal::ncp::Ncp),
+    /* ZPN,      Zenithal polynomial        */
+    Zpn(mapproj::zenithal::zpn::Zpn),
 
     // Pseudo-cylindrical projections
     /* AIT,      Aitoff                     */
     Ait(mapproj::pseudocyl::ait::Ait),
     // MOL,      Mollweide                  */
     Mol(mapproj::pseudocyl::mol::Mol),
-    // PAR,                                 */
-    //Par(mapproj::pseudocyl::par::Par),
-    // SFL,                                 */
-    //Sfl(mapproj::pseudocyl::sfl::Sfl),
+    // PAR,      Parabolic                  */
+    Par(mapproj::pseudocyl::par::Par),
+    // SFL,      Sanson-Flamsteed           */
+    Sfl(mapproj::pseudocyl::sfl::Sfl),
 
     // Cylindrical projections
     // MER,      Mercator                   */
     Mer(mapproj::cylindrical::mer::Mer),
-    // CAR,                                 */
-    //Car(mapproj::cylindrical::car::Car),
-    // CEA,                                 */
-    //Cea(mapproj::cylindrical::cea::Cea),
-    // CYP,                                 */
-    //Cyp(mapproj::cylindrical::cyp::Cyp),
+    // CAR,      Plate carrée               */
+    Car(mapproj::cylindrical::car::Car),
+    // CEA,      Cylindrical equal area     */
+    Cea(mapproj::cylindrical::cea::Cea),
+    // CYP,      Cylindrical perspective    */
+    Cyp(mapproj::cylindrical::cyp::Cyp),
 
     // Conic projections
-    // COD,                                 */
-    //Cod(mapproj::conic::cod::Cod),
+    // COD,      Conic equidistant          */
+    Cod(Conic),
 
     // HEALPix hybrid projection
-    //Hpx(mapproj::hybrid::hpx::Hpx),
+    Hpx(mapproj::hybrid::hpx::Hpx),
 }
 
 impl ProjectionType {
+    /// Build a projection from its FITS/WCS code and its parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The projection code among TAN, STG, SIN, ZEA, FEYE, AIR, AZP, ARC,
+    ///   NCP, ZPN, MER, CAR, CEA, CYP, AIT, PAR, SFL, MOL, COD and HPX
+    /// * `params` - The projection parameters (PV2_m FITS keywords, angles in degrees).
+    ///   Undefined parameters take the default values of the WCS standard
+    pub fn from_name(name: &str, params: &ProjectionParams) -> Result<Self, JsValue> {
+        let in_range = |name: &str, v: f64, min: f64, max: f64| -> Result<f64, JsValue> {
+            if v > min && v < max {
+                Ok(v)
+            } else {
+                Err(JsValue::from_str(&format!(
+                    "{} = {} must be in ]{}, {}[",
+                    name, v, min, max
+                )))
+            }
+        };
+
+        let projection = match name {
+            // Zenithal
+            "TAN" => ProjectionType::Tan(mapproj::zenithal::tan::Tan::new()), /* Gnomonic projection      */
+            "STG" => ProjectionType::Stg(mapproj::zenithal::stg::Stg::new()), /* Stereographic projection */
+            "SIN" => ProjectionType::Sin(mapproj::zenithal::sin::Sin::new()), /* Orthographic		         */
+            "ZEA" => ProjectionType::Zea(mapproj::zenithal::zea::Zea::new()), /* Equal-area 		         */
+            "FEYE" => ProjectionType::Feye(mapproj::zenithal::feye::Feye::new()), /* Fish-eyes                */
+            "AIR" => {
+                let theta_b = params.theta_b.unwrap_or(90.0);
+                if !(theta_b > -90.0 && theta_b <= 90.0) {
+                    return Err(JsValue::from_str("AIR: thetaB must be in ]-90, 90]"));
+                }
+
+                let air_proj = mapproj::zenithal::air::Air::from_param(theta_b.to_radians());
+                //air_proj.set_n_iter(10);
+                //air_proj.set_eps(1e-12);
+                ProjectionType::Air(air_proj)
+            }
+            "AZP" => {
+                let mu = params.mu.unwrap_or(0.0);
+                let gamma = in_range("gamma", params.gamma.unwrap_or(0.0), -90.0, 90.0)?;
+                if mu == -1.0 {
+                    return Err(JsValue::from_str("AZP: mu = -1 is not allowed"));
+                }
+
+                ProjectionType::Azp(mapproj::zenithal::azp::Azp::from_params(
+                    mu,
+                    gamma.to_radians(),
+                )) /* Zenithal perspective     */
+            }
+            "ARC" => ProjectionType::Arc(mapproj::zenithal::arc::Arc::new()), /* Zenithal equidistant     */
+            "NCP" => ProjectionType::Ncp(mapproj::zenithal::ncp::Ncp::new()), /* North celestial pole     */
+            "ZPN" => {
+                // Without coefficients, the polynomial gives the zenithal equidistant projection
+                let coeffs = params.coeffs.clone().unwrap_or_else(|| vec![0.0, 1.0]);
+                if coeffs.len() > 21 {
+                    return Err(JsValue::from_str("ZPN: at most 21 coefficients are accepted"));
+                }
+
+                let zpn_proj = mapproj::zenithal::zpn::Zpn::from_params(coeffs).ok_or_else(|| {
+                    JsValue::from_str("ZPN: the polynomial coefficients do not define a valid projection")
+                })?;
+                ProjectionType::Zpn(zpn_proj) /* Zenithal polynomial      */
+            }
+            // Cylindrical
+            "MER" => ProjectionType::Mer(mapproj::cylindrical::mer::Mer::new()),
+            "CAR" => ProjectionType::Car(mapproj::cylindrical::car::Car::new()),
+            "CEA" => {
+                let lambda = params.lambda.unwrap_or(1.0);
+                if !(lambda > 0.0 && lambda <= 1.0) {
+                    return Err(JsValue::from_str("CEA: lambda must be in ]0, 1]"));
+                }
+
+                ProjectionType::Cea(mapproj::cylindrical::cea::Cea::from_param(lambda))
+            }
+            "CYP" => {
+                let mu = params.mu.unwrap_or(1.0);
+                let lambda = params.lambda.unwrap_or(1.0);
+                if mu == -lambda {
+                    return Err(JsValue::from_str("CYP: mu = -lambda is not allowed"));
+                }
+
+                ProjectionType::Cyp(mapproj::cylindrical::cyp::Cyp::from_params(mu, lambda))
+            }
+            // Pseudo-cylindrical
+            "AIT" => ProjectionType::Ait(mapproj::pseudocyl::ait::Ait::new()),
+            "PAR" => ProjectionType::Par(mapproj::pseudocyl::par::Par::new()),
+            "SFL" => ProjectionType::Sfl(mapproj::pseudocyl::sfl::Sfl::new()),
+            "MOL" => {
+                let mut mol_proj = mapproj::pseudocyl::mol::Mol::new();
+                mol_proj.set_n_iter(10);
+                mol_proj.set_epsilon(1e-12);
+
+                ProjectionType::Mol(mol_proj)
+            }
+            // Conic
+            "COD" => match (params.theta_a, params.eta) {
+                (None, None) => ProjectionType::Cod(Conic::new()),
+                (theta_a, eta) => {
+                    let theta_a = in_range("thetaA", theta_a.unwrap_or(45.0), -90.0, 90.0)?;
+                    let eta = in_range("eta", eta.unwrap_or(0.0), -90.0, 90.0)?;
+                    if theta_a == 0.0 {
+                        return Err(JsValue::from_str("COD: thetaA = 0 is not allowed"));
+                    }
+
+                    ProjectionType::Cod(Conic::from_params(theta_a.to_radians(), eta.to_radians()))
+                }
+            },
+            // Hybrid
+            "HPX" => ProjectionType::Hpx(mapproj::hybrid::hpx::Hpx::new()),
+            _ => {
+                return Err(JsValue::from_str(
+                    "Not a valid projection name. TAN, STG, SIN, ZEA, FEYE, AIR, AZP, ARC, NCP, ZPN, MER, CAR, CEA, CYP, AIT, PAR, SFL, MOL, COD and HPX are accepted",
+                ))
+            }
+        };
+
+
+        Ok(projection)
+    }
+
     /// Screen to model space deprojection
 
     /// Perform a screen to the world space deprojection
@@ -232,7 +358,8 @@ impl ProjectionType {
         camera: &CameraViewPort,
     ) -> Option<Vector2<f64>> {
         let view_coosys = camera.get_coo_system();
-        let c = CooSystem::ICRS.to::<f64>(view_coosys);
+        let c =
+            CooSystem::ICRS.to::<f64>(view_coosys, camera.get_horizontal_frame().icrs_to_altaz());
 
         let m2w = camera.get_m2w();
         let pos_world_space = m2w * c * pos_view_space;
@@ -245,7 +372,7 @@ impl ProjectionType {
         camera: &CameraViewPort,
     ) -> Vector2<f64> {
         let view_coosys = camera.get_coo_system();
-        let c = CooSystem::ICRS.to::<f64>(view_coosys);
+        let c = CooSystem::ICRS.to::<f64>(view_coosys, camera.get_horizontal_frame().icrs_to_altaz());
 
         let m2w = camera.get_m2w();
         let pos_world_space = m2w * c * pos_view_space;
@@ -309,64 +436,74 @@ impl ProjectionType {
 
     pub(crate) fn is_allsky(&self) -> bool {
         match self {
-            ProjectionType::Sin(_) | ProjectionType::Tan(_) => false,
-            //| ProjectionType::Feye(_)
-            //| ProjectionType::Ncp(_) => false,
+            ProjectionType::Sin(_)
+            | ProjectionType::Tan(_)
+            | ProjectionType::Feye(_)
+            | ProjectionType::Azp(_)
+            | ProjectionType::Ncp(_)
+            | ProjectionType::Zpn(_) => false,
             _ => true,
         }
     }
 
-    pub fn bounds_size_ratio(&self) -> f64 {
+    /// Bounds of the projection plane given by mapproj
+    fn bounds(&self) -> &ProjBounds {
         match self {
             // Zenithal projections
-            /* TAN,      Gnomonic projection        */
-            ProjectionType::Tan(_) => 1.0,
-            /* STG,	     Stereographic projection   */
-            ProjectionType::Stg(_) => 1.0,
-            /* SIN,	     Orthographic		        */
-            ProjectionType::Sin(_) => 1.0,
-            /* ZEA,	     Equal-area 		        */
-            ProjectionType::Zea(_) => 1.0,
-            /* FEYE,     Fish-eyes                  */
-            //ProjectionType::Feye(_) => 1.0,
-            /* AIR,                                 */
-            //ProjectionType::Air(_) => 1.0,
-            //AZP: {fov: 180},
-            //Azp(mapproj::zenithal::azp::Azp),
-            /* ARC,                                 */
-            //ProjectionType::Arc(_) => 1.0,
-            /* NCP,                                 */
-            //ProjectionType::Ncp(_) => 1.0,
+            ProjectionType::Tan(tan) => tan.bounds(),
+            ProjectionType::Stg(stg) => stg.bounds(),
+            ProjectionType::Sin(sin) => sin.bounds(),
+            ProjectionType::Zea(zea) => zea.bounds(),
+            ProjectionType::Feye(feye) => feye.bounds(),
+            ProjectionType::Air(air) => air.bounds(),
+            ProjectionType::Azp(azp) => azp.bounds(),
+            ProjectionType::Arc(arc) => arc.bounds(),
+            ProjectionType::Ncp(ncp) => ncp.bounds(),
+            ProjectionType::Zpn(zpn) => zpn.bounds(),
 
             // Pseudo-cylindrical projections
-            /* AIT,      Aitoff                     */
-            ProjectionType::Ait(_) => 2.0,
-            // MOL,      Mollweide                  */
-            ProjectionType::Mol(_) => 2.0,
-            // PAR,                                 */
-            //ProjectionType::Par(_) => 2.0,
-            // SFL,                                 */
-            //ProjectionType::Sfl(_) => 2.0,
+            ProjectionType::Ait(ait) => ait.bounds(),
+            ProjectionType::Mol(mol) => mol.bounds(),
+            ProjectionType::Par(par) => par.bounds(),
+            ProjectionType::Sfl(sfl) => sfl.bounds(),
 
             // Cylindrical projections
-            // MER,      Mercator                   */
-            ProjectionType::Mer(_) => 1.0,
-            // CAR,                                 */
-            //ProjectionType::Car(_) => 1.0,
-            // CEA,                                 */
-            //ProjectionType::Cea(_) => 1.0,
-            // CYP,                                 */
-            //ProjectionType::Cyp(_) => 1.0,
+            ProjectionType::Mer(mer) => mer.bounds(),
+            ProjectionType::Car(car) => car.bounds(),
+            ProjectionType::Cea(cea) => cea.bounds(),
+            ProjectionType::Cyp(cyp) => cyp.bounds(),
 
             // Conic projections
-            // COD,                                 */
-            //ProjectionType::Cod(_) => 1.0,
+            ProjectionType::Cod(cod) => cod.proj.bounds(),
 
             // HEALPix hybrid projection
-            //ProjectionType::Hpx(_) => 2.0,
+            ProjectionType::Hpx(hpx) => hpx.bounds(),
         }
     }
 
+    /// Width and height of the projection plane mapped to the clipping space [-1, 1]^2.
+    ///
+    /// Unbounded axis are taken in [-PI, PI]
+    pub fn bounds_size(&self) -> Vector2<f64> {
+        let proj_bounds = self.bounds();
+        let x_proj_bounds = proj_bounds.x_bounds().as_ref().unwrap_or(&(-PI..=PI));
+        let y_proj_bounds = proj_bounds.y_bounds().as_ref().unwrap_or(&(-PI..=PI));
+
+        Vector2::new(
+            x_proj_bounds.end() - x_proj_bounds.start(),
+            y_proj_bounds.end() - y_proj_bounds.start(),
+        )
+    }
+
+    /// Width over height ratio of the projection plane.
+    ///
+    /// The clipping space maps the bounds of the projection plane to [-1, 1]^2,
+    /// this ratio is used to keep the projection not distorted on the screen.
+    pub fn bounds_size_ratio(&self) -> f64 {
+        let size = self.bounds_size();
+        size.x / size.y
+    }
+
     pub fn aperture_start(&self) -> f64 {
         match self {
             // Zenithal projections
@@ -379,42 +516,44 @@ impl ProjectionType {
             /* ZEA,	     Equal-area 		        */
             ProjectionType::Zea(_) => 360.0,
             /* FEYE,     Fish-eyes                  */
-            //ProjectionType::Feye(_) => 190.0,
-            /* AIR,                                 */
-            //ProjectionType::Air(_) => 360.0,
-            //AZP: {fov: 180},
-            //Azp(mapproj::zenithal::azp::Azp),
-            /* ARC,                                 */
-            //ProjectionType::Arc(_) => 360.0,
-            /* NCP,                                 */
-            //ProjectionType::Ncp(_) => 180.0,
+            ProjectionType::Feye(_) => 190.0,
+            /* AIR,      Airy                       */
+            ProjectionType::Air(_) => 360.0,
+            /* AZP,      Zenithal perspective       */
+            ProjectionType::Azp(_) => 180.0,
+            /* ARC,      Zenithal equidistant       */
+            ProjectionType::Arc(_) => 360.0,
+            /* NCP,      North celestial pole       */
+            ProjectionType::Ncp(_) => 180.0,
+            /* ZPN,      Zenithal polynomial        */
+            ProjectionType::Zpn(_) => 180.0,
 
             // Pseudo-cylindrical projections
             /* AIT,      Aitoff                     */
             ProjectionType::Ait(_) => 360.0,
             // MOL,      Mollweide                  */
             ProjectionType::Mol(_) => 360.0,
-            // PAR,                                 */
-            //ProjectionType::Par(_) => 360.0,
-            // SFL,                                 */
-            //ProjectionType::Sfl(_) => 360.0,
+            // PAR,      Parabolic                  */
+            ProjectionType::Par(_) => 360.0,
+            // SFL,      Sanson-Flamsteed           */
+            ProjectionType::Sfl(_) => 360.0,
 
             // Cylindrical projections
             // MER,      Mercator                   */
             ProjectionType::Mer(_) => 360.0,
-            // CAR,                                 */
-            //ProjectionType::Car(_) => 360.0,
-            // CEA,                                 */
-            //ProjectionType::Cea(_) => 360.0,
-            // CYP,                                 */
-            //ProjectionType::Cyp(_) => 360.0,
+            // CAR,      Plate carrée               */
+            ProjectionType::Car(_) => 360.0,
+            // CEA,      Cylindrical equal area     */
+            ProjectionType::Cea(_) => 360.0,
+            // CYP,      Cylindrical perspective    */
+            ProjectionType::Cyp(_) => 360.0,
 
             // Conic projections
-            // COD,                                 */
-            //ProjectionType::Cod(_) => 330.0,
+            // COD,      Conic equidistant          */
+            ProjectionType::Cod(_) => 330.0,
 
             // HEALPix hybrid projection
-            //ProjectionType::Hpx(_) => 360.0,
+            ProjectionType::Hpx(_) => 360.0,
         }
     }
 
@@ -442,27 +581,35 @@ impl ProjectionType {
                 &DISK
             }
             /* FEYE,     Fish-eyes                  */
-            /*ProjectionType::Feye(_) => {
+            ProjectionType::Feye(_) => {
                 const DISK: ProjDefType = ProjDefType::Disk(basic::disk::Disk { radius: 1.0 });
                 &DISK
-            }*/
-            /* AIR,                                 */
-            /*ProjectionType::Air(_) => {
-                const DISK: ProjDefType = ProjDefType::FullScreen(FullScreen);
+            }
+            /* AIR,      Airy                       */
+            ProjectionType::Air(_) => {
+                const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
+                &FULL_SCREEN
+            }
+            /* AZP,      Zenithal perspective       */
+            ProjectionType::Azp(_) => {
+                const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
+                &FULL_SCREEN
+            }
+            /* ARC,      Zenithal equidistant       */
+            ProjectionType::Arc(_) => {
+                const DISK: ProjDefType = ProjDefType::Disk(basic::disk::Disk { radius: 1.0 });
                 &DISK
-            }*/
-            //AZP: {fov: 180},
-            //Azp(mapproj::zenithal::azp::Azp),
-            /* ARC,                                 */
-            /*ProjectionType::Arc(_) => {
+            }
+            /* NCP,      North celestial pole       */
+            ProjectionType::Ncp(_) => {
                 const DISK: ProjDefType = ProjDefType::Disk(basic::disk::Disk { radius: 1.0 });
                 &DISK
-            }*/
-            /* NCP,                                 */
-            /*ProjectionType::Ncp(_) => {
+            }
+            /* ZPN,      Zenithal polynomial        */
+            ProjectionType::Zpn(_) => {
                 const DISK: ProjDefType = ProjDefType::Disk(basic::disk::Disk { radius: 1.0 });
                 &DISK
-            }*/
+            }
 
             // Pseudo-cylindrical projections
             /* AIT,      Aitoff                     */
@@ -475,51 +622,96 @@ impl ProjectionType {
                 const ELLIPSE: ProjDefType = ProjDefType::Disk(basic::disk::Disk { radius: 1.0 });
                 &ELLIPSE
             }
-            // PAR,                                 */
-            /*ProjectionType::Par(_) => {
-                const PAR: ProjDefType = ProjDefType::Par(Par);
-                &PAR
-            }*/
-            // SFL,                                 */
-            /*ProjectionType::Sfl(_) => {
+            // PAR,      Parabolic                  */
+            ProjectionType::Par(_) => {
                 const PAR: ProjDefType = ProjDefType::Par(Par);
                 &PAR
-            }*/
+            }
+            // SFL,      Sanson-Flamsteed           */
+            ProjectionType::Sfl(_) => {
+                const SFL: ProjDefType = ProjDefType::Sfl(Sfl);
+                &SFL
+            }
 
             // Cylindrical projections
             // MER,      Mercator                   */
             ProjectionType::Mer(_) => {
                 const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
                 &FULL_SCREEN
-            } // CAR,                                 */
-              /*ProjectionType::Car(_) => {
-                  const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
-                  &FULL_SCREEN
-              }*/
-              // CEA,                                 */
-              /*ProjectionType::Cea(_) => {
-                  const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
-                  &FULL_SCREEN
-              }*/
-              // CYP,                                 */
-              /*ProjectionType::Cyp(_) => {
-                  const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
-                  &FULL_SCREEN
-              }*/
-
-              // Conic projections
-              // COD,                                 */
-              /*ProjectionType::Cod(_) => {
-                  const CONIC: ProjDefType = ProjDefType::Cod(Cod::new());
-                  &CONIC
-              }*/
-              // HEALPix hybrid projection
-              /*ProjectionType::Hpx(_) => {
-                  const HPX_DEF_REG: ProjDefType = ProjDefType::Hpx(Hpx);
-                  &HPX_DEF_REG
-              }*/
+            }
+            // CAR,      Plate carrée               */
+            ProjectionType::Car(_) => {
+                const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
+                &FULL_SCREEN
+            }
+            // CEA,      Cylindrical equal area     */
+            ProjectionType::Cea(_) => {
+                const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
+                &FULL_SCREEN
+            }
+            // CYP,      Cylindrical perspective    */
+            ProjectionType::Cyp(_) => {
+                const FULL_SCREEN: ProjDefType = ProjDefType::FullScreen(FullScreen);
+                &FULL_SCREEN
+            }
+
+            // Conic projections
+            // COD,      Conic equidistant          */
+            // The definition domain depends on the standard parallels
+            ProjectionType::Cod(cod) => &cod.area,
+
+            // HEALPix hybrid projection
+            ProjectionType::Hpx(_) => {
+                const HPX_DEF_REG: ProjDefType = ProjDefType::Hpx(Hpx);
+                &HPX_DEF_REG
+            }
+        }
+    }
+}
+
+/// A conic projection with its definition domain.
+///
+/// The conic projections are defined around the mean of their standard parallels.
+/// The rotation brings the center of the view onto it
+pub struct Conic {
+    proj: mapproj::conic::cod::Cod,
+    area: ProjDefType,
+    rotation: Rotation<f64>,
+}
+
+impl Conic {
+    /// Conic equidistant projection with its standard parallel at 45 deg
+    pub fn new() -> Self {
+        Self {
+            proj: mapproj::conic::cod::Cod::new(),
+            area: ProjDefType::Cod(Cod::new()),
+            rotation: Self::standard_parallel_rotation(HALF_PI * 0.5),
+        }
+    }
+
+    /// Conic equidistant projection
+    ///
+    /// # Arguments
+    ///
+    /// * `theta_a` - Mean of the two standard parallels in radians
+    /// * `eta` - Half of the angle between the two standard parallels in radians
+    pub fn from_params(theta_a: f64, eta: f64) -> Self {
+        Self {
+            proj: mapproj::conic::cod::Cod::from_params(theta_a, eta),
+            area: ProjDefType::Cod(Cod::from_params(theta_a, eta)),
+            rotation: Self::standard_parallel_rotation(theta_a),
         }
     }
+
+    fn standard_parallel_rotation(theta_a: f64) -> Rotation<f64> {
+        Rotation::from_sky_position(&LonLatT::new(0.0_f64.to_angle(), theta_a.to_angle()).vector())
+    }
+}
+
+impl Default for Conic {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Projection for ProjectionType {
@@ -536,46 +728,47 @@ impl Projection for ProjectionType {
             /* ZEA,	     Equal-area 		        */
             ProjectionType::Zea(zea) => zea.clip_to_world_space(xy),
             /* FEYE,     Fish-eyes                  */
-            //ProjectionType::Feye(feye) => feye.clip_to_world_space(xy),
-            /* AIR,                                 */
-            //ProjectionType::Air(air) => air.clip_to_world_space(xy),
-            //AZP: {fov: 180},
-            //Azp(mapproj::zenithal::azp::Azp),
-            /* ARC,                                 */
-            //ProjectionType::Arc(arc) => arc.clip_to_world_space(xy),
-            /* NCP,                                 */
-            //ProjectionType::Ncp(ncp) => ncp.clip_to_world_space(xy),
+            ProjectionType::Feye(feye) => feye.clip_to_world_space(xy),
+            /* AIR,      Airy                       */
+            ProjectionType::Air(air) => air.clip_to_world_space(xy),
+            /* AZP,      Zenithal perspective       */
+            ProjectionType::Azp(azp) => azp.clip_to_world_space(xy),
+            /* ARC,      Zenithal equidistant       */
+            ProjectionType::Arc(arc) => arc.clip_to_world_space(xy),
+            /* NCP,      North celestial pole       */
+            ProjectionType::Ncp(ncp) => ncp.clip_to_world_space(xy),
+            /* ZPN,      Zenithal polynomial        */
+            ProjectionType::Zpn(zpn) => zpn.clip_to_world_space(xy),
 
             // Pseudo-cylindrical projections
             /* AIT,      Aitoff                     */
             ProjectionType::Ait(ait) => ait.clip_to_world_space(xy),
             // MOL,      Mollweide                  */
             ProjectionType::Mol(mol) => mol.clip_to_world_space(xy),
-            // PAR,                                 */
-            //ProjectionType::Par(par) => par.clip_to_world_space(xy),
-            // SFL,                                 */
-            //ProjectionType::Sfl(sfl) => sfl.clip_to_world_space(xy),
+            // PAR,      Parabolic                  */
+            ProjectionType::Par(par) => par.clip_to_world_space(xy),
+            // SFL,      Sanson-Flamsteed           */
+            ProjectionType::Sfl(sfl) => sfl.clip_to_world_space(xy),
 
             // Cylindrical projections
             // MER,      Mercator                   */
             ProjectionType::Mer(mer) => mer.clip_to_world_space(xy),
-            // CAR,                                 */
-            //ProjectionType::Car(car) => car.clip_to_world_space(xy),
-            // CEA,                                 */
-            //ProjectionType::Cea(cea) => cea.clip_to_world_space(xy),
-            // CYP,                                 */
-            //ProjectionType::Cyp(cyp) => cyp.clip_to_world_space(xy),
+            // CAR,      Plate carrée               */
+            ProjectionType::Car(car) => car.clip_to_world_space(xy),
+            // CEA,      Cylindrical equal area     */
+            ProjectionType::Cea(cea) => cea.clip_to_world_space(xy),
+            // CYP,      Cylindrical perspective    */
+            ProjectionType::Cyp(cyp) => cyp.clip_to_world_space(xy),
 
             // Conic projections
-            // COD,                                 */
-            /*ProjectionType::Cod(cod) => cod.clip_to_world_space(xy).map(|xyzw| {
-                let rot = Rotation::from_sky_position(
-                    &LonLatT::new(0.0_f64.to_angle(), (HALF_PI * 0.5).to_angle()).vector(),
-                );
-                rot.inv_rotate(&xyzw)
-            }),*/
+            // COD,      Conic equidistant          */
+            ProjectionType::Cod(cod) => cod
+                .proj
+                .clip_to_world_space(xy)
+                .map(|xyzw| cod.rotation.inv_rotate(&xyzw)),
+
             // HEALPix hybrid projection
-            //ProjectionType::Hpx(hpx) => hpx.clip_to_world_space(xy),
+            ProjectionType::Hpx(hpx) => hpx.clip_to_world_space(xy),
         }
     }
 
@@ -592,46 +785,44 @@ impl Projection for ProjectionType {
             /* ZEA,	     Equal-area 		        */
             ProjectionType::Zea(zea) => zea.world_to_clip_space(xyzw),
             /* FEYE,     Fish-eyes                  */
-            //ProjectionType::Feye(feye) => feye.world_to_clip_space(xyzw),
-            /* AIR,                                 */
-            //ProjectionType::Air(air) => air.world_to_clip_space(xyzw),
-            //AZP: {fov: 180},
-            //Azp(mapproj::zenithal::azp::Azp),
-            /* ARC,                                 */
-            //ProjectionType::Arc(arc) => arc.world_to_clip_space(xyzw),
-            /* NCP,                                 */
-            //ProjectionType::Ncp(ncp) => ncp.world_to_clip_space(xyzw),
+            ProjectionType::Feye(feye) => feye.world_to_clip_space(xyzw),
+            /* AIR,      Airy                       */
+            ProjectionType::Air(air) => air.world_to_clip_space(xyzw),
+            /* AZP,      Zenithal perspective       */
+            ProjectionType::Azp(azp) => azp.world_to_clip_space(xyzw),
+            /* ARC,      Zenithal equidistant       */
+            ProjectionType::Arc(arc) => arc.world_to_clip_space(xyzw),
+            /* NCP,      North celestial pole       */
+            ProjectionType::Ncp(ncp) => ncp.world_to_clip_space(xyzw),
+            /* ZPN,      Zenithal polynomial        */
+            ProjectionType::Zpn(zpn) => zpn.world_to_clip_space(xyzw),
 
             // Pseudo-cylindrical projections
             /* AIT,      Aitoff                     */
             ProjectionType::Ait(ait) => ait.world_to_clip_space(xyzw),
             // MOL,      Mollweide                  */
             ProjectionType::Mol(mol) => mol.world_to_clip_space(xyzw),
-            // PAR,                                 */
-            //ProjectionType::Par(par) => par.world_to_clip_space(xyzw),
-            // SFL,                                 */
-            //ProjectionType::Sfl(sfl) => sfl.world_to_clip_space(xyzw),
+            // PAR,      Parabolic                  */
+            ProjectionType::Par(par) => par.world_to_clip_space(xyzw),
+            // SFL,      Sanson-Flamsteed           */
+            ProjectionType::Sfl(sfl) => sfl.world_to_clip_space(xyzw),
 
             // Cylindrical projections
             // MER,      Mercator                   */
             ProjectionType::Mer(mer) => mer.world_to_clip_space(xyzw),
-            // CAR,                                 */
-            //ProjectionType::Car(car) => car.world_to_clip_space(xyzw),
-            // CEA,                                 */
-            //ProjectionType::Cea(cea) => cea.world_to_clip_space(xyzw),
-            // CYP,                                 */
-            //ProjectionType::Cyp(cyp) => cyp.world_to_clip_space(xyzw),
+            // CAR,      Plate carrée               */
+            ProjectionType::Car(car) => car.world_to_clip_space(xyzw),
+            // CEA,      Cylindrical equal area     */
+            ProjectionType::Cea(cea) => cea.world_to_clip_space(xyzw),
+            // CYP,      Cylindrical perspective    */
+            ProjectionType::Cyp(cyp) => cyp.world_to_clip_space(xyzw),
+
             // Conic projections
-            // COD,                                 */
-            /*ProjectionType::Cod(cod) => {
-                // The Cod projection is centered on (0, 45 deg)
-                let rot = Rotation::from_sky_position(
-                    &LonLatT::new(0.0_f64.to_angle(), (HALF_PI * 0.5).to_angle()).vector(),
-                );
-                cod.world_to_clip_space(&rot.rotate(&xyzw))
-            }*/
+            // COD,      Conic equidistant          */
+            ProjectionType::Cod(cod) => cod.proj.world_to_clip_space(&cod.rotation.rotate(xyzw)),
+
             // HEALPix hybrid projection
-            //ProjectionType::Hpx(hpx) => hpx.world_to_clip_space(xyzw),
+            ProjectionType::Hpx(hpx) => hpx.world_to_clip_space(xyzw),
         }
     }
 }
@@ -654,7 +845,7 @@ pub trait Projection {
     fn world_to_clip_space(&self, pos_world_space: &XYZWWorld) -> Option<XYClip>;
 }
 
-use mapproj::ProjXY;
+use mapproj::{ProjBounds, ProjXY};
 
 use self::coo_space::XYNDC;
 
@@ -734,6 +925,7 @@ where
     }
 }
 
+#[cfg(test)]
 mod tests {
     #[test]
     fn generate_maps() {
@@ -787,31 +979,35 @@ mod tests {
             "./../img/zea.jpg",
             ProjectionType::Zea(mapproj::zenithal::zea::Zea),
         );
-        /*generate_projection_map(
+        generate_projection_map(
             "./../img/feye.png",
-            ProjectionType::Feye(mapproj::zenithal::feye::Feye),
+            ProjectionType::Feye(mapproj::zenithal::feye::Feye::new()),
         );
         generate_projection_map(
             "./../img/arc.png",
-            ProjectionType::Arc(mapproj::zenithal::arc::Arc),
+            ProjectionType::Arc(mapproj::zenithal::arc::Arc::new()),
         );
         generate_projection_map(
             "./../img/ncp.png",
-            ProjectionType::Ncp(mapproj::zenithal::ncp::Ncp),
+            ProjectionType::Ncp(mapproj::zenithal::ncp::Ncp::new()),
         );
         generate_projection_map(
             "./../img/air.png",
             ProjectionType::Air(mapproj::zenithal::air::Air::new()),
-        );*/
+        );
+        generate_projection_map(
+            "./../img/azp.png",
+            ProjectionType::Azp(mapproj::zenithal::azp::Azp::new()),
+        );
 
         // Cylindrical
         generate_projection_map(
             "./../img/mer.jpg",
             ProjectionType::Mer(mapproj::cylindrical::mer::Mer),
         );
-        /*generate_projection_map(
+        generate_projection_map(
             "./../img/car.png",
-            ProjectionType::Car(mapproj::cylindrical::car::Car),
+            ProjectionType::Car(mapproj::cylindrical::car::Car::new()),
         );
         generate_projection_map(
             "./../img/cea.png",
@@ -820,33 +1016,188 @@ mod tests {
         generate_projection_map(
             "./../img/cyp.png",
             ProjectionType::Cyp(mapproj::cylindrical::cyp::Cyp::new()),
-        );*/
+        );
         // Pseudo-cylindrical
         generate_projection_map(
             "./../img/ait.jpg",
             ProjectionType::Ait(mapproj::pseudocyl::ait::Ait),
         );
-        /*generate_projection_map(
-            "./../img/car.png",
-            ProjectionType::Par(mapproj::pseudocyl::par::Par),
+        generate_projection_map(
+            "./../img/par.png",
+            ProjectionType::Par(mapproj::pseudocyl::par::Par::new()),
         );
         generate_projection_map(
-            "./../img/cea.png",
-            ProjectionType::Sfl(mapproj::pseudocyl::sfl::Sfl),
-        );*/
+            "./../img/sfl.png",
+            ProjectionType::Sfl(mapproj::pseudocyl::sfl::Sfl::new()),
+        );
         generate_projection_map(
             "./../img/mol.jpg",
             ProjectionType::Mol(mapproj::pseudocyl::mol::Mol::new()),
         );
         // Conic
-        /*generate_projection_map(
+        generate_projection_map(
             "./../img/cod.png",
-            ProjectionType::Cod(mapproj::conic::cod::Cod::new()),
+            ProjectionType::Cod(super::Conic::new()),
         );
         // Hybrid
         generate_projection_map(
             "./../img/hpx.png",
-            ProjectionType::Hpx(mapproj::hybrid::hpx::Hpx),
-        );*/
+            ProjectionType::Hpx(mapproj::hybrid::hpx::Hpx::new()),
+        );
+    }
+
+    fn all_projections() -> Vec<(&'static str, super::ProjectionType)> {
+        use super::ProjectionType;
+
+        let mut mol = mapproj::pseudocyl::mol::Mol::new();
+        mol.set_n_iter(10);
+        mol.set_epsilon(1e-12);
+
+        vec![
+            // Zenithal
+            ("TAN", ProjectionType::Tan(mapproj::zenithal::tan::Tan::new())),
+            ("STG", ProjectionType::Stg(mapproj::zenithal::stg::Stg::new())),
+            ("SIN", ProjectionType::Sin(mapproj::zenithal::sin::Sin::new())),
+            ("ZEA", ProjectionType::Zea(mapproj::zenithal::zea::Zea::new())),
+            ("FEYE", ProjectionType::Feye(mapproj::zenithal::feye::Feye::new())),
+            ("AIR", ProjectionType::Air(mapproj::zenithal::air::Air::new())),
+            ("AZP", ProjectionType::Azp(mapproj::zenithal::azp::Azp::new())),
+            ("ARC", ProjectionType::Arc(mapproj::zenithal::arc::Arc::new())),
+            ("NCP", ProjectionType::Ncp(mapproj::zenithal::ncp::Ncp::new())),
+            (
+                "ZPN",
+                ProjectionType::Zpn(
+                    mapproj::zenithal::zpn::Zpn::from_params(vec![0.0, 1.0, 0.0, -0.05]).unwrap(),
+                ),
+            ),
+            // Pseudo-cylindrical
+            ("AIT", ProjectionType::Ait(mapproj::pseudocyl::ait::Ait::new())),
+            ("MOL", ProjectionType::Mol(mol)),
+            ("PAR", ProjectionType::Par(mapproj::pseudocyl::par::Par::new())),
+            ("SFL", ProjectionType::Sfl(mapproj::pseudocyl::sfl::Sfl::new())),
+            // Cylindrical
+            ("MER", ProjectionType::Mer(mapproj::cylindrical::mer::Mer::new())),
+            ("CAR", ProjectionType::Car(mapproj::cylindrical::car::Car::new())),
+            ("CEA", ProjectionType::Cea(mapproj::cylindrical::cea::Cea::new())),
+            ("CYP", ProjectionType::Cyp(mapproj::cylindrical::cyp::Cyp::new())),
+            // Conic
+            ("COD", ProjectionType::Cod(super::Conic::new())),
+            (
+                "COD(30, 10)",
+                ProjectionType::Cod(super::Conic::from_params(
+                    30.0_f64.to_radians(),
+                    10.0_f64.to_radians(),
+                )),
+            ),
+            // Hybrid
+            ("HPX", ProjectionType::Hpx(mapproj::hybrid::hpx::Hpx::new())),
+        ]
+    }
+
+    #[test]
+    fn world_clip_world_roundtrip() {
+        use super::Projection;
+        use crate::math::lonlat::LonLat;
+        use crate::ArcDeg;
+        use crate::LonLatT;
+        use cgmath::{InnerSpace, Vector4};
+
+        for (name, projection) in all_projections() {
+            let mut num_projected = 0;
+            // Sample the sky around the center of projection (lon = 0, lat = 0)
+            for lon in (-60..=60).step_by(10) {
+                for lat in (-60..=60).step_by(10) {
+                    let lonlat: LonLatT<f64> =
+                        LonLatT::new(ArcDeg(lon as f64).into(), ArcDeg(lat as f64).into());
+                    let xyzw: Vector4<f64> = lonlat.vector();
+
+                    if let Some(xy) = projection.world_to_clip_space(&xyzw) {
+                        num_projected += 1;
+
+                        let res = projection.clip_to_world_space(&xy).unwrap_or_else(|| {
+                            panic!("{}: ({}, {}) cannot be deprojected", name, lon, lat)
+                        });
+                        let d = (res.truncate() - xyzw.truncate()).magnitude();
+                        assert!(d < 1e-6, "{}: ({}, {}) distance {}", name, lon, lat, d);
+                    }
+                }
+            }
+
+            assert!(num_projected > 0, "{}: no position projected", name);
+        }
+    }
+
+    #[test]
+    fn clip_world_clip_roundtrip() {
+        use super::domain::sdf::ProjDef;
+        use super::Projection;
+        use cgmath::{InnerSpace, Vector2};
+
+        for (name, projection) in all_projections() {
+            let area = projection.get_area();
+            for i in -9..=9 {
+                for j in -9..=9 {
+                    let xy = Vector2::new((i as f64) * 0.1, (j as f64) * 0.1);
+                    // Avoid the frontier of the projection definition domain
+                    if area.sdf(&xy) > -1e-2 {
+                        continue;
+                    }
+
+                    if let Some(xyzw) = projection.clip_to_world_space(&xy) {
+                        let res = projection.world_to_clip_space(&xyzw).unwrap_or_else(|| {
+                            panic!("{}: ({}, {}) cannot be projected", name, xy.x, xy.y)
+                        });
+                        let d = (res - xy).magnitude();
+                        assert!(d < 1e-6, "{}: ({}, {}) distance {}", name, xy.x, xy.y, d);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_size_ratio() {
+        use super::ProjectionType;
+
+        // Values used before the ratio was derived from the projection bounds
+        assert_eq!(
+            ProjectionType::Tan(mapproj::zenithal::tan::Tan::new()).bounds_size_ratio(),
+            1.0
+        );
+        assert_eq!(
+            ProjectionType::Mer(mapproj::cylindrical::mer::Mer::new()).bounds_size_ratio(),
+            1.0
+        );
+        assert!(
+            (ProjectionType::Ait(mapproj::pseudocyl::ait::Ait::new()).bounds_size_ratio() - 2.0)
+                .abs()
+                < 1e-12
+        );
+        assert!(
+            (ProjectionType::Car(mapproj::cylindrical::car::Car::new()).bounds_size_ratio() - 2.0)
+                .abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn cod_domain_from_params() {
+        use super::domain::cod::Cod;
+
+        // The default domain is the one of a standard parallel at 45 deg
+        let def = Cod::new();
+        let cod = Cod::from_params(45.0_f64.to_radians(), 0.0);
+        for (a, b) in [
+            (def.r_min, cod.r_min),
+            (def.r_max, cod.r_max),
+            (def.c, cod.c),
+            (def.y0, cod.y0),
+            (def.x_min, cod.x_min),
+            (def.x_max, cod.x_max),
+            (def.y_min, cod.y_min),
+            (def.y_max, cod.y_max),
+        ] {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
     }
 }
@@ -85,6 +85,52 @@ impl BoundingBox {
         BoundingBox { lon, lat }
     }
 
+    /// Bounding box of points, e.g. the vertices of HEALPix cells
+    ///
+    /// Contrary to `from_polygon`, the longitude range is the smallest one containing
+    /// all the points, whatever its size. It starts at a negative longitude if it
+    /// crosses the 0deg meridian
+    pub fn from_points(
+        pole_contained: &PoleContained,
+        mut lon: Vec<f64>,
+        lat: &[f64],
+    ) -> Self {
+        if *pole_contained != PoleContained::None || lon.is_empty() {
+            return Self::from_polygon(pole_contained, lon, lat, false);
+        }
+
+        lon.iter_mut().for_each(|lon| *lon = lon.rem_euclid(TWICE_PI));
+        lon.sort_unstable_by(|a, b| a.total_cmp(b));
+
+        // The longitude range is the complement of the widest gap
+        // between two consecutive longitudes
+        let n = lon.len();
+        let mut widest_gap = (n - 1, lon[0] + TWICE_PI - lon[n - 1]);
+        for i in 0..(n - 1) {
+            let gap = lon[i + 1] - lon[i];
+            if gap > widest_gap.1 {
+                widest_gap = (i, gap);
+            }
+        }
+
+        let (i, _) = widest_gap;
+        let lon = if i == n - 1 {
+            lon[0]..lon[n - 1]
+        } else {
+            (lon[i + 1] - TWICE_PI)..lon[i]
+        };
+        let (min_lat, max_lat) = lat
+            .iter()
+            .fold((std::f64::MAX, std::f64::MIN), |(min, max), &b| {
+                (min.min(b), max.max(b))
+            });
+
+        BoundingBox {
+            lon,
+            lat: min_lat..max_lat,
+        }
+    }
+
     #[inline]
     pub fn get_lon_size(&self) -> f64 {
         self.lon.end - self.lon.start
@@ -40,6 +40,10 @@ pub struct Manager {
 
     catalogs: HashMap<String, Catalog>,
     kernel_size: Vector2<f32>,
+
+    // Epoch at which the sources are rendered
+    // If None, the sources are drawn at their catalog epoch
+    epoch: Option<Epoch>,
 }
 
 impl Manager {
@@ -173,6 +177,8 @@ impl Manager {
 
             catalogs,
             kernel_size,
+
+            epoch: None,
         };
 
         manager.set_kernel_size(camera);
@@ -184,14 +190,15 @@ impl Manager {
     pub fn add_catalog<P: Projection>(
         &mut self,
         name: String,
-        sources: Box<[LonLatT<f32>]>,
+        sources: Box<[Source]>,
+        epoch: Epoch,
         colormap: Colormap,
         camera: &mut CameraViewPort,
         proj: &ProjectionType,
     ) {
         // Create the HashMap storing the source indices with respect to the
         // HEALPix cell at depth 7 in which they are contained
-        let catalog = Catalog::new::<P>(&self.gl, colormap, sources);
+        let catalog = Catalog::new::<P>(&self.gl, colormap, sources, epoch);
 
         // Update the number of sources loaded
         //self.num_sources += num_instances_in_catalog as usize;
@@ -223,13 +230,23 @@ impl Manager {
         self.kernel_size = Vector2::new(32.0 / size.x, 32.0 / size.y);
     }
 
+    /// Set the epoch at which the sources are drawn.
+    /// Sources having a space motion are propagated to that epoch.
+    pub fn set_epoch(&mut self, epoch: Option<Epoch>) {
+        self.epoch = epoch;
+    }
+
+    pub fn get_epoch(&self) -> Option<Epoch> {
+        self.epoch
+    }
+
     pub fn get_mut_catalog(&mut self, name: &str) -> Result<&mut Catalog, Error> {
         self.catalogs.get_mut(name).ok_or(Error::CatalogNotPresent {
             message: format!("{} catalog is not present!", name),
         })
     }
 
-    pub fn update(&mut self, camera: &mut CameraViewPort) {
+    pub fn update(&mut self, camera: &mut CameraViewPort, projection: &ProjectionType) {
         // Render only the sources in the current field of view
         // Cells that are of depth > 7 are not handled by the hashmap (limited to depth 7)
         // For these cells, we draw all the sources lying in the ancestor cell of depth 7 containing
@@ -239,7 +256,7 @@ impl Manager {
             let cells = crate::healpix::cell::ALLSKY_HPX_CELLS_D0;
 
             for catalog in self.catalogs.values_mut() {
-                catalog.update(cells);
+                catalog.update(cells, self.epoch, camera, projection);
             }
         } else {
             let depth = camera.get_texture_depth().min(7);
@@ -249,7 +266,7 @@ impl Manager {
                 .collect();
 
             for catalog in self.catalogs.values_mut() {
-                catalog.update(&cells);
+                catalog.update(&cells, self.epoch, camera, projection);
             }
         }
     }
@@ -273,16 +290,90 @@ impl Manager {
     }
 }
 
+// Whether a vertex shader projects the sources of a catalog for this projection
+fn has_catalog_shader(projection: &ProjectionType) -> bool {
+    matches!(
+        projection,
+        ProjectionType::Sin(_)
+            | ProjectionType::Ait(_)
+            | ProjectionType::Mer(_)
+            | ProjectionType::Mol(_)
+            | ProjectionType::Arc(_)
+            | ProjectionType::Tan(_)
+            | ProjectionType::Hpx(_)
+    )
+}
+
+use crate::epoch::{Epoch, SpaceMotion};
 use crate::healpix::index_vector::IdxVec;
+use crate::math::angle::Angle;
+use crate::math::lonlat::{LonLat, LonLatEpochT};
 use crate::LonLatT;
 
+/// A catalog source given at the epoch of its catalog
+#[derive(Clone, Copy, Debug)]
+pub struct Source {
+    pub lonlat: LonLatT<f32>,
+    pub motion: SpaceMotion,
+}
+
+impl Source {
+    /// Position of the source at a specific epoch
+    fn lonlat_at(&self, from: Epoch, to: Epoch) -> LonLatT<f32> {
+        if self.motion.is_static() {
+            self.lonlat
+        } else {
+            let lonlat = LonLatT::new(
+                Angle(self.lonlat.lon().0 as f64),
+                Angle(self.lonlat.lat().0 as f64),
+            );
+            let pos = crate::epoch::propagate(&LonLatEpochT::new(lonlat, from), &self.motion, to);
+
+            LonLatT::new(
+                Angle(pos.lonlat.lon().0 as f32),
+                Angle(pos.lonlat.lat().0 as f32),
+            )
+        }
+    }
+}
+
+impl LonLat<f32> for Source {
+    #[inline]
+    fn lon(&self) -> Angle<f32> {
+        self.lonlat.lon()
+    }
+
+    #[inline]
+    fn lat(&self) -> Angle<f32> {
+        self.lonlat.lat()
+    }
+
+    #[inline]
+    fn lonlat(&self) -> LonLatT<f32> {
+        self.lonlat
+    }
+
+    #[inline]
+    fn from_lonlat(lonlat: &LonLatT<f32>) -> Self {
+        Self {
+            lonlat: *lonlat,
+            motion: SpaceMotion::default(),
+        }
+    }
+}
+
 pub struct Catalog {
     colormap: Colormap,
     num_instances: i32,
     index_vec: IdxVec,
     alpha: f32,
     strength: f32,
-    lonlat: Box<[LonLatT<f32>]>,
+    // Sources sorted by their HEALPix cell at the catalog epoch.
+    // Proper motions are small enough to keep that index valid
+    // for the epochs we render the catalog at
+    sources: Box<[Source]>,
+    // Epoch of the catalog positions
+    epoch: Epoch,
     vertex_array_object_catalog: VertexArrayObject,
 }
 use crate::healpix::cell::HEALPixCell;
@@ -297,12 +388,13 @@ impl Catalog {
     fn new<P: Projection>(
         gl: &WebGlContext,
         colormap: Colormap,
-        mut lonlat: Box<[LonLatT<f32>]>,
+        mut sources: Box<[Source]>,
+        epoch: Epoch,
     ) -> Catalog {
         let alpha = 1_f32;
         let strength = 1_f32;
-        let index_vec = IdxVec::from_coo(&mut lonlat);
-        let num_instances = lonlat.len() as i32;
+        let index_vec = IdxVec::from_coo(&mut sources);
+        let num_instances = sources.len() as i32;
 
         //let sources = unsafe { utils::transmute_boxed_slice(sources) };
 
@@ -391,7 +483,8 @@ impl Catalog {
             colormap,
             num_instances,
             index_vec,
-            lonlat,
+            sources,
+            epoch,
 
             vertex_array_object_catalog,
         }
@@ -421,7 +514,13 @@ impl Catalog {
     }
 
     // Cells are of depth <= 7
-    fn update(&mut self, cells: &[HEALPixCell]) {
+    fn update(
+        &mut self,
+        cells: &[HEALPixCell],
+        epoch: Option<Epoch>,
+        camera: &CameraViewPort,
+        projection: &ProjectionType,
+    ) {
         let num_sources_in_fov = self.get_total_num_sources_in_fov(cells) as f32;
         // reset the sources in the frame
         let mut sources: Vec<_> = vec![];
@@ -444,12 +543,46 @@ impl Catalog {
                         idx = idx.start..(idx.start + num_sources);
                     }
 
-                    sources.extend(&self.lonlat[idx]);
+                    let catalog_epoch = self.epoch;
+                    match epoch {
+                        Some(epoch) if epoch != catalog_epoch => sources.extend(
+                            self.sources[idx]
+                                .iter()
+                                .map(|s| s.lonlat_at(catalog_epoch, epoch)),
+                        ),
+                        _ => sources.extend(self.sources[idx].iter().map(|s| s.lonlat)),
+                    }
                 }
             }
         }
         self.num_instances = sources.len() as i32;
-        let sources = unsafe { utils::transmute_vec::<LonLatT<f32>, f32>(sources).unwrap() };
+        let sources = if has_catalog_shader(projection) {
+            unsafe { utils::transmute_vec::<LonLatT<f32>, f32>(sources).unwrap() }
+        } else {
+            // No vertex shader performs the projection, the centers are given in clip space.
+            // The third component tells whether the source is projected
+            sources
+                .iter()
+                .flat_map(|lonlat| {
+                    let lonlat = LonLatT::new(
+                        Angle(lonlat.lon().0 as f64),
+                        Angle(lonlat.lat().0 as f64),
+                    );
+                    let xyzw = crate::coosys::apply_coo_system(
+                        CooSystem::ICRS,
+                        camera.get_coo_system(),
+                        &lonlat.vector(),
+                        camera.get_horizontal_frame(),
+                    );
+
+                    if let Some(xy) = projection.model_to_clip_space(&xyzw, camera) {
+                        [xy.x as f32, xy.y as f32, 1.0]
+                    } else {
+                        [0.0, 0.0, 0.0]
+                    }
+                })
+                .collect()
+        };
 
         // Update the vertex buffer
         #[cfg(feature = "webgl1")]
@@ -483,28 +616,32 @@ impl Catalog {
                     gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
 
                     let shader = match projection {
-                        ProjectionType::Sin(_) => {
-                            crate::shader::get_shader(gl, shaders, "CatalogOrtVS", "CatalogOrtFS")
-                        }
+                        ProjectionType::Sin(_) => crate::shader::get_shader(
+                            gl,
+                            shaders,
+                            "CatalogOrthoVS",
+                            "CatalogOrthoFS",
+                        ),
                         ProjectionType::Ait(_) => {
-                            crate::shader::get_shader(gl, shaders, "CatalogAitVS", "CatalogFS")
+                            crate::shader::get_shader(gl, shaders, "CatalogAitoffVS", "CatalogFS")
                         }
                         ProjectionType::Mer(_) => {
-                            crate::shader::get_shader(gl, shaders, "CatalogMerVS", "CatalogFS")
+                            crate::shader::get_shader(gl, shaders, "CatalogMercatVS", "CatalogFS")
                         }
                         ProjectionType::Mol(_) => {
-                            crate::shader::get_shader(gl, shaders, "CatalogMolVS", "CatalogFS")
+                            crate::shader::get_shader(gl, shaders, "CatalogMollVS", "CatalogFS")
                         }
-                        /*ProjectionType::Arc(_) => {
+                        ProjectionType::Arc(_) => {
                             crate::shader::get_shader(gl, shaders, "CatalogArcVS", "CatalogFS")
-                        }*/
+                        }
                         ProjectionType::Tan(_) => {
                             crate::shader::get_shader(gl, shaders, "CatalogTanVS", "CatalogFS")
                         }
-                        /*ProjectionType::Hpx(_) => {
-                            crate::shader::get_shader(gl, shaders, "CatalogHpxVS", "CatalogFS")
-                        }*/
-                        _ => todo!(),
+                        ProjectionType::Hpx(_) => {
+                            crate::shader::get_shader(gl, shaders, "CatalogHEALPixVS", "CatalogFS")
+                        }
+                        // The sources have been projected on the CPU
+                        _ => crate::shader::get_shader(gl, shaders, "CatalogClipVS", "CatalogFS"),
                     }?;
                     let shader_bound = shader.bind(gl);
 
@@ -1,2 +1,2 @@
 mod manager;
-pub use manager::{Catalog, Manager};
+pub use manager::{Catalog, Manager, Source};
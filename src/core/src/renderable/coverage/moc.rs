@@ -303,8 +303,12 @@ impl MOCIntern {
                         let (lon, lat) = line_vertices[k];
 
                         let xyzw = crate::math::lonlat::radec_to_xyzw(Angle(lon), Angle(lat));
-                        let xyzw =
-                            crate::coosys::apply_coo_system(CooSystem::ICRS, camera_coosys, &xyzw);
+                        let xyzw = crate::coosys::apply_coo_system(
+                            CooSystem::ICRS,
+                            camera_coosys,
+                            &xyzw,
+                            camera.get_horizontal_frame(),
+                        );
 
                         if let Some(p) = proj.model_to_normalized_device_space(&xyzw, camera) {
                             if ndc.len() > 0 && crossing_edges_testing {
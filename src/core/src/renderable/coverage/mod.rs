@@ -1,7 +1,7 @@
 use crate::renderable::coverage::moc::MOC;
 use crate::{
     healpix::{cell::HEALPixCell, coverage::HEALPixCoverage, index_vector::IdxVec},
-    math::angle::Angle,
+    math::{angle::Angle, lonlat::LonLatT},
     CameraViewPort, ShaderManager,
 };
 mod graph;
@@ -28,8 +28,11 @@ pub struct MOCRenderer {
 
 use cgmath::Vector2;
 
-fn is_crossing_projection(
+/// Tell whether a cell defined in `frame` is cut by a discontinuity of the projection
+/// or is partially out of it
+pub(crate) fn is_crossing_projection(
     cell: &HEALPixCell,
+    frame: CooSystem,
     camera: &CameraViewPort,
     projection: &ProjectionType,
 ) -> bool {
@@ -38,8 +41,12 @@ fn is_crossing_projection(
         .iter()
         .filter_map(|(lon, lat)| {
             let xyzw = crate::math::lonlat::radec_to_xyzw(Angle(*lon), Angle(*lat));
-            let xyzw =
-                crate::coosys::apply_coo_system(CooSystem::ICRS, camera.get_coo_system(), &xyzw);
+            let xyzw = crate::coosys::apply_coo_system(
+                frame,
+                camera.get_coo_system(),
+                &xyzw,
+                camera.get_horizontal_frame(),
+            );
 
             projection
                 .model_to_normalized_device_space(&xyzw, camera)
@@ -78,8 +85,12 @@ pub fn rasterize_hpx_cell(
         .iter()
         .filter_map(|(lon, lat)| {
             let xyzw = crate::math::lonlat::radec_to_xyzw(Angle(*lon), Angle(*lat));
-            let xyzw =
-                crate::coosys::apply_coo_system(CooSystem::ICRS, camera.get_coo_system(), &xyzw);
+            let xyzw = crate::coosys::apply_coo_system(
+                CooSystem::ICRS,
+                camera.get_coo_system(),
+                &xyzw,
+                camera.get_horizontal_frame(),
+            );
 
             proj.model_to_normalized_device_space(&xyzw, camera)
                 .map(|v| [v.x as f32, v.y as f32])
@@ -233,6 +244,32 @@ impl MOCRenderer {
         }
     }
 
+    /// The shown MOCs containing an ICRS position, along with their cell containing it.
+    /// The MOCs are given from the one drawn last, i.e. on top of the others.
+    pub fn pick(&self, lonlat: &LonLatT<f64>) -> Vec<(&Cfg, HEALPixCell)> {
+        self.mocs
+            .iter()
+            .zip(self.cfgs.iter())
+            .rev()
+            .filter(|(_, cfg)| cfg.show)
+            .filter_map(|(hmoc, cfg)| {
+                hmoc.get_full_moc()
+                    .cell_containing(lonlat)
+                    .map(|cell| (cfg, cell))
+            })
+            .collect()
+    }
+
+    /// Replace the coverage drawn by a MOC, keeping its config
+    pub fn set_coverage(&mut self, cfg: &Cfg, moc: HEALPixCoverage) -> Option<()> {
+        let name = cfg.get_uuid();
+
+        let idx = self.cfgs.iter().position(|cfg| cfg.get_uuid() == name)?;
+        self.mocs[idx] = MOCHierarchy::from_full_res_moc(moc, &self.cfgs[idx]);
+
+        Some(())
+    }
+
     pub fn remove(
         &mut self,
         cfg: &Cfg,
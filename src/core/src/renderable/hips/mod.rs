@@ -628,6 +628,11 @@ impl HiPS {
         self.textures.set_format(&self.gl, ext)
     }
 
+    // Return whether the tiles of the slice were still in the GPU
+    pub fn set_cube_frame(&mut self, cube_frame: u32) -> Result<bool, JsValue> {
+        self.textures.set_cube_frame(&self.gl, cube_frame)
+    }
+
     /*pub fn get_fading_factor(&self) -> f32 {
         self.textures
             .start_time
@@ -657,7 +662,12 @@ impl HiPS {
         let camera_frame = camera.get_coo_system();
         let hips_frame = cfg.get_frame();
 
-        let pos = crate::coosys::apply_coo_system(camera_frame, hips_frame, &pos.vector());
+        let pos = crate::coosys::apply_coo_system(
+            camera_frame,
+            hips_frame,
+            &pos.vector(),
+            camera.get_horizontal_frame(),
+        );
 
         // Get the array of textures from that survey
         let tile_depth = camera.get_texture_depth().min(cfg.get_max_depth_texture());
@@ -821,8 +831,12 @@ impl HiPS {
                         let lat = lonlat.lat();
 
                         let xyzw = crate::math::lonlat::radec_to_xyzw(lon, lat);
-                        let xyzw =
-                            crate::coosys::apply_coo_system(hips_frame, selected_frame, &xyzw);
+                        let xyzw = crate::coosys::apply_coo_system(
+                            hips_frame,
+                            selected_frame,
+                            &xyzw,
+                            camera.get_horizontal_frame(),
+                        );
 
                         let ndc = projection
                             .model_to_normalized_device_space(&xyzw, camera)
@@ -1015,7 +1029,7 @@ impl HiPS {
         let selected_frame = camera.get_coo_system();
         let hips_cfg = self.textures.config();
         let hips_frame = hips_cfg.get_frame();
-        let c = selected_frame.to(hips_frame);
+        let c = selected_frame.to(hips_frame, camera.get_horizontal_frame().icrs_to_altaz());
 
         // Retrieve the model and inverse model matrix
         let w2v = c * (*camera.get_w2m());
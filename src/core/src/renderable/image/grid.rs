@@ -195,6 +195,7 @@ pub fn get_grid_vertices(
                         image_coo_sys,
                         camera.get_coo_system(),
                         &xyzw,
+                        camera.get_horizontal_frame(),
                     );
 
                     projection
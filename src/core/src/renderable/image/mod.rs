@@ -1,4 +1,5 @@
 pub mod grid;
+pub mod native_wcs;
 pub mod subdivide_texture;
 
 use std::cmp::Ordering;
@@ -27,12 +28,15 @@ use al_core::VecData;
 use al_core::WebGlContext;
 use al_core::{Texture2D, VertexArrayObject};
 
+use crate::altaz::HorizontalFrame;
 use crate::camera::CameraViewPort;
 use crate::math::lonlat::LonLat;
 use crate::Colormaps;
 use crate::ProjectionType;
 use crate::ShaderManager;
 
+use native_wcs::NativeWCS;
+
 use std::ops::Range;
 
 pub struct Image {
@@ -49,6 +53,8 @@ pub struct Image {
     /// Parameters extracted from the fits
     wcs: WCS,
     image_coo_sys: CooSystem,
+    /// The celestial WCS as it can be adopted by the view
+    native_wcs: Option<NativeWCS>,
     blank: f32,
     scale: f32,
     offset: f32,
@@ -104,6 +110,7 @@ impl Image {
         gl: &WebGlContext,
         hdu: &mut AsyncHDU<'a, BufReader<R>, extension::image::Image>,
         //reader: &'a mut BufReader<R>,
+        horizontal_frame: &HorizontalFrame,
     ) -> Result<Self, JsValue>
     where
         R: AsyncRead + Unpin + Debug + 'a,
@@ -138,10 +145,45 @@ impl Image {
         let wcs = WCS::new(&header)
             .map_err(|e| JsValue::from_str(&format!("WCS parsing error: reason: {}", e)))?;
 
-        let image_coo_sys = match wcs.coo_system() {
-            wcs::coo_system::CooSystem::GALACTIC => CooSystem::GAL,
-            _ => CooSystem::ICRS,
+        // The celestial axes give the family of the frame. For equatorial axes,
+        // RADESYS and EQUINOX tell which realization is used (see FITS WCS paper II, §3.1)
+        let get_card_str = |key: &[u8; 8]| -> Option<String> {
+            header
+                .get_parsed::<String>(key)
+                .and_then(|v| v.ok())
+                .map(|v| v.trim().to_uppercase())
         };
+        let ctype1 = get_card_str(b"CTYPE1  ").unwrap_or_default();
+        let image_coo_sys = if ctype1.starts_with("GLON") {
+            CooSystem::GAL
+        } else if ctype1.starts_with("ELON") {
+            CooSystem::ECL
+        } else if ctype1.starts_with("SLON") {
+            CooSystem::SGAL
+        } else if let wcs::coo_system::CooSystem::GALACTIC = wcs.coo_system() {
+            CooSystem::GAL
+        } else {
+            let equinox = header
+                .get_parsed::<f64>(b"EQUINOX ")
+                .or_else(|| header.get_parsed::<f64>(b"EPOCH   "))
+                .and_then(|v| v.ok());
+
+            match get_card_str(b"RADESYS ").as_deref() {
+                Some("FK4") | Some("FK4-NO-E") => CooSystem::FK4B1950,
+                Some("FK5") => CooSystem::FK5J2000,
+                Some("ECLIPTIC") => CooSystem::ECL,
+                Some("GALACTIC") => CooSystem::GAL,
+                Some("ICRS") => CooSystem::ICRS,
+                // RADESYS absent: the default is FK4 for EQUINOX < 1984, FK5 otherwise
+                _ => match equinox {
+                    Some(equinox) if equinox < 1984.0 => CooSystem::FK4B1950,
+                    Some(_) => CooSystem::FK5J2000,
+                    None => CooSystem::ICRS,
+                },
+            }
+        };
+
+        let native_wcs = NativeWCS::from_wcs(&wcs, header, image_coo_sys).ok();
 
         let (w, h) = wcs.img_dimensions();
         let width = w as f64;
@@ -361,8 +403,12 @@ impl Image {
         let center = {
             use crate::LonLatT;
             let center: LonLatT<_> = center.into();
-            let center =
-                crate::coosys::apply_coo_system(image_coo_sys, CooSystem::ICRS, &center.vector());
+            let center = crate::coosys::apply_coo_system(
+                image_coo_sys,
+                CooSystem::ICRS,
+                &center.vector(),
+                horizontal_frame,
+            );
             center.lonlat()
         };
 
@@ -388,6 +434,7 @@ impl Image {
             wcs,
             // CooSystem of the wcs, this should belong to the WCS
             image_coo_sys,
+            native_wcs,
             scale,
             offset,
             blank,
@@ -441,6 +488,7 @@ impl Image {
                     camera.get_coo_system(),
                     self.image_coo_sys,
                     vertex,
+                    camera.get_horizontal_frame(),
                 );
 
                 let lonlat = xyzw.lonlat();
@@ -631,4 +679,9 @@ impl Image {
     pub fn get_centered_fov(&self) -> &CenteredFoV {
         &self.centered_fov
     }
+
+    #[inline]
+    pub fn get_native_wcs(&self) -> Option<&NativeWCS> {
+        self.native_wcs.as_ref()
+    }
 }
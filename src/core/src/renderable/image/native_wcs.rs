@@ -0,0 +1,293 @@
+use al_api::coo_system::CooSystem;
+use al_api::projection::ProjectionParams;
+
+use fitsrs::hdu::header::{extension, Header};
+use wcs::{ImgXY, LonLat, WCS};
+
+use crate::math::angle::ToAngle;
+use crate::LonLatT;
+
+/// Number of PV2_m keywords (m = 0..=20) defined by the FITS WCS standard
+const NUM_PV: usize = 21;
+
+/// The celestial WCS of a FITS image as it can be adopted by the view:
+/// its projection, its reference point and its linear transformation
+#[derive(Debug, Clone)]
+pub struct NativeWCS {
+    /// Projection code, e.g. TAN, SIN, ZPN
+    proj: String,
+    /// Projection parameters given by the PV2_m keywords
+    params: ProjectionParams,
+    /// Reference point (CRVAL) in the image frame
+    crval: LonLatT<f64>,
+    coo_sys: CooSystem,
+    /// Linear transformation from pixel to the intermediate world coordinates
+    /// in degrees i.e. the CD matrix
+    cd: [[f64; 2]; 2],
+}
+
+/// The 8 bytes keyword of a header card, padded with spaces
+pub(crate) fn key(name: &str) -> [u8; 8] {
+    let mut k = [b' '; 8];
+    for (c, b) in k.iter_mut().zip(name.bytes()) {
+        *c = b;
+    }
+
+    k
+}
+
+impl NativeWCS {
+    /// Extract the celestial WCS of a FITS image.
+    ///
+    /// The projection and its reference point are read from the header. The linear transformation
+    /// is the one of the `wcs` already built from that header: it is measured around the reference
+    /// pixel, in the plane tangent to the sky at the reference point. This is exact for the zenithal
+    /// projections, whose scale is unity at their reference point
+    pub fn from_wcs(
+        wcs: &WCS,
+        header: &Header<extension::image::Image>,
+        coo_sys: CooSystem,
+    ) -> Result<Self, &'static str> {
+        let get_f64 = |name: &str| -> Option<f64> {
+            header.get_parsed::<f64>(&key(name)).and_then(|v| v.ok())
+        };
+
+        let ctype1 = header
+            .get_parsed::<String>(&key("CTYPE1"))
+            .and_then(|v| v.ok())
+            .ok_or("CTYPE1 keyword not found")?;
+        // CTYPE1 is of the form 'RA---TAN'
+        let proj = ctype1
+            .trim()
+            .get(5..8)
+            .ok_or("CTYPE1 does not contain a projection code")?
+            .trim_matches('-')
+            .to_uppercase();
+
+        let crval1 = get_f64("CRVAL1").unwrap_or(0.0).to_radians();
+        let crval2 = get_f64("CRVAL2").unwrap_or(0.0).to_radians();
+        let crval = LonLatT::new(crval1.to_angle(), crval2.to_angle());
+
+        let crpix = wcs
+            .proj(&LonLat::new(crval1, crval2))
+            .ok_or("The reference point cannot be projected onto the image")?;
+        // Gnomonic coordinates in degrees of the image position offset from the reference pixel
+        let tangent_plane_pos = |dx: f64, dy: f64| -> Result<[f64; 2], &'static str> {
+            let lonlat = wcs
+                .unproj(&ImgXY::new(crpix.x() + dx, crpix.y() + dy))
+                .ok_or("The pixels around the reference one cannot be unprojected")?;
+            let (s_dlon, c_dlon) = (lonlat.lon() - crval1).sin_cos();
+            let (s_lat, c_lat) = lonlat.lat().sin_cos();
+            let (s_lat0, c_lat0) = crval2.sin_cos();
+
+            let cos_dist = s_lat0 * s_lat + c_lat0 * c_lat * c_dlon;
+            Ok([
+                (c_lat * s_dlon / cos_dist).to_degrees(),
+                ((c_lat0 * s_lat - s_lat0 * c_lat * c_dlon) / cos_dist).to_degrees(),
+            ])
+        };
+        // Central differences along the two image axes
+        let [x1, y1] = tangent_plane_pos(0.5, 0.0)?;
+        let [x0, y0] = tangent_plane_pos(-0.5, 0.0)?;
+        let [x3, y3] = tangent_plane_pos(0.0, 0.5)?;
+        let [x2, y2] = tangent_plane_pos(0.0, -0.5)?;
+        let cd = [[x1 - x0, x3 - x2], [y1 - y0, y3 - y2]];
+
+        let mut pv = [None; NUM_PV];
+        for (m, pv_m) in pv.iter_mut().enumerate() {
+            *pv_m = get_f64(&format!("PV2_{}", m));
+        }
+
+        Ok(Self {
+            params: params_from_pv(&proj, &pv),
+            proj,
+            crval,
+            coo_sys,
+            cd,
+        })
+    }
+
+    pub fn get_projection_name(&self) -> &str {
+        &self.proj
+    }
+
+    pub fn get_projection_params(&self) -> &ProjectionParams {
+        &self.params
+    }
+
+    /// The reference point of the WCS in the frame of the image
+    pub fn get_crval(&self) -> &LonLatT<f64> {
+        &self.crval
+    }
+
+    pub fn get_coo_system(&self) -> CooSystem {
+        self.coo_sys
+    }
+
+    /// Size of a pixel in radians
+    pub fn pixel_scale(&self) -> f64 {
+        let det = self.cd[0][0] * self.cd[1][1] - self.cd[0][1] * self.cd[1][0];
+        det.abs().sqrt().to_radians()
+    }
+
+    /// A positive CD determinant means that the longitudes increase
+    /// towards the right of the image
+    pub fn is_longitude_reversed(&self) -> bool {
+        let det = self.cd[0][0] * self.cd[1][1] - self.cd[0][1] * self.cd[1][0];
+        det > 0.0
+    }
+
+    /// Angle in radians between the north and the y axis of the image,
+    /// counted counterclockwise
+    pub fn rotation(&self) -> f64 {
+        // The second column of the CD matrix gives the intermediate world
+        // coordinates of the image y axis
+        let (x, y) = (self.cd[0][1], self.cd[1][1]);
+        let x = if self.is_longitude_reversed() { -x } else { x };
+
+        (-x).atan2(y)
+    }
+}
+
+/// Give the meaning of the PV2_m keywords for a projection (see FITS WCS paper II)
+fn params_from_pv(proj: &str, pv: &[Option<f64>; NUM_PV]) -> ProjectionParams {
+    match proj {
+        "AZP" => ProjectionParams {
+            mu: pv[1],
+            gamma: pv[2],
+            ..Default::default()
+        },
+        "AIR" => ProjectionParams {
+            theta_b: pv[1],
+            ..Default::default()
+        },
+        "CYP" => ProjectionParams {
+            mu: pv[1],
+            lambda: pv[2],
+            ..Default::default()
+        },
+        "CEA" => ProjectionParams {
+            lambda: pv[1],
+            ..Default::default()
+        },
+        "COD" => ProjectionParams {
+            theta_a: pv[1],
+            eta: pv[2],
+            ..Default::default()
+        },
+        "ZPN" => {
+            // Undefined coefficients are null, the polynomial stops at the last one given
+            let degree = pv.iter().rposition(|p| p.is_some());
+            ProjectionParams {
+                coeffs: degree.map(|d| pv[..=d].iter().map(|p| p.unwrap_or(0.0)).collect()),
+                ..Default::default()
+            }
+        }
+        _ => ProjectionParams::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NativeWCS;
+    use al_api::coo_system::CooSystem;
+    use fitsrs::fits::Fits;
+    use std::io::Cursor;
+    use wcs::WCS;
+
+    // The WCS of a 1x1 image whose header contains the given cards
+    // in addition to its reference point
+    fn wcs_from_cards(cards: &[&str]) -> NativeWCS {
+        let mut bytes = vec![];
+        for card in [
+            "SIMPLE  = T",
+            "BITPIX  = 8",
+            "NAXIS   = 2",
+            "NAXIS1  = 1",
+            "NAXIS2  = 1",
+            "CTYPE1  = 'RA---TAN'",
+            "CTYPE2  = 'DEC--TAN'",
+            "CRPIX1  = 1.0",
+            "CRPIX2  = 1.0",
+            "CRVAL1  = 83.6",
+            "CRVAL2  = 22.0",
+        ]
+        .iter()
+        .chain(cards)
+        .chain(&["END"])
+        {
+            bytes.extend(format!("{:<80}", card).bytes());
+        }
+        // The header block followed by the data block
+        bytes.resize(2880, b' ');
+        bytes.resize(2 * 2880, 0);
+
+        let mut reader = Cursor::new(&bytes[..]);
+        let Fits { hdu } = Fits::from_reader(&mut reader).unwrap();
+        let header = hdu.get_header();
+        let wcs = WCS::new(header).unwrap();
+        NativeWCS::from_wcs(&wcs, header, CooSystem::ICRS).unwrap()
+    }
+
+    fn assert_cd(wcs: &NativeWCS, cd: [[f64; 2]; 2]) {
+        for (row, expected_row) in wcs.cd.iter().zip(cd.iter()) {
+            for (v, expected) in row.iter().zip(expected_row.iter()) {
+                assert!((v - expected).abs() < 1e-12, "{:?} != {:?}", wcs.cd, cd);
+            }
+        }
+    }
+
+    #[test]
+    fn pc_matrix() {
+        // PCi_j takes precedence over CROTA2 and CDi_j
+        let wcs = wcs_from_cards(&[
+            "CDELT1  = -0.5",
+            "CDELT2  = 0.25",
+            "PC1_2   = 1.0",
+            "PC2_1   = -1.0",
+            "CROTA2  = 30.0",
+            "CD1_1   = 3.0",
+        ]);
+        assert_cd(&wcs, [[-0.5, -0.5], [-0.25, 0.25]]);
+    }
+
+    #[test]
+    fn cd_matrix() {
+        let wcs = wcs_from_cards(&[
+            "CDELT1  = -0.5",
+            "CROTA2  = 30.0",
+            "CD1_1   = -0.001",
+            "CD2_2   = 0.001",
+        ]);
+        assert_cd(&wcs, [[-0.001, 0.0], [0.0, 0.001]]);
+        assert!(!wcs.is_longitude_reversed());
+    }
+
+    #[test]
+    fn crota2() {
+        let wcs = wcs_from_cards(&["CDELT1  = -0.001", "CDELT2  = 0.001", "CROTA2  = 90.0"]);
+        assert_cd(&wcs, [[0.0, -0.001], [-0.001, 0.0]]);
+        assert!((wcs.rotation().to_degrees() - 90.0).abs() < 1e-9);
+
+        let wcs = wcs_from_cards(&["CDELT1  = -0.001", "CDELT2  = 0.001", "CROTA2  = -30.0"]);
+        assert!((wcs.rotation().to_degrees() + 30.0).abs() < 1e-9);
+
+        // without rotation
+        let wcs = wcs_from_cards(&["CDELT1  = -0.001", "CDELT2  = 0.001"]);
+        assert_cd(&wcs, [[-0.001, 0.0], [0.0, 0.001]]);
+    }
+
+    #[test]
+    fn zpn_params() {
+        let mut pv = [None; super::NUM_PV];
+        pv[1] = Some(1.0);
+        pv[3] = Some(-0.05);
+
+        let params = super::params_from_pv("ZPN", &pv);
+        assert_eq!(params.coeffs, Some(vec![0.0, 1.0, 0.0, -0.05]));
+
+        let params = super::params_from_pv("COD", &pv);
+        assert_eq!(params.theta_a, Some(1.0));
+        assert_eq!(params.eta, None);
+    }
+}
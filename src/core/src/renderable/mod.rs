@@ -5,6 +5,7 @@ pub mod hips;
 pub mod image;
 pub mod line;
 pub mod text;
+pub mod tissot;
 pub mod utils;
 
 use crate::renderable::image::Image;
@@ -16,7 +17,10 @@ pub use hips::HiPS;
 pub use catalog::Manager;
 
 use al_api::color::ColorRGB;
+use al_api::fov::CenteredFoV;
 use al_api::hips::HiPSCfg;
+use al_api::hips::HiPSProperties;
+use al_api::hips::ImageExt;
 use al_api::hips::ImageMetadata;
 use al_api::image::ImageParams;
 
@@ -28,6 +32,7 @@ use al_core::VertexArrayObject;
 use al_core::WebGlContext;
 
 use crate::camera::CameraViewPort;
+use crate::healpix::map::HEALPixMap;
 use crate::shader::ShaderId;
 use crate::Abort;
 use crate::ProjectionType;
@@ -112,6 +117,54 @@ impl ImageCfg {
     }
 }
 
+/// Max order of the tile pixels of the HiPS generated from a HEALPix map
+const HEALPIX_MAP_MAX_TILE_DEPTH: u8 = 9;
+
+/// A HEALPix map displayed as a HiPS whose tiles are generated from the map
+pub struct HEALPixMapCfg {
+    /// Layer name
+    pub layer: String,
+    pub url: String,
+    pub map: HEALPixMap,
+    /// Its color
+    pub meta: ImageMetadata,
+}
+
+impl HEALPixMapCfg {
+    pub fn get_params(&self) -> ImageParams {
+        let cuts = self.map.cuts();
+
+        ImageParams {
+            layer: self.layer.clone(),
+            url: self.url.clone(),
+            // A map covers the whole sky
+            centered_fov: CenteredFoV {
+                ra: 0.0,
+                dec: 0.0,
+                fov: 180.0,
+            },
+            automatic_min_cut: cuts.start,
+            automatic_max_cut: cuts.end,
+        }
+    }
+
+    /// Properties of the HiPS generated from the map
+    pub fn get_properties(&self) -> HiPSProperties {
+        let depth = self.map.depth();
+        let tile_depth = depth.min(HEALPIX_MAP_MAX_TILE_DEPTH);
+
+        HiPSProperties::new(
+            self.url.clone(),
+            format!("healpixmap://{}", self.url),
+            depth - tile_depth,
+            1 << tile_depth,
+            self.map.frame(),
+            vec![ImageExt::Fits],
+            Some(-32),
+        )
+    }
+}
+
 impl Layers {
     pub fn new(gl: &WebGlContext, projection: &ProjectionType) -> Result<Self, JsValue> {
         let surveys = HashMap::new();
@@ -637,7 +690,7 @@ impl Layers {
         self.surveys.get_mut(cdid)
     }
 
-    pub fn get_hips_from_cdid(&mut self, cdid: &str) -> Option<&HiPS> {
+    pub fn get_hips_from_cdid(&self, cdid: &str) -> Option<&HiPS> {
         self.surveys.get(cdid)
     }
 
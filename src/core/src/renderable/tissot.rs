@@ -0,0 +1,141 @@
+use crate::camera::CameraViewPort;
+use crate::math::projection::screen_to_ndc_space;
+use crate::renderable::line;
+use crate::renderable::line::PathVertices;
+use crate::renderable::line::RasterizedLineRenderer;
+use crate::ProjectionType;
+
+use al_api::color::ColorRGBA;
+use al_api::tissot::TissotCfg;
+
+use cgmath::Vector2;
+
+/// Number of vertices used to draw one ellipse
+const NUM_VERTICES_ELLIPSE: usize = 32;
+
+/// Overlay drawing the Tissot indicatrices of the current projection
+/// on a regular grid of screen positions
+pub struct TissotOverlay {
+    pub color: ColorRGBA,
+    pub enabled: bool,
+    thickness: f32,
+    // Number of ellipses along the width of the view
+    num: u32,
+
+    line_style: line::Style,
+}
+
+impl TissotOverlay {
+    pub fn new() -> Self {
+        let color = ColorRGBA {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.7,
+        };
+
+        TissotOverlay {
+            color,
+            enabled: false,
+            thickness: 2.0,
+            num: 10,
+            line_style: line::Style::None,
+        }
+    }
+
+    pub fn set_cfg(&mut self, new_cfg: TissotCfg) {
+        let TissotCfg {
+            color,
+            thickness,
+            opacity,
+            enabled,
+            num,
+        } = new_cfg;
+
+        if let Some(color) = color {
+            self.color = ColorRGBA {
+                r: color.r,
+                g: color.g,
+                b: color.b,
+                a: self.color.a,
+            };
+        }
+
+        if let Some(opacity) = opacity {
+            self.color.a = opacity;
+        }
+
+        if let Some(thickness) = thickness {
+            self.thickness = thickness;
+        }
+
+        if let Some(enabled) = enabled {
+            self.enabled = enabled;
+        }
+
+        if let Some(num) = num {
+            self.num = num.max(1);
+        }
+    }
+
+    pub fn draw(
+        &self,
+        camera: &CameraViewPort,
+        projection: &ProjectionType,
+        rasterizer: &mut RasterizedLineRenderer,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        // Size of the view in css pixels
+        let dpi = camera.get_dpi() as f64;
+        let (width, height) = (
+            camera.get_width() as f64 / dpi,
+            camera.get_height() as f64 / dpi,
+        );
+        let cell_size = width / (self.num as f64);
+        let num_rows = (height / cell_size).ceil() as u32;
+        // Radius of the ellipses at the center of the projection
+        let radius = 0.3 * cell_size;
+
+        let ellipses = (0..num_rows)
+            .flat_map(|j| (0..self.num).map(move |i| (i, j)))
+            .filter_map(|(i, j)| {
+                let center = Vector2::new(
+                    (i as f64 + 0.5) * cell_size,
+                    (j as f64 + 0.5) * cell_size,
+                );
+                let tissot = projection.distortion_at(&center, camera)?.tissot;
+
+                let a = tissot.semi_major * radius;
+                let b = tissot.semi_minor * radius;
+                // Too stretched ellipses would overlap their neighbours
+                if a > cell_size {
+                    return None;
+                }
+
+                // Major and minor axes directions in the screen space whose y axis points downwards
+                let (s, c) = tissot.angle.sin_cos();
+                let u = Vector2::new(c, -s);
+                let v = Vector2::new(s, c);
+
+                let vertices = (0..NUM_VERTICES_ELLIPSE)
+                    .map(|k| {
+                        let t = (k as f64) * std::f64::consts::TAU / (NUM_VERTICES_ELLIPSE as f64);
+                        let p = center + u * (a * t.cos()) + v * (b * t.sin());
+                        let ndc = screen_to_ndc_space(&p, camera);
+
+                        [ndc.x as f32, ndc.y as f32]
+                    })
+                    .collect::<Vec<_>>();
+
+                Some(PathVertices {
+                    vertices,
+                    closed: true,
+                })
+            });
+
+        rasterizer.add_stroke_paths(ellipses, self.thickness, &self.color, &self.line_style);
+    }
+}
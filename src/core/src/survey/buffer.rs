@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::rc::Rc;
 
 use al_core::image::format::ChannelType;
@@ -124,6 +125,53 @@ impl HEALPixCellHeap {
     }
 }
 
+// The textures of a HiPS cube slice kept in the GPU
+// while another slice is displayed
+struct CubeFrameTextures {
+    heap: HEALPixCellHeap,
+    textures: HashMap<HEALPixCell, Texture>,
+    texture_2d_array: Texture2DArray,
+}
+
+// Number of HiPS cube slices kept in the GPU besides the displayed one
+//
+// Each slice kept holds its own texture array, e.g. 4096x4096 pixels for 512 pixels tiles.
+// A HiPS cube can then use up to 1 + NUM_CUBE_FRAMES_KEPT = 4 times the GPU memory of a plain HiPS
+const NUM_CUBE_FRAMES_KEPT: usize = 3;
+
+// The slices of a HiPS cube displayed last, the most recent first
+struct KeptCubeFrames<T> {
+    frames: VecDeque<(u32, T)>,
+}
+
+impl<T> KeptCubeFrames<T> {
+    fn new() -> Self {
+        Self {
+            frames: VecDeque::new(),
+        }
+    }
+
+    // Take the textures of a slice back if they were kept
+    fn take(&mut self, cube_frame: u32) -> Option<T> {
+        self.frames
+            .iter()
+            .position(|(frame, _)| *frame == cube_frame)
+            .and_then(|idx| self.frames.remove(idx))
+            .map(|(_, textures)| textures)
+    }
+
+    // Keep the textures of the slice left, the oldest slice being dropped
+    // once more than NUM_CUBE_FRAMES_KEPT are kept
+    fn keep(&mut self, cube_frame: u32, textures: T) {
+        self.frames.push_front((cube_frame, textures));
+        self.frames.truncate(NUM_CUBE_FRAMES_KEPT);
+    }
+
+    fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
 // Fixed sized binary heap
 pub struct ImageSurveyTextures {
     // Some information about the HiPS
@@ -148,6 +196,9 @@ pub struct ImageSurveyTextures {
     available_tiles_during_frame: bool,
     //num_base_textures: usize,
     //exec: Rc<RefCell<TaskExecutor>>,
+
+    // The slices of a HiPS cube displayed last
+    cube_frames: KeptCubeFrames<CubeFrameTextures>,
 }
 
 // Define a set of textures compatible with the HEALPix tile format and size
@@ -167,6 +218,26 @@ fn create_texture_array<F: ImageFormat>(
     )
 }
 
+fn new_texture_array(gl: &WebGlContext, config: &HiPSConfig) -> Result<Texture2DArray, JsValue> {
+    let channel = config.get_format().get_channel();
+
+    match channel {
+        ChannelType::RGBA32F => unimplemented!(),
+        ChannelType::RGB32F => unimplemented!(),
+        ChannelType::RGBA8U => create_texture_array::<RGBA8U>(gl, config),
+        ChannelType::RGB8U => create_texture_array::<RGB8U>(gl, config),
+        ChannelType::R32F => create_texture_array::<R32F>(gl, config),
+        #[cfg(feature = "webgl2")]
+        ChannelType::R8UI => create_texture_array::<R8UI>(gl, config),
+        #[cfg(feature = "webgl2")]
+        ChannelType::R16I => create_texture_array::<R16I>(gl, config),
+        #[cfg(feature = "webgl2")]
+        ChannelType::R32I => create_texture_array::<R32I>(gl, config),
+        #[cfg(feature = "webgl2")]
+        ChannelType::R64F => create_texture_array::<R64F>(gl, config),
+    }
+}
+
 impl ImageSurveyTextures {
     pub fn new(gl: &WebGlContext, config: HiPSConfig) -> Result<ImageSurveyTextures, JsValue> {
         let size = config.num_textures();
@@ -191,23 +262,7 @@ impl ImageSurveyTextures {
             Texture::new(&HEALPixCell(0, 10), 10, now),
             Texture::new(&HEALPixCell(0, 11), 11, now),
         ];*/
-        let channel = config.get_format().get_channel();
-
-        let texture_2d_array = match channel {
-            ChannelType::RGBA32F => unimplemented!(),
-            ChannelType::RGB32F => unimplemented!(),
-            ChannelType::RGBA8U => create_texture_array::<RGBA8U>(gl, &config)?,
-            ChannelType::RGB8U => create_texture_array::<RGB8U>(gl, &config)?,
-            ChannelType::R32F => create_texture_array::<R32F>(gl, &config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R8UI => create_texture_array::<R8UI>(gl, &config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R16I => create_texture_array::<R16I>(gl, &config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R32I => create_texture_array::<R32I>(gl, &config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R64F => create_texture_array::<R64F>(gl, &config)?,
-        };
+        let texture_2d_array = new_texture_array(gl, &config)?;
         // The root textures have not been loaded
         //let ready = false;
         //let num_root_textures_available = 0;
@@ -228,29 +283,15 @@ impl ImageSurveyTextures {
 
             //ready,
             start_time,
+
+            cube_frames: KeptCubeFrames::new(),
         })
     }
 
     pub fn set_format(&mut self, gl: &WebGlContext, ext: ImageExt) -> Result<(), JsValue> {
         self.config.set_image_fmt(ext)?;
 
-        let channel = self.config.get_format().get_channel();
-
-        self.texture_2d_array = match channel {
-            ChannelType::RGBA32F => unimplemented!(),
-            ChannelType::RGB32F => unimplemented!(),
-            ChannelType::RGBA8U => create_texture_array::<RGBA8U>(gl, &self.config)?,
-            ChannelType::RGB8U => create_texture_array::<RGB8U>(gl, &self.config)?,
-            ChannelType::R32F => create_texture_array::<R32F>(gl, &self.config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R8UI => create_texture_array::<R8UI>(gl, &self.config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R16I => create_texture_array::<R16I>(gl, &self.config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R32I => create_texture_array::<R32I>(gl, &self.config)?,
-            #[cfg(feature = "webgl2")]
-            ChannelType::R64F => create_texture_array::<R64F>(gl, &self.config)?,
-        };
+        self.texture_2d_array = new_texture_array(gl, &self.config)?;
 
         let now = Time::now();
         /*self.base_textures = [
@@ -275,9 +316,53 @@ impl ImageSurveyTextures {
         self.available_tiles_during_frame = false;
         self.start_time = None;
 
+        // The slices kept are in the former format
+        self.cube_frames.clear();
+
         Ok(())
     }
 
+    // Display another slice of a HiPS cube
+    // The textures of the slice left are kept in the GPU so that going back to it
+    // does not need to download its tiles again.
+    // Return whether the textures of the new slice were kept
+    pub fn set_cube_frame(&mut self, gl: &WebGlContext, cube_frame: u32) -> Result<bool, JsValue> {
+        let cur_cube_frame = self.config.get_cube_frame();
+        if cur_cube_frame == cube_frame {
+            return Ok(true);
+        }
+
+        self.config.set_cube_frame(cube_frame)?;
+
+        let kept = self.cube_frames.take(cube_frame);
+        let is_kept = kept.is_some();
+
+        let (heap, textures, texture_2d_array) = if let Some(frame) = kept {
+            (frame.heap, frame.textures, frame.texture_2d_array)
+        } else {
+            (
+                HEALPixCellHeap::with_capacity(self.size),
+                HashMap::with_capacity(self.size),
+                new_texture_array(gl, &self.config)?,
+            )
+        };
+
+        // The oldest slices are removed from the GPU
+        self.cube_frames.keep(
+            cur_cube_frame,
+            CubeFrameTextures {
+                heap: std::mem::replace(&mut self.heap, heap),
+                textures: std::mem::replace(&mut self.textures, textures),
+                texture_2d_array: std::mem::replace(&mut self.texture_2d_array, texture_2d_array),
+            },
+        );
+
+        // Ask for a redraw with the textures of the new slice
+        self.available_tiles_during_frame = true;
+
+        Ok(is_kept)
+    }
+
     pub fn push_allsky(&mut self, allsky: Allsky) -> Result<(), JsValue> {
         let Allsky {
             image,
@@ -708,3 +793,36 @@ impl Drop for ImageSurveyTextures {
         self.textures.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{KeptCubeFrames, NUM_CUBE_FRAMES_KEPT};
+
+    #[test]
+    fn kept_cube_frames() {
+        // the textures are stood for by the index of their slice
+        let mut kept = KeptCubeFrames::new();
+        for cube_frame in 0..5 {
+            kept.keep(cube_frame, cube_frame);
+        }
+        assert_eq!(kept.frames.len(), NUM_CUBE_FRAMES_KEPT);
+
+        // the oldest slices have been dropped
+        assert_eq!(kept.take(0), None);
+        assert_eq!(kept.take(1), None);
+        // a slice taken back is not kept anymore
+        assert_eq!(kept.take(3), Some(3));
+        assert_eq!(kept.take(3), None);
+
+        // a slice displayed again becomes the most recent one
+        kept.keep(3, 3);
+        kept.keep(5, 5);
+        kept.keep(6, 6);
+        assert_eq!(kept.take(2), None);
+        assert_eq!(kept.take(4), None);
+        assert_eq!(kept.take(3), Some(3));
+
+        kept.clear();
+        assert_eq!(kept.take(5), None);
+    }
+}
@@ -1,4 +1,4 @@
-use al_api::hips::ImageExt;
+use al_api::hips::{CubeAxis, ImageExt};
 use al_core::log::console_log;
 use al_core::{image::format::ImageFormat, image::raw::ImageBuffer};
 
@@ -132,7 +132,8 @@ fn create_black_tile(format: FormatImageType, width: i32, value: f32) -> TileArr
 
 #[derive(Debug)]
 pub struct HiPSConfig {
-    pub root_url: String,
+    // The urls of the HiPS service, the tiles are fetched from the current one
+    mirrors: Mirrors,
     // HiPS image format
     // TODO: Make that independant of the HiPS but of the ImageFormat
     pub empty_image: EmptyTileImage,
@@ -170,11 +171,16 @@ pub struct HiPSConfig {
     pub frame: CooSystem,
     pub bitpix: Option<i32>,
     format: ImageFormatType,
+    // The number of slices of a HiPS cube and the one currently displayed
+    cube_depth: Option<u32>,
+    cube_frame: u32,
+    cube_axis: Option<CubeAxis>,
     //dataproduct_subtype: Option<Vec<String>>,
     //colored: bool,
     pub creator_did: String,
 }
 
+use super::mirrors::Mirrors;
 use crate::math;
 use crate::HiPSProperties;
 use al_api::coo_system::CooSystem;
@@ -192,7 +198,7 @@ impl HiPSConfig {
     /// * `properties` - A description of the HiPS, its metadata, available formats  etc...
     /// * `img_format` - Image format wanted by the user
     pub fn new(properties: &HiPSProperties, img_ext: ImageExt) -> Result<HiPSConfig, JsValue> {
-        let root_url = properties.get_url();
+        let mirrors = Mirrors::new(properties.get_url().to_string(), properties.get_mirrors());
         let creator_did = properties.get_creator_did().to_string();
         // Define the size of the 2d texture array depending on the
         // characterics of the client
@@ -302,6 +308,10 @@ impl HiPSConfig {
 
         let is_allsky = sky_fraction >= 1.0;
 
+        let cube_depth = properties.get_cube_depth();
+        let cube_frame = properties.get_cube_first_frame().unwrap_or(0);
+        let cube_axis = properties.get_cube_axis();
+
         let min_depth_tile = properties.get_min_order().unwrap_or(0);
         let min_depth_texture = if min_depth_tile >= delta_depth {
             min_depth_tile - delta_depth
@@ -311,7 +321,7 @@ impl HiPSConfig {
         let hips_config = HiPSConfig {
             creator_did,
             // HiPS name
-            root_url: root_url.to_string(),
+            mirrors,
             // Tile size & blank tile data
             empty_image,
             // Texture config
@@ -344,6 +354,9 @@ impl HiPSConfig {
             bitpix,
             format,
             tile_size,
+            cube_depth,
+            cube_frame,
+            cube_axis,
             //dataproduct_subtype,
             //colored,
         };
@@ -439,12 +452,37 @@ impl HiPSConfig {
 
     #[inline(always)]
     pub fn get_root_url(&self) -> &str {
-        &self.root_url
+        self.mirrors.get_url()
     }
 
     #[inline(always)]
     pub fn set_root_url(&mut self, root_url: String) {
-        self.root_url = root_url;
+        self.mirrors.set_url(root_url);
+    }
+
+    #[inline(always)]
+    pub fn get_mirrors(&self) -> &Mirrors {
+        &self.mirrors
+    }
+
+    #[inline(always)]
+    pub fn get_mirrors_mut(&mut self) -> &mut Mirrors {
+        &mut self.mirrors
+    }
+
+    /// Set the slice of a HiPS cube to display
+    pub fn set_cube_frame(&mut self, cube_frame: u32) -> Result<(), JsValue> {
+        match self.cube_depth {
+            Some(cube_depth) if cube_frame < cube_depth => {
+                self.cube_frame = cube_frame;
+                Ok(())
+            }
+            Some(cube_depth) => Err(JsValue::from_str(&format!(
+                "Slice {} out of the HiPS cube of depth {}",
+                cube_frame, cube_depth
+            ))),
+            None => Err(JsValue::from_str("The HiPS is not a cube")),
+        }
     }
 
     #[inline(always)]
@@ -530,6 +568,21 @@ impl HiPSConfig {
         self.format
     }
 
+    #[inline(always)]
+    pub fn get_cube_depth(&self) -> Option<u32> {
+        self.cube_depth
+    }
+
+    #[inline(always)]
+    pub fn get_cube_frame(&self) -> u32 {
+        self.cube_frame
+    }
+
+    #[inline(always)]
+    pub fn get_cube_axis(&self) -> Option<&CubeAxis> {
+        self.cube_axis.as_ref()
+    }
+
     #[inline(always)]
     pub fn is_colored(&self) -> bool {
         self.format.is_colored()
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use crate::downloader::query::QueryId;
+use crate::downloader::retry::FetchError;
+use crate::renderable::Url;
+
+/// Number of consecutive failed tile requests after which another mirror is used
+const MAX_NUM_FAILURES: u32 = 5;
+
+/// The mirrors of a HiPS service
+///
+/// The tiles are fetched from the current mirror. It is changed for the next one
+/// once too many tile requests in a row failed after having been retried.
+#[derive(Debug)]
+pub struct Mirrors {
+    urls: Vec<Url>,
+    cur: usize,
+    // Consecutive failures on the current mirror
+    num_failures: u32,
+    // The tiles not found on the service
+    not_found: HashSet<QueryId>,
+}
+
+impl Mirrors {
+    /// Define the mirrors of a HiPS
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The url currently used
+    /// * `mirrors` - All the urls of the HiPS service, by order of preference
+    pub fn new(url: Url, mirrors: &[Url]) -> Self {
+        let mut urls = vec![url];
+        for mirror in mirrors {
+            let mirror = mirror.trim_end_matches('/');
+            if urls.iter().all(|url| url.trim_end_matches('/') != mirror) {
+                urls.push(mirror.to_string());
+            }
+        }
+
+        Self {
+            urls,
+            cur: 0,
+            num_failures: 0,
+            not_found: HashSet::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get_url(&self) -> &Url {
+        &self.urls[self.cur]
+    }
+
+    #[inline]
+    pub fn get_urls(&self) -> &[Url] {
+        &self.urls
+    }
+
+    /// Use another url, e.g. the fastest mirror, which becomes the current one
+    pub fn set_url(&mut self, url: Url) {
+        if let Some(idx) = self.urls.iter().position(|u| u == &url) {
+            self.cur = idx;
+        } else {
+            self.urls.insert(self.cur, url);
+        }
+
+        self.num_failures = 0;
+    }
+
+    /// Whether a tile is known to not exist, it must not be requested again
+    pub fn is_not_found(&self, id: &QueryId) -> bool {
+        self.not_found.contains(id)
+    }
+
+    /// Take into account a tile request succeeding
+    pub fn notify_success(&mut self, url: &str) {
+        if self.is_from_cur_mirror(url) {
+            self.num_failures = 0;
+        }
+    }
+
+    /// Take into account a tile request failing after its retries
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the tile query
+    /// * `url` - The url of the tile
+    /// * `err` - The error of the last attempt
+    ///
+    /// The new url is returned if another mirror is used from now on
+    pub fn notify_failure(&mut self, id: &QueryId, url: &str, err: FetchError) -> Option<&Url> {
+        if err.is_not_found() {
+            self.not_found.insert(id.clone());
+        }
+
+        // The failures of the former mirrors do not count
+        if !err.is_transient() || !self.is_from_cur_mirror(url) {
+            return None;
+        }
+
+        self.num_failures += 1;
+        if self.num_failures >= MAX_NUM_FAILURES && self.urls.len() > 1 {
+            self.cur = (self.cur + 1) % self.urls.len();
+            self.num_failures = 0;
+
+            Some(self.get_url())
+        } else {
+            None
+        }
+    }
+
+    fn is_from_cur_mirror(&self, url: &str) -> bool {
+        url.strip_prefix(self.get_url().as_str())
+            .is_some_and(|path| path.starts_with('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mirrors, MAX_NUM_FAILURES};
+    use crate::downloader::retry::FetchError;
+
+    fn mirrors() -> Mirrors {
+        Mirrors::new(
+            "https://alasky.cds.unistra.fr/DSS".to_string(),
+            &[
+                "https://alasky.cds.unistra.fr/DSS/".to_string(),
+                "https://alaskybis.cds.unistra.fr/DSS".to_string(),
+            ],
+        )
+    }
+
+    fn tile_url(mirrors: &Mirrors) -> String {
+        format!("{}/Norder3/Dir0/Npix0.jpg", mirrors.get_url())
+    }
+
+    #[test]
+    fn switch_mirror() {
+        let mut mirrors = mirrors();
+        assert_eq!(mirrors.get_urls().len(), 2);
+
+        let id = "Npix0".to_string();
+        let first_url = tile_url(&mirrors);
+        for _ in 1..MAX_NUM_FAILURES {
+            assert!(mirrors
+                .notify_failure(&id, &first_url, FetchError::Status(503))
+                .is_none());
+        }
+        // a success resets the count
+        mirrors.notify_success(&first_url);
+        for _ in 1..MAX_NUM_FAILURES {
+            assert!(mirrors
+                .notify_failure(&id, &first_url, FetchError::Network)
+                .is_none());
+        }
+
+        assert_eq!(
+            mirrors
+                .notify_failure(&id, &first_url, FetchError::Timeout)
+                .map(|url| url.as_str()),
+            Some("https://alaskybis.cds.unistra.fr/DSS")
+        );
+
+        // the requests sent to the former mirror are ignored
+        for _ in 0..MAX_NUM_FAILURES {
+            mirrors.notify_failure(&id, &first_url, FetchError::Network);
+        }
+        assert_eq!(mirrors.get_url(), "https://alaskybis.cds.unistra.fr/DSS");
+    }
+
+    #[test]
+    fn not_found() {
+        let mut mirrors = mirrors();
+        let url = tile_url(&mirrors);
+
+        let id = "Npix0".to_string();
+        for _ in 0..MAX_NUM_FAILURES {
+            assert!(mirrors
+                .notify_failure(&id, &url, FetchError::Status(404))
+                .is_none());
+        }
+
+        assert!(mirrors.is_not_found(&id));
+        assert!(!mirrors.is_not_found(&"Npix1".to_string()));
+        assert_eq!(mirrors.get_url(), "https://alasky.cds.unistra.fr/DSS");
+    }
+
+    #[test]
+    fn single_url() {
+        let url = "https://alasky.cds.unistra.fr/DSS".to_string();
+        let mut mirrors = Mirrors::new(url.clone(), &[]);
+
+        let tile_url = tile_url(&mirrors);
+        for _ in 0..2 * MAX_NUM_FAILURES {
+            assert!(mirrors
+                .notify_failure(&"Npix0".to_string(), &tile_url, FetchError::Network)
+                .is_none());
+        }
+        assert_eq!(mirrors.get_url(), &url);
+
+        mirrors.set_url("https://alaskybis.cds.unistra.fr/DSS".to_string());
+        assert_eq!(mirrors.get_urls().len(), 2);
+        assert_eq!(mirrors.get_url(), "https://alaskybis.cds.unistra.fr/DSS");
+    }
+}
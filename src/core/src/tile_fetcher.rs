@@ -1,23 +1,223 @@
-use crate::downloader::{query, Downloader};
-use crate::renderable::HiPS;
+use crate::downloader::{query, query::QueryId, Downloader};
+use crate::healpix::{cell::HEALPixCell, coverage::HEALPixCoverage};
+use crate::math::{angle::Angle, lonlat::radec_to_xyz, vector::angle3};
+use crate::renderable::{CreatorDid, HiPS};
 use crate::time::{DeltaTime, Time};
-use crate::Abort;
 
-use std::collections::VecDeque;
+use cgmath::Vector3;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 
-const MAX_NUM_TILE_FETCHING: isize = 8;
+/// Default number of tile requests in flight to the same host
+pub const DEFAULT_MAX_NUM_TILE_FETCHING: usize = 8;
 const MAX_QUERY_QUEUE_LENGTH: usize = 100;
 
+/// The urgency of fetching a tile
+///
+/// The tiles of low depth come first as they quickly cover the view,
+/// then the ones the closest to the center of the view
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TilePriority {
+    depth: u8,
+    // Angular distance from the center of the view, in radians
+    dist: f64,
+}
+
+impl TilePriority {
+    /// # Arguments
+    ///
+    /// * `cell` - The tile cell
+    /// * `center` - The center of the view, in the frame of the HiPS
+    pub fn new(cell: &HEALPixCell, center: &Vector3<f64>) -> Self {
+        let (lon, lat) = cell.center();
+        let dist = angle3(&radec_to_xyz(Angle(lon), Angle(lat)), center).0;
+
+        Self {
+            depth: cell.depth(),
+            dist,
+        }
+    }
+}
+
+impl Eq for TilePriority {}
+
+impl PartialOrd for TilePriority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The greater the more urgent
+impl Ord for TilePriority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .depth
+            .cmp(&self.depth)
+            .then_with(|| other.dist.total_cmp(&self.dist))
+    }
+}
+
+struct TileQuery {
+    query: query::Tile,
+    priority: TilePriority,
+}
+
+impl PartialEq for TileQuery {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for TileQuery {}
+
+impl PartialOrd for TileQuery {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TileQuery {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+/// The tile requests of the downloader, this is where the network layer can be mocked
+pub trait TileRequests {
+    /// Whether a tile is already being downloaded
+    fn is_queried(&self, id: &QueryId) -> bool;
+    /// Number of tile requests in flight to a host
+    fn num_tile_requests(&self, host: &str) -> usize;
+    /// Send the request of a tile. Returns false if it has not been sent
+    fn fetch(&mut self, query: query::Tile) -> bool;
+    /// Abort the tile requests in flight that are not kept, given their cell and their HiPS
+    fn cancel_tile_requests(&mut self, keep: &mut dyn FnMut(&HEALPixCell, &CreatorDid) -> bool);
+}
+
+impl TileRequests for Downloader {
+    fn is_queried(&self, id: &QueryId) -> bool {
+        Downloader::is_queried(self, id)
+    }
+
+    fn num_tile_requests(&self, host: &str) -> usize {
+        Downloader::num_tile_requests(self, host)
+    }
+
+    fn fetch(&mut self, query: query::Tile) -> bool {
+        Downloader::fetch(self, query)
+    }
+
+    fn cancel_tile_requests(&mut self, keep: &mut dyn FnMut(&HEALPixCell, &CreatorDid) -> bool) {
+        Downloader::cancel_tile_requests(self, |tile| keep(tile.cell(), tile.get_hips_cdid()))
+    }
+}
+
+/// Whether a tile is still needed by the view
+///
+/// # Arguments
+///
+/// * `cell` - The tile cell
+/// * `delta_depth` - The depth difference between the textures and the tiles of the HiPS
+/// * `min_depth_tile` - The minimum depth of the tiles of the HiPS
+/// * `view` - The coverage of the view, in the frame of the HiPS
+///
+/// The base tiles are always needed as they cover the whole sky. The other ones are needed
+/// if their texture intersects the view
+pub fn is_tile_in_view(
+    cell: &HEALPixCell,
+    delta_depth: u8,
+    min_depth_tile: u8,
+    view: &HEALPixCoverage,
+) -> bool {
+    cell.depth() <= delta_depth.max(min_depth_tile)
+        || cell
+            .get_texture_cell(delta_depth)
+            .get_tile_cells(delta_depth)
+            .any(|tile_cell| view.intersects_cell(&tile_cell))
+}
+
+// The tile queries ordered by urgency, fetched within the number of
+// requests that can be in flight to their host
+struct TileQueue {
+    // The queries to fetch, the most urgent on top
+    queries: BinaryHeap<TileQuery>,
+
+    // The number of tile requests that can be in flight to a host
+    max_num_fetching: usize,
+    max_num_fetching_by_host: HashMap<String, usize>,
+}
+
+impl TileQueue {
+    fn new() -> Self {
+        Self {
+            queries: BinaryHeap::new(),
+            max_num_fetching: DEFAULT_MAX_NUM_TILE_FETCHING,
+            max_num_fetching_by_host: HashMap::new(),
+        }
+    }
+
+    fn set_max_num_fetching(&mut self, max: usize, host: Option<&str>) {
+        let max = max.max(1);
+
+        if let Some(host) = host {
+            self.max_num_fetching_by_host
+                .insert(query::host(host).to_string(), max);
+        } else {
+            self.max_num_fetching = max;
+        }
+    }
+
+    fn max_num_fetching(&self, host: &str) -> usize {
+        self.max_num_fetching_by_host
+            .get(host)
+            .copied()
+            .unwrap_or(self.max_num_fetching)
+    }
+
+    // Fetch the most urgent queries whose host can accept a new request
+    fn fetch<D: TileRequests>(&mut self, downloader: &mut D) {
+        // The number of requests in flight by host
+        let mut num_requests_by_host = HashMap::new();
+        // The queries whose host is busy are kept for the next fetch
+        let mut postponed = vec![];
+        while let Some(tile_query) = self.queries.pop() {
+            let query = &tile_query.query;
+            // The tile is already being downloaded
+            if downloader.is_queried(&query.id) {
+                continue;
+            }
+
+            let host = query::host(&query.url);
+            let max_num_fetching = self.max_num_fetching(host);
+            let num_requests = num_requests_by_host
+                .entry(host.to_string())
+                .or_insert_with(|| downloader.num_tile_requests(host));
+
+            if *num_requests < max_num_fetching {
+                if downloader.fetch(tile_query.query) {
+                    // The fetch has succeded
+                    *num_requests += 1;
+                }
+            } else {
+                postponed.push(tile_query);
+            }
+        }
+
+        // Discard the least urgent queries
+        postponed.truncate(MAX_QUERY_QUEUE_LENGTH);
+        self.queries = postponed.into();
+    }
+}
+
 pub struct TileFetcherQueue {
-    // A stack of queries to fetch
-    queries: VecDeque<query::Tile>,
+    queries: TileQueue,
     base_tile_queries: Vec<query::Tile>,
     tiles_fetched_time: Time,
 }
 
 impl TileFetcherQueue {
     pub fn new() -> Self {
-        let queries = VecDeque::new();
+        let queries = TileQueue::new();
         let base_tile_queries = Vec::new();
         let tiles_fetched_time = Time::now();
         Self {
@@ -28,19 +228,17 @@ impl TileFetcherQueue {
     }
 
     pub fn clear(&mut self) {
-        self.queries.clear();
+        self.queries.queries.clear();
         //self.query_set.clear();
     }
 
-    pub fn append(&mut self, query: query::Tile, _downloader: &mut Downloader) {
-        // Check if the query has already been done
-        //if !self.query_set.contains(&query) {
-        // discard too old tile queries
-        // this may not be the best thing to do but
-        if self.queries.len() > MAX_QUERY_QUEUE_LENGTH {
-            self.queries.pop_front();
-        }
-        self.queries.push_back(query.clone());
+    pub fn append(
+        &mut self,
+        query: query::Tile,
+        priority: TilePriority,
+        _downloader: &mut Downloader,
+    ) {
+        self.queries.queries.push(TileQuery { query, priority });
     }
 
     // fetch the base tile
@@ -48,6 +246,17 @@ impl TileFetcherQueue {
         self.base_tile_queries.push(query);
     }
 
+    /// Set the number of tile requests that can be in flight to a host
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The number of requests, at least 1
+    /// * `host` - The host, e.g. alasky.cds.unistra.fr. If not given, the number is set for the hosts
+    ///   that have not been given one
+    pub fn set_max_num_fetching(&mut self, max: usize, host: Option<&str>) {
+        self.queries.set_max_num_fetching(max, host);
+    }
+
     pub fn notify(&mut self, downloader: &mut Downloader, dt: Option<DeltaTime>) {
         // notify all the x ms
         let now = Time::now();
@@ -73,15 +282,7 @@ impl TileFetcherQueue {
             downloader.fetch(query);
         }
 
-        let mut num_fetched_tile = 0;
-        while num_fetched_tile < MAX_NUM_TILE_FETCHING && !self.queries.is_empty() {
-            let query = self.queries.pop_back().unwrap_abort();
-
-            if downloader.fetch(query) {
-                // The fetch has succeded
-                num_fetched_tile += 1;
-            }
-        }
+        self.queries.fetch(downloader);
     }
 
     pub fn launch_starting_hips_requests(&mut self, hips: &HiPS, downloader: &mut Downloader) {
@@ -114,3 +315,301 @@ impl TileFetcherQueue {
         }*/
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_tile_in_view, TilePriority, TileQuery, TileQueue, TileRequests, MAX_QUERY_QUEUE_LENGTH,
+    };
+    use crate::downloader::query::{self, host, QueryId};
+    use crate::downloader::{cancel_tile_requests, InFlightTile};
+    use crate::healpix::{cell::HEALPixCell, coverage::HEALPixCoverage};
+    use crate::math::{
+        angle::{Angle, ToAngle},
+        lonlat::{radec_to_xyz, LonLatT},
+    };
+    use crate::renderable::CreatorDid;
+    use al_api::hips::ImageExt;
+    use al_core::image::format::{ChannelType, ImageFormatType};
+    use std::collections::HashSet;
+
+    // Keep the requests in flight instead of sending them
+    #[derive(Default)]
+    struct MockDownloader {
+        in_flight: Vec<query::Tile>,
+        queried_list: HashSet<QueryId>,
+        // The ids of the tiles fetched, in order
+        fetched: Vec<QueryId>,
+    }
+
+    impl MockDownloader {
+        // The requests in flight are resolved
+        fn resolve(&mut self) {
+            self.in_flight.clear();
+            self.queried_list.clear();
+        }
+    }
+
+    // The requests in flight are never resolved before being given back by `resolve`
+    impl InFlightTile for query::Tile {
+        fn id(&self) -> &QueryId {
+            &self.id
+        }
+
+        fn is_resolved(&self) -> bool {
+            false
+        }
+
+        fn abort(&self) {}
+    }
+
+    fn as_tile(query: &query::Tile) -> Option<&query::Tile> {
+        Some(query)
+    }
+
+    impl TileRequests for MockDownloader {
+        fn is_queried(&self, id: &QueryId) -> bool {
+            self.queried_list.contains(id)
+        }
+
+        fn num_tile_requests(&self, h: &str) -> usize {
+            self.in_flight
+                .iter()
+                .filter(|query| host(&query.url) == h)
+                .count()
+        }
+
+        fn fetch(&mut self, query: query::Tile) -> bool {
+            self.fetched.push(query.id.clone());
+            self.queried_list.insert(query.id.clone());
+            self.in_flight.push(query);
+
+            true
+        }
+
+        fn cancel_tile_requests(
+            &mut self,
+            keep: &mut dyn FnMut(&HEALPixCell, &CreatorDid) -> bool,
+        ) {
+            // the filtering of the downloader
+            cancel_tile_requests(
+                &mut self.in_flight,
+                &mut self.queried_list,
+                as_tile,
+                |query| keep(&query.cell, &query.hips_cdid),
+            );
+        }
+    }
+
+    // A tile of a HiPS on a host, the lower `dist` the more urgent
+    fn tile_query(host: &str, idx: u64, dist: f64) -> TileQuery {
+        let cell = HEALPixCell(5, idx);
+        let format = ImageFormatType {
+            ext: ImageExt::Jpeg,
+            channel: ChannelType::RGB8U,
+        };
+        let query = query::Tile::new(
+            &cell,
+            format!("ivo://{}", host),
+            format!("https://{}/hips", host),
+            format,
+            0,
+        );
+
+        TileQuery {
+            query,
+            priority: TilePriority { depth: 5, dist },
+        }
+    }
+
+    fn ids(host: &str, idx: &[u64]) -> Vec<QueryId> {
+        idx.iter()
+            .map(|idx| tile_query(host, *idx, 0.0).query.id)
+            .collect()
+    }
+
+    #[test]
+    fn concurrency_per_host() {
+        let mut queue = TileQueue::new();
+        queue.set_max_num_fetching(3, None);
+        queue.set_max_num_fetching(1, Some("https://alaskybis.cds.unistra.fr/DSS"));
+
+        for idx in 0..10 {
+            queue
+                .queries
+                .push(tile_query("alasky.cds.unistra.fr", idx, idx as f64));
+        }
+        for idx in 0..2 {
+            queue
+                .queries
+                .push(tile_query("alaskybis.cds.unistra.fr", idx, idx as f64));
+        }
+
+        let mut downloader = MockDownloader::default();
+        queue.fetch(&mut downloader);
+        // the most urgent tiles of each host are fetched within their limit
+        let mut expected = ids("alasky.cds.unistra.fr", &[0, 1, 2]);
+        expected.extend(ids("alaskybis.cds.unistra.fr", &[0]));
+        let mut fetched = downloader.fetched.clone();
+        fetched.sort();
+        expected.sort();
+        assert_eq!(fetched, expected);
+        // the others are postponed
+        assert_eq!(queue.queries.len(), 8);
+
+        // no more request can be sent while the hosts are busy
+        queue.fetch(&mut downloader);
+        assert_eq!(downloader.fetched.len(), 4);
+        assert_eq!(queue.queries.len(), 8);
+
+        downloader.resolve();
+        downloader.fetched.clear();
+        queue.fetch(&mut downloader);
+        let mut expected = ids("alasky.cds.unistra.fr", &[3, 4, 5]);
+        expected.extend(ids("alaskybis.cds.unistra.fr", &[1]));
+        let mut fetched = downloader.fetched.clone();
+        fetched.sort();
+        expected.sort();
+        assert_eq!(fetched, expected);
+    }
+
+    #[test]
+    fn tiles_in_flight_are_not_fetched_again() {
+        let mut queue = TileQueue::new();
+        queue.set_max_num_fetching(2, None);
+
+        let mut downloader = MockDownloader::default();
+        downloader.fetch(tile_query("alasky.cds.unistra.fr", 0, 0.0).query);
+        downloader.fetched.clear();
+
+        for idx in 0..3 {
+            queue
+                .queries
+                .push(tile_query("alasky.cds.unistra.fr", idx, idx as f64));
+        }
+        queue.fetch(&mut downloader);
+        // the tile in flight takes one of the two requests
+        assert_eq!(downloader.fetched, ids("alasky.cds.unistra.fr", &[1]));
+        assert_eq!(queue.queries.len(), 1);
+    }
+
+    #[test]
+    fn queue_truncation() {
+        let mut queue = TileQueue::new();
+        queue.set_max_num_fetching(1, None);
+
+        let num_queries = MAX_QUERY_QUEUE_LENGTH as u64 + 50;
+        // pushed from the least urgent
+        for idx in (0..num_queries).rev() {
+            queue
+                .queries
+                .push(tile_query("alasky.cds.unistra.fr", idx, idx as f64));
+        }
+
+        let mut downloader = MockDownloader::default();
+        queue.fetch(&mut downloader);
+        assert_eq!(downloader.fetched, ids("alasky.cds.unistra.fr", &[0]));
+        // the least urgent queries are discarded
+        assert_eq!(queue.queries.len(), MAX_QUERY_QUEUE_LENGTH);
+        let last = queue
+            .queries
+            .iter()
+            .map(|q| q.priority.dist)
+            .fold(0.0, f64::max);
+        assert_eq!(last, MAX_QUERY_QUEUE_LENGTH as f64);
+
+        downloader.resolve();
+        queue.fetch(&mut downloader);
+        assert_eq!(downloader.fetched, ids("alasky.cds.unistra.fr", &[0, 1]));
+    }
+
+    #[test]
+    fn cancel_tiles_leaving_the_view() {
+        let (delta_depth, min_depth_tile) = (3, 0);
+        let center = LonLatT::new(
+            45_f64.to_radians().to_angle(),
+            45_f64.to_radians().to_angle(),
+        );
+        let view = HEALPixCoverage::from_cone(&center, 1_f64.to_radians(), 9);
+
+        let in_view = HEALPixCell::new(6, 45_f64.to_radians(), 45_f64.to_radians());
+        let out_of_view = HEALPixCell::new(6, 225_f64.to_radians(), -45_f64.to_radians());
+        // a base tile, covering the whole sky with the others
+        let base = HEALPixCell::new(3, 225_f64.to_radians(), -45_f64.to_radians());
+
+        assert!(is_tile_in_view(
+            &in_view,
+            delta_depth,
+            min_depth_tile,
+            &view
+        ));
+        assert!(!is_tile_in_view(
+            &out_of_view,
+            delta_depth,
+            min_depth_tile,
+            &view
+        ));
+        assert!(is_tile_in_view(&base, delta_depth, min_depth_tile, &view));
+
+        let mut downloader = MockDownloader::default();
+        for cell in [in_view, out_of_view, base].iter() {
+            let mut query = tile_query("alasky.cds.unistra.fr", 0, 0.0).query;
+            query.cell = *cell;
+            query.id = format!("{:?}", cell);
+            downloader.fetch(query);
+        }
+
+        downloader.cancel_tile_requests(&mut |cell, _| {
+            is_tile_in_view(cell, delta_depth, min_depth_tile, &view)
+        });
+        let cells = downloader
+            .in_flight
+            .iter()
+            .map(|query| query.cell)
+            .collect::<Vec<_>>();
+        assert_eq!(cells, vec![in_view, base]);
+        // the tile leaving the view can be queried again
+        assert!(!downloader.is_queried(&format!("{:?}", out_of_view)));
+        assert!(downloader.is_queried(&format!("{:?}", in_view)));
+    }
+
+    #[test]
+    fn tile_priority() {
+        let cell = HEALPixCell(3, 300);
+        let (lon, lat) = cell.center();
+        let center = radec_to_xyz(Angle(lon), Angle(lat));
+
+        let priority = TilePriority::new(&cell, &center);
+        // the tiles the closest to the center come first
+        assert!(priority > TilePriority::new(&HEALPixCell(3, 0), &center));
+        // then the ones of lower depth
+        assert!(TilePriority::new(&HEALPixCell(2, 0), &center) > priority);
+
+        let mut priorities = vec![
+            TilePriority::new(&HEALPixCell(3, 0), &center),
+            TilePriority::new(&HEALPixCell(4, 1200), &center),
+            priority,
+            TilePriority::new(&HEALPixCell(0, 11), &center),
+        ];
+        priorities.sort();
+        assert_eq!(
+            priorities.iter().map(|p| p.depth).collect::<Vec<_>>(),
+            vec![4, 3, 3, 0]
+        );
+        assert_eq!(priorities[2], priority);
+    }
+
+    #[test]
+    fn url_host() {
+        assert_eq!(
+            host("https://alasky.cds.unistra.fr/DSS/DSSColor/Norder3/Dir0/Npix0.jpg"),
+            "alasky.cds.unistra.fr"
+        );
+        assert_eq!(host("http://localhost:8080/hips"), "localhost:8080");
+        assert_eq!(host("alaskybis.cds.unistra.fr"), "alaskybis.cds.unistra.fr");
+        assert_eq!(
+            host("https://skies.esac.esa.int?hips=1"),
+            "skies.esac.esa.int"
+        );
+    }
+}
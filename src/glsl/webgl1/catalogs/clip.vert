@@ -0,0 +1,26 @@
+precision lowp float;
+
+attribute vec2 offset;
+attribute vec2 uv;
+// Center of the source already projected in the clipping space.
+// The z component is 0 when the source could not be projected.
+attribute vec3 center;
+
+uniform vec2 ndc_to_clip;
+uniform float czf;
+uniform vec2 kernel_size;
+
+varying vec2 out_uv;
+varying vec3 out_p;
+
+void main() {
+    vec2 pos_clip_space = center.xy;
+    gl_Position = vec4((pos_clip_space / (ndc_to_clip * czf)) + offset * kernel_size , 0.0, 1.0);
+    // Discard the sources not projected by sending them outside of the screen
+    if (center.z == 0.0) {
+        gl_Position = vec4(2.0, 2.0, 0.0, 1.0);
+    }
+
+    out_uv = uv;
+    out_p = vec3(pos_clip_space, center.z);
+}
@@ -0,0 +1,26 @@
+#version 300 es
+precision lowp float;
+layout (location = 0) in vec2 offset;
+layout (location = 1) in vec2 uv;
+// Center of the source already projected in the clipping space.
+// The z component is 0 when the source could not be projected.
+layout (location = 2) in vec3 center;
+
+uniform vec2 ndc_to_clip;
+uniform float czf;
+uniform vec2 kernel_size;
+
+out vec2 out_uv;
+out vec3 out_p;
+
+void main() {
+    vec2 pos_clip_space = center.xy;
+    gl_Position = vec4((pos_clip_space / (ndc_to_clip * czf)) + offset * kernel_size , 0.f, 1.f);
+    // Discard the sources not projected by sending them outside of the screen
+    if (center.z == 0.f) {
+        gl_Position = vec4(2.f, 2.f, 0.f, 1.f);
+    }
+
+    out_uv = uv;
+    out_p = vec3(pos_clip_space, center.z);
+}
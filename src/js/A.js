@@ -130,7 +130,8 @@ A.imageHiPS = function (id, options) {
  * @function
  * @name A.imageFITS
  * @memberof A
- * @param {string} url - Options describing the fits file. An url is mandatory
+ * @param {string} url - Options describing the fits file. An url is mandatory.
+ * It can also refer to a single resolution HEALPix map (PIXTYPE = 'HEALPIX' binary table), e.g. a Planck or WMAP all sky map
  * @param {ImageFITSOptions} [options] - Options describing the fits file. An url is mandatory
  * @returns {ImageFITS} - A HiPS image object
  * @example
@@ -388,7 +389,7 @@ A.footprintsFromSTCS = function (stcs, options) {
  * @memberof A
  * @name MOCFromURL
  *
- * @param {string} url - The url to the MOC (e.g. stored as FITS file)
+ * @param {string} url - The url to the MOC, stored as a FITS file or serialized in JSON or ASCII. S-MOCs, T-MOCs and ST-MOCs are accepted
  * @param {MOCOptions} [options] - Display options for the MOC
  * @param {function} [successCallback] - Callback function when the MOC loads
  * @param {function} [errorCallback] - Callback function when the MOC fails loading
@@ -434,6 +435,37 @@ A.MOCFromJSON = function (jsonMOC, options, successCallback, errorCallback) {
     return moc;
 };
 
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from its IVOA ASCII serialization
+ *
+ * Space (S-MOC), time (T-MOC) and space-time (ST-MOC) MOCs are accepted. Only the spatial
+ * coverage of a T-MOC or a ST-MOC is drawn, over a time interval that can be changed with {@link MOC#setTimeRange}.
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromASCII
+ *
+ * @param {string} asciiMOC - The MOC serialized in ASCII, e.g. <code>"3/1-5 4/30"</code>
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ *
+ * @example
+ * // two sets of cells, each observed during a time cell of about 19 hours in December 2022
+ * var stmoc = A.MOCFromASCII("t25/3092836 s5/200-210 t25/3092837 s5/300-310", {color: 'orange'}, (moc) => {
+ *     // only display the cells observed during the first one
+ *     moc.setTimeRange(new Date('2022-12-17T00:00:00Z'), new Date('2022-12-18T00:00:00Z'));
+ * });
+ * aladin.addMOC(stmoc);
+ */
+A.MOCFromASCII = function (asciiMOC, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({ascii: asciiMOC}, successCallback, errorCallback);
+
+    return moc;
+};
+
 /**
  * Creates a new MOC (Multi-Order-Coverage) from an object describing a cone on the sky
  *
@@ -445,6 +477,7 @@ A.MOCFromJSON = function (jsonMOC, options, successCallback, errorCallback) {
  * @param {number} circle.ra - Right-ascension of the circle's center (in deg)
  * @param {number} circle.dec - Declination of the circle's center (in deg)
  * @param {number} circle.radius - Radius of the circle (in deg)
+ * @param {number} [circle.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
  * @param {MOCOptions} [options] - Display options for the MOC
  * @param {function} [successCallback] - Callback function when the MOC loads
  * @param {function} [errorCallback] - Callback function when the MOC fails loading
@@ -467,6 +500,7 @@ A.MOCFromCone = function (circle, options, successCallback, errorCallback) {
  * @param {Object} polygon - A object describing a polygon in the sky
  * @param {number[]} polygon.ra - Right-ascensions of the polygon's vertices (in deg)
  * @param {number[]} polygon.dec - Declination of the polygon's vertices (in deg)
+ * @param {number} [polygon.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
  * @param {MOCOptions} [options] - Display options for the MOC
  * @param {function} [successCallback] - Callback function when the MOC loads
  * @param {function} [errorCallback] - Callback function when the MOC fails loading
@@ -479,6 +513,180 @@ A.MOCFromPolygon= function (polygon, options, successCallback, errorCallback) {
     return moc;
 };
 
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from a polygon made of several rings, e.g. a footprint with holes or made of several parts
+ *
+ * The inside of a ring is on its left, i.e. a ring goes counter-clockwise around its inside as seen on the sky
+ * and a hole is given by a clockwise ring. Unlike {@link A.MOCFromPolygon}, polygons larger than a hemisphere are supported.
+ * The MOC is made of the points enclosed by more rings than its outside, or by at least as many rings as the control point if one is given.
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromMultiPolygon
+ *
+ * @param {Object} multiPolygon - A object describing the rings
+ * @param {Object[]} multiPolygon.rings - The rings, each one given by the right-ascensions (<code>ra</code>) and the declinations (<code>dec</code>) of its vertices (in deg)
+ * @param {Object} [multiPolygon.controlPoint] - A point inside the MOC given by its <code>ra</code> and <code>dec</code> (in deg)
+ * @param {number} [multiPolygon.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ *
+ * @example
+ * // a square with a square hole, both straddling the RA=0 meridian
+ * var moc = A.MOCFromMultiPolygon({rings: [
+ *     {ra: [20, 340, 340, 20], dec: [-20, -20, 20, 20]},
+ *     {ra: [355, 355, 5, 5], dec: [5, -5, -5, 5]},
+ * ]});
+ * aladin.addMOC(moc);
+ */
+A.MOCFromMultiPolygon = function (multiPolygon, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse(multiPolygon, successCallback, errorCallback);
+
+    return moc;
+};
+
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from an object describing an elliptical cone on the sky
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromEllipse
+ *
+ * @param {Object} ellipse - A object describing an ellipse in the sky
+ * @param {number} ellipse.ra - Right-ascension of the ellipse's center (in deg)
+ * @param {number} ellipse.dec - Declination of the ellipse's center (in deg)
+ * @param {number} ellipse.a - Semi-major axis (in deg)
+ * @param {number} ellipse.b - Semi-minor axis (in deg)
+ * @param {number} [ellipse.pa=0] - Position angle of the major axis, east of north (in deg)
+ * @param {number} [ellipse.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ */
+A.MOCFromEllipse = function (ellipse, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({...ellipse, shape: 'ellipse'}, successCallback, errorCallback);
+
+    return moc;
+};
+
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from an object describing a box on the sky
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromBox
+ *
+ * @param {Object} box - A object describing a box in the sky
+ * @param {number} box.ra - Right-ascension of the box's center (in deg)
+ * @param {number} box.dec - Declination of the box's center (in deg)
+ * @param {number} box.a - Half width of the box along its first axis (in deg)
+ * @param {number} box.b - Half width of the box along its second axis (in deg)
+ * @param {number} [box.pa=0] - Position angle of the first axis, east of north (in deg)
+ * @param {number} [box.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ */
+A.MOCFromBox = function (box, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({...box, shape: 'box'}, successCallback, errorCallback);
+
+    return moc;
+};
+
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from an object describing a zone delimited by two meridians and two parallels
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromZone
+ *
+ * @param {Object} zone - A object describing a zone in the sky. It crosses the meridian 0 if <code>zone.raMin > zone.raMax</code>
+ * @param {number} zone.raMin - Right-ascension of the first meridian (in deg)
+ * @param {number} zone.decMin - Declination of the lower parallel (in deg)
+ * @param {number} zone.raMax - Right-ascension of the second meridian (in deg)
+ * @param {number} zone.decMax - Declination of the upper parallel (in deg)
+ * @param {number} [zone.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ */
+A.MOCFromZone = function (zone, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({...zone, shape: 'zone'}, successCallback, errorCallback);
+
+    return moc;
+};
+
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from an object describing an annulus on the sky
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromRing
+ *
+ * @param {Object} ring - A object describing an annulus in the sky
+ * @param {number} ring.ra - Right-ascension of the ring's center (in deg)
+ * @param {number} ring.dec - Declination of the ring's center (in deg)
+ * @param {number} ring.rInt - Internal radius of the ring (in deg)
+ * @param {number} ring.rExt - External radius of the ring (in deg)
+ * @param {number} [ring.depth] - The HEALPix order of the MOC. Defaults to an order finer than the current view
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ */
+A.MOCFromRing = function (ring, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({...ring, shape: 'ring'}, successCallback, errorCallback);
+
+    return moc;
+};
+
+/**
+ * Creates a new MOC (Multi-Order-Coverage) from the credible region of a HEALPix probability map,
+ * e.g. the localization of a gravitational wave event given as a multi-order map (UNIQ and PROBDENSITY columns).
+ * The credible region is the smallest region containing a given fraction of the total probability.
+ * Its area in square degrees is given by the {@link MOC#area} method once loaded.
+ *
+ * @function
+ * @memberof A
+ * @name MOCFromCredibleRegion
+ *
+ * @param {ImageFITS|string} image - The probability map layer, already added to the view, or its layer name
+ * @param {number} level - The fraction of the total probability contained in the region, in ]0, 1]
+ * @param {MOCOptions} [options] - Display options for the MOC
+ * @param {function} [successCallback] - Callback function when the MOC loads
+ * @param {function} [errorCallback] - Callback function when the MOC fails loading
+ * @returns {MOC} Returns a new MOC object
+ *
+ * @example
+ * let map = A.imageFITS('https://example.org/bayestar.multiorder.fits', {
+ *     name: 'GW localization',
+ *     successCallback: (ra, dec, fov, image) => {
+ *         for (const level of [0.5, 0.9]) {
+ *             aladin.addMOC(A.MOCFromCredibleRegion(image, level, {name: level * 100 + '%'}, (moc) => {
+ *                 console.log(moc.name, moc.area(), 'deg2');
+ *             }));
+ *         }
+ *     }
+ * });
+ * aladin.setOverlayImageLayer(map);
+ */
+A.MOCFromCredibleRegion = function (image, level, options, successCallback, errorCallback) {
+    var moc = new MOC(options);
+    moc.parse({image: image, level: level}, successCallback, errorCallback);
+
+    return moc;
+};
+
 /**
  * Represents options for configuring a catalog.
  *
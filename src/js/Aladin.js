@@ -134,7 +134,7 @@ import { CooFrame } from "./gui/Input/CooFrame";
  * @property {boolean} [gridOptions.showLabels=true] - Whether the grid has labels.
  * @property {number} [gridOptions.labelSize=15] - The font size of the labels.
  * 
- * @property {string} [projection="SIN"] - Projection type. Can be 'SIN' for orthographic, 'MOL' for mollweide, 'AIT' for hammer-aitoff, 'ZEA' for zenital equal-area or 'MER' for mercator
+ * @property {string} [projection="SIN"] - Projection type. Can be any value accepted by {@link Aladin#setProjection}, e.g. 'SIN' for orthographic, 'MOL' for mollweide, 'AIT' for hammer-aitoff, 'ZEA' for zenital equal-area or 'MER' for mercator
  * @property {boolean} [log=true] - Whether to log events.
  * @property {boolean} [samp=false] - Whether to enable SAMP (Simple Application Messaging Protocol).
  * @property {boolean} [realFullscreen=false] - Whether to use real fullscreen mode.
@@ -924,7 +924,7 @@ export let Aladin = (function () {
      * Sets the coordinate frame of the Aladin instance to the specified frame.
      *
      * @memberof Aladin
-     * @param {string} frame - The name of the coordinate frame. Possible values: 'j2000d', 'j2000', 'gal', 'icrs'. The given string is case insensitive.
+     * @param {string} frame - The name of the coordinate frame. Possible values: 'j2000d', 'j2000', 'gal', 'icrs', 'altaz'. The given string is case insensitive.
      *
      * @example
      * // Set the coordinate frame to 'J2000'
@@ -948,6 +948,103 @@ export let Aladin = (function () {
         }
     };
 
+    /**
+     * Sets the location of the observer used by the horizontal ('AltAz') frame.
+     *
+     * @memberof Aladin
+     * @param {number} lon - Geodetic longitude in degrees, positive towards the East
+     * @param {number} lat - Geodetic latitude in degrees
+     * @param {number} [elevation=0] - Elevation above the sea level in meters. It is not used yet as the atmospheric refraction is not taken into account
+     *
+     * @example
+     * // Observe from Paranal in the horizontal frame
+     * let aladin = A.aladin('#aladin-lite-div', {cooFrame: 'altaz'});
+     * aladin.setObserver(-70.404, -24.627, 2635);
+     */
+    Aladin.prototype.setObserver = function (lon, lat, elevation) {
+        this.wasm.setObserver(lon, lat, elevation || 0.0);
+        this.view.requestRedraw();
+    };
+
+    /**
+     * Sets the UTC time used by the horizontal ('AltAz') frame.
+     *
+     * @memberof Aladin
+     * @param {Date|number} time - A Date object or a number of milliseconds since the Unix epoch
+     */
+    Aladin.prototype.setObservationTime = function (time) {
+        const ms = time instanceof Date ? time.getTime() : time;
+        this.wasm.setObservationTime(ms);
+        this.view.requestRedraw();
+    };
+
+    /**
+     * Sets the epoch at which the catalogs are drawn.
+     *
+     * Only the sources of the catalogs given with their proper motion fields are moved.
+     *
+     * @memberof Aladin
+     * @param {number} [epoch] - A Julian year (e.g. 2024.5). If not given, the sources are drawn at their catalog epoch
+     *
+     * @example
+     * // Show a Gaia DR3 catalog at the current date
+     * const gaia = A.catalog({epoch: 2016, pmraField: 'pmra', pmdecField: 'pmdec'});
+     * aladin.setCatalogEpoch(new Date().getFullYear());
+     */
+    Aladin.prototype.setCatalogEpoch = function (epoch) {
+        this.wasm.setCatalogEpoch(epoch);
+        this.view.requestRedraw();
+    };
+
+    /**
+     * Precesses equatorial coordinates between two mean equinoxes.
+     *
+     * @memberof Aladin
+     * @param {number} lon - The longitude in degrees
+     * @param {number} lat - The latitude in degrees
+     * @param {string} frame - The equatorial frame of the coordinates: 'icrs', 'fk5' or 'fk4'
+     * @param {number} from - The equinox of the coordinates as a year (Besselian for FK4, Julian otherwise)
+     * @param {number} to - The equinox of the returned coordinates as a year (Besselian for FK4, Julian otherwise)
+     *
+     * @returns {number[]} - The precessed longitude and latitude in degrees
+     */
+    Aladin.prototype.precessCoordinates = function (lon, lat, frame, from, to) {
+        return this.wasm.precessCoordinates(lon, lat, toCooSystem(frame), from, to);
+    };
+
+    /**
+     * Propagates the position of a source to another epoch given its space motion.
+     *
+     * @memberof Aladin
+     * @param {number} lon - The longitude at <code>epoch</code> in degrees
+     * @param {number} lat - The latitude at <code>epoch</code> in degrees
+     * @param {string} frame - The frame of the coordinates, e.g. 'icrs'
+     * @param {number} epoch - The epoch of the position as a year (Besselian for FK4, Julian otherwise)
+     * @param {Object} motion - The space motion of the source
+     * @param {number} motion.pmra - The proper motion in longitude (including the cos(lat) factor) in mas/yr
+     * @param {number} motion.pmdec - The proper motion in latitude in mas/yr
+     * @param {number} [motion.parallax=0] - The parallax in mas
+     * @param {number} [motion.rv=0] - The radial velocity in km/s
+     * @param {number} to - The target epoch as a year (Besselian for FK4, Julian otherwise)
+     * @param {string} [toFrame] - The frame of the returned coordinates. If not given, <code>frame</code> is used
+     *
+     * @returns {number[]} - The longitude and latitude at <code>to</code> in degrees
+     */
+    Aladin.prototype.propagateCoordinates = function (lon, lat, frame, epoch, motion, to, toFrame) {
+        return this.wasm.propagateCoordinates(
+            lon,
+            lat,
+            toCooSystem(frame),
+            epoch,
+            motion.pmra,
+            motion.pmdec,
+            motion.parallax || 0.0,
+            motion.rv || 0.0,
+            to,
+            toCooSystem(toFrame, undefined)
+        );
+    };
+
     /**
      * Sets the projection of the Aladin instance to the specified type.
      *
@@ -957,23 +1054,73 @@ export let Aladin = (function () {
      * <br>"STG" (Stereographic projection)
      * <br>"SIN" (Orthographic projection)
      * <br>"ZEA" (Zenital equal-area projection)
+     * <br>"FEYE" (Fish eye projection)
+     * <br>"AIR" (Airy projection)
+     * <br>"AZP" (Zenithal perspective projection)
+     * <br>"ARC" (Zenithal equidistant projection)
+     * <br>"NCP" (North celestial pole projection)
      * <br>"MER" (Mercator projection)
+     * <br>"CAR" (Plate carrée projection)
+     * <br>"CEA" (Cylindrical equal area projection)
+     * <br>"CYP" (Cylindrical perspective projection)
      * <br>"AIT" (Hammer-Aitoff projection)
+     * <br>"PAR" (Parabolic projection)
+     * <br>"SFL" (Sanson-Flamsteed projection)
      * <br>"MOL" (Mollweide projection)
+     * <br>"COD" (Conic equidistant projection)
+     * <br>"HPX" (HEALPix projection)
+     * <br>"ZPN" (Zenithal polynomial projection)
+     * @param {Object} [params] - The projection parameters, i.e. the values of the FITS PV2_m keywords. Angles are in degrees.
+     * Parameters not given take the default values of the FITS WCS standard.
+     * @param {number} [params.mu] - Distance of the point of projection from the sphere center in spherical radii (AZP, CYP)
+     * @param {number} [params.gamma] - Tilt angle of the projection plane (AZP)
+     * @param {number} [params.thetaB] - Latitude of the minimisation of the error (AIR)
+     * @param {number} [params.lambda] - Radius of the cylinder in spherical radii (CEA, CYP)
+     * @param {number} [params.thetaA] - Mean of the two standard parallels (COD)
+     * @param {number} [params.eta] - Half of the angle between the two standard parallels (COD)
+     * @param {number[]} [params.coeffs] - Polynomial coefficients P0, P1, ..., P20 (ZPN)
      *
      * @example
      * // Set the projection to 'orthographic'
      * let aladin = A.aladin('#aladin-lite-div');
      * aladin.setProjection('SIN');
+     * // A conic equidistant projection with standard parallels at 20 and 60 degrees
+     * aladin.setProjection('COD', {thetaA: 40, eta: 20});
      */
-    Aladin.prototype.setProjection = function (projection) {
+    Aladin.prototype.setProjection = function (projection, params) {
         if (!projection) {
             return;
         }
-        this.view.setProjection(projection);
+        this.view.setProjection(projection, params);
 
         ALEvent.PROJECTION_CHANGED.dispatchedTo(this.aladinDiv, {
             projection: projection,
+            params: params,
+        });
+    };
+
+    /**
+     * Make the view adopt the WCS of a FITS image layer.
+     *
+     * The view takes the projection (with its PV parameters), the frame, the reference point (CRVAL)
+     * and the orientation of the image WCS. It is zoomed so that one image pixel covers one screen pixel.
+     * HiPS, MOCs and catalogs are then reprojected onto that WCS.
+     *
+     * @memberof Aladin
+     * @param {string} [layer="base"] - The name of the layer containing the FITS image
+     *
+     * @example
+     * let aladin = A.aladin('#aladin-lite-div');
+     * aladin.setOverlayImageLayer(A.imageFITS('https://fits.gsfc.nasa.gov/samples/FOCx38i0101t_c0f.fits'), 'qa');
+     * // Once the image is loaded
+     * aladin.setNativeWCS('qa');
+     */
+    Aladin.prototype.setNativeWCS = function (layer = "base") {
+        const wcs = this.view.setNativeWCS(layer);
+
+        ALEvent.PROJECTION_CHANGED.dispatchedTo(this.aladinDiv, {
+            projection: wcs.projection,
+            params: wcs.params,
         });
     };
 
@@ -1421,6 +1568,43 @@ export let Aladin = (function () {
         return this.view.displayHpxGrid;
     };
 
+    /**
+     * Sets the options of the HEALPix grid.
+     *
+     * The grid draws the edges of the HEALPix cells in view at the order of the tiles.
+     *
+     * @memberof Aladin
+     * @param {Object} options - Options to customize the HEALPix grid.
+     * @param {string} [options.color] - The color of the cell edges.
+     * @param {number} [options.opacity] - The opacity of the grid (value between 0 and 1).
+     * @param {number} [options.thickness] - The thickness of the cell edges.
+     * @param {boolean} [options.showLabels] - If true, the cells are labelled with their norder/npix index.
+     * @param {number} [options.labelSize] - The size of the labels in pixels.
+     * @param {CooFrame|string} [options.frame] - The frame in which the HEALPix tessellation is defined. ICRS by default.
+     * @param {boolean} [options.enabled] - If true, the grid is drawn.
+     *
+     * @example
+     * aladin.setHEALPixGridOptions({ enabled: true, frame: 'galactic', showLabels: false });
+     */
+    Aladin.prototype.setHEALPixGridOptions = function (options) {
+        options = {...options};
+        if (options.color) {
+            options.color = new Color(options.color);
+            // convert from 0-255 to 0-1
+            options.color.r /= 255;
+            options.color.g /= 255;
+            options.color.b /= 255;
+        }
+
+        if (options.frame) {
+            // The core expects the name of the coordinate system
+            const CooSystem = Aladin.wasmLibs.core.CooSystem;
+            options.frame = CooSystem[toCooSystem(options.frame)];
+        }
+
+        this.view.setHEALPixGridOptions(options);
+    };
+
     Aladin.prototype.showSurvey = function (show) {
         this.view.showSurvey(show);
     };
@@ -1479,6 +1663,83 @@ export let Aladin = (function () {
         return result[0];
     };
 
+    /**
+     * Get the MOCs covering a position of the view, e.g. to display a tooltip over overlapping footprints
+     *
+     * The origin (0,0) of pixel coordinates is at the top-left corner of the Aladin Lite view.
+     * Hidden MOCs are ignored.
+     *
+     * @memberof Aladin
+     * @param {number} x - The x-coordinate in pixel coordinates.
+     * @param {number} y - The y-coordinate in pixel coordinates.
+     *
+     * @returns {Object[]} - The MOCs containing the position, from the one drawn on top. Each item gives
+     * the MOC (<code>moc</code>), the order (<code>order</code>) and the index (<code>ipix</code>) of its HEALPix cell
+     * containing the position, and the fraction of the sky covered by that cell (<code>skyFraction</code>)
+     */
+    Aladin.prototype.pickMOCs = function (x, y) {
+        return this.view.wasm.pickMOCs(x, y).map(({uuid, ...cell}) => {
+            return {moc: this.findLayerByUUID(uuid), ...cell};
+        });
+    };
+
+    /**
+     * Add a HiPS layer from its url, its <code>properties</code> file being fetched and parsed by the core
+     *
+     * @memberof Aladin
+     * @param {string} url - The base url of the HiPS, or the url of its <code>properties</code> file
+     * @param {string} [layer="overlay"] - The name of the layer
+     * @param {ImageHiPSOptions} [options] - The options of the layer. If <code>imgFormat</code> is not given,
+     * the first format provided by the HiPS among <code>jpeg</code>, <code>png</code>, <code>fits</code> and <code>webp</code> is chosen.
+     * <code>errorCallback</code> is called if the <code>properties</code> file cannot be fetched, is malformed or describes a HiPS that cannot be displayed
+     *
+     * @returns {ImageHiPS} - The layer added. Once added, its <code>properties</code> field stores the key/value pairs of the <code>properties</code> file
+     */
+    Aladin.prototype.addHiPSFromURL = function (url, layer = "overlay", options) {
+        const hips = ImageHiPS.fromURL(url, options);
+
+        return this.view.setOverlayImageLayer(hips, layer);
+    };
+
+    /**
+     * Set the memory budget of the cache keeping the downloaded tiles not displayed yet
+     *
+     * The least recently used tiles are evicted once the budget is exceeded.
+     *
+     * @memberof Aladin
+     * @param {number} budget - The budget in bytes
+     */
+    Aladin.prototype.setResourceCacheBudget = function (budget) {
+        this.view.wasm.setResourceCacheBudget(budget);
+    };
+
+    /**
+     * Get the use of the cache keeping the downloaded tiles not displayed yet
+     *
+     * @memberof Aladin
+     *
+     * @returns {Object} - The memory budget (<code>budget</code>) and the memory used (<code>size</code>) in bytes,
+     * the number of cached resources (<code>numEntries</code>) and the number of cache hits (<code>hits</code>) and misses (<code>misses</code>)
+     */
+    Aladin.prototype.getResourceCacheStats = function () {
+        return this.view.wasm.getResourceCacheStats();
+    };
+
+    /**
+     * Set the number of tile requests that can be in flight to a host
+     *
+     * The tiles are fetched from the center of the view and the requests of the tiles
+     * leaving the view are cancelled.
+     *
+     * @memberof Aladin
+     * @param {number} max - The number of requests, at least 1. The default is 8
+     * @param {string} [host] - The host, e.g. <code>alasky.cds.unistra.fr</code>. If not given,
+     * the number is set for all the hosts that have not been given one
+     */
+    Aladin.prototype.setTileRequestConcurrency = function (max, host) {
+        this.view.wasm.setTileRequestConcurrency(max, host);
+    };
+
     /**
      * Remove all the overlays (MOC, Overlay, ProgressiveCat, Catalog) from the view
      * @memberof Aladin
@@ -2095,6 +2356,54 @@ aladin.on("positionChanged", ({ra, dec}) => {
         this.setCooGrid({ enabled: false });
     };
 
+    /**
+     * Sets the options of the Tissot indicatrices overlay.
+     *
+     * The overlay draws, on a regular grid of the view, the ellipses into which small circles of the sky
+     * are mapped by the current projection. It shows how the projection distorts the sky across the view.
+     *
+     * @memberof Aladin
+     * @param {Object} options - Options of the overlay.
+     * @param {string} [options.color] - The color of the ellipses.
+     * @param {number} [options.opacity] - The opacity of the ellipses (value between 0 and 1).
+     * @param {number} [options.thickness] - The thickness of the ellipses lines.
+     * @param {number} [options.num] - The number of ellipses along the width of the view.
+     * @param {boolean} [options.enabled] - If true, the overlay is drawn.
+     *
+     * @example
+     * aladin.setTissotOptions({ enabled: true, color: 'orange', num: 8 });
+     */
+    Aladin.prototype.setTissotOptions = function (options) {
+        if (options.color) {
+            options.color = new Color(options.color);
+            // convert from 0-255 to 0-1
+            options.color.r /= 255;
+            options.color.g /= 255;
+            options.color.b /= 255;
+        }
+
+        this.view.setTissotOptions(options);
+    };
+
+    /**
+     * Gets how the current projection locally distorts the sky at a pixel position of the view.
+     *
+     * @memberof Aladin
+     * @param {number} x - The x pixel coordinate.
+     * @param {number} y - The y pixel coordinate.
+     *
+     * @returns {Object|null} - null if the position is out of the projection, otherwise an object with:
+     * scaleX and scaleY, the pixel scales along the x and y axes in arcsec/px;
+     * areaScale, the area scale factor relative to the projection center;
+     * northAngle and eastAngle, the angles in radians of the local north and east of the view frame,
+     * counted counterclockwise from the x axis;
+     * tissot, the Tissot indicatrix with its semiMajor and semiMinor scale factors relative to the projection
+     * center and the angle in radians of its major axis.
+     */
+    Aladin.prototype.getDistortion = function (x, y) {
+        return this.view.getDistortion(x, y);
+    };
+
     Aladin.prototype.layerByName = function (name) {
         var c = this.view.allOverlayLayers;
         for (var k = 0; k < c.length; k++) {
@@ -2335,6 +2644,75 @@ aladin.on("positionChanged", ({ra, dec}) => {
         return this.view.wasm.world2pix(lon, lat, frame);
     };
 
+    // Get the backend coordinate system of a frame given as a string or a CooFrame
+    var toCooSystem = function (frame, defaultFrame) {
+        if (!frame) {
+            return defaultFrame;
+        }
+
+        if (typeof frame === 'string') {
+            frame = CooFrameEnum.fromString(frame, null);
+            if (!frame) {
+                throw 'Unknown frame. Accepted frames are icrs, galactic, ecliptic, supergalactic, fk5, fk4 and altaz';
+            }
+        }
+
+        const CooSystem = Aladin.wasmLibs.core.CooSystem;
+        switch (frame.system) {
+            case CooFrameEnum.SYSTEMS.GAL: return CooSystem.GAL;
+            case CooFrameEnum.SYSTEMS.ECL: return CooSystem.ECL;
+            case CooFrameEnum.SYSTEMS.SGAL: return CooSystem.SGAL;
+            case CooFrameEnum.SYSTEMS.FK5: return CooSystem.FK5J2000;
+            case CooFrameEnum.SYSTEMS.FK4: return CooSystem.FK4B1950;
+            case CooFrameEnum.SYSTEMS.ALTAZ: return CooSystem.ALTAZ;
+            default: return CooSystem.ICRS;
+        }
+    };
+
+    /**
+     * Project a batch of world positions onto the view.
+     *
+     * @memberof Aladin
+     * @param {Float64Array|number[]} lon - Longitudes in degrees.
+     * @param {Float64Array|number[]} lat - Latitudes in degrees.
+     * @param {CooFrame|string} [frame] - The frame of the positions. If not specified, ICRS is used
+     * @param {Uint8Array} [mask] - If given, filled with 1 for the projected positions and 0 for the ones
+     * lying behind the boundary of the projection. It must have the same length as `lon`
+     *
+     * @returns {Float64Array} - The interleaved [x0, y0, x1, y1, ...] pixel coordinates in the view.
+     * Positions that cannot be projected are given as NaN.
+     *
+     * @example
+     * const mask = new Uint8Array(ra.length);
+     * const xy = aladin.worldToScreen(ra, dec, 'galactic', mask);
+     */
+    Aladin.prototype.worldToScreen = function (lon, lat, frame, mask) {
+        lon = lon instanceof Float64Array ? lon : new Float64Array(lon);
+        lat = lat instanceof Float64Array ? lat : new Float64Array(lat);
+
+        return this.view.wasm.worldToScreen(lon, lat, toCooSystem(frame, undefined), mask);
+    };
+
+    /**
+     * Unproject a batch of pixel positions of the view.
+     *
+     * @memberof Aladin
+     * @param {Float64Array|number[]} x - The x pixel coordinates.
+     * @param {Float64Array|number[]} y - The y pixel coordinates.
+     * @param {CooFrame|string} [frame] - The frame of the returned positions. If not specified, the frame of the view is used
+     * @param {Uint8Array} [mask] - If given, filled with 1 for the unprojected positions and 0 for the ones
+     * lying out of the projection. It must have the same length as `x`
+     *
+     * @returns {Float64Array} - The interleaved [lon0, lat0, lon1, lat1, ...] positions in degrees.
+     * Pixels that cannot be unprojected are given as NaN.
+     */
+    Aladin.prototype.screenToWorld = function (x, y, frame, mask) {
+        x = x instanceof Float64Array ? x : new Float64Array(x);
+        y = y instanceof Float64Array ? y : new Float64Array(y);
+
+        return this.view.wasm.screenToWorld(x, y, toCooSystem(frame, undefined), mask);
+    };
+
     /**
      * Get the angular distance in degrees between two locations
      *
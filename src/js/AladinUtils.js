@@ -29,6 +29,20 @@
  *****************************************************************************/
 import { Aladin } from "./Aladin";
 
+let coreWasm = function() {
+    let wasm = Aladin.wasmLibs.core;
+    if (!wasm) {
+        throw 'A.init must be called first'
+    }
+
+    return wasm;
+};
+
+// Cast to 1d array
+let toArray = function(v) {
+    return Array.isArray(v) || ArrayBuffer.isView(v) ? v : [v];
+};
+
 /**
  * @namespace AladinUtils
  * @description Aladin Lite utils API namespace for basic functions
@@ -142,6 +156,213 @@ export let AladinUtils = {
 
                 const lonlat = wasm.HEALPixPix2Ang(nside, ipix)
                 return lonlat;
+            },
+
+            /**
+             * Computes the 8 neighbours of HEALPix cells.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name neighbours
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - Pixel index or an array of pixel indices.
+             * @returns {BigInt64Array} - 8 neighbours per cell in the order S, SE, E, SW, NE, W, NW, N. A missing neighbour is -1.
+             */
+            neighbours: function(nside, ipix) {
+                return coreWasm().HEALPixNeighbours(nside, toArray(ipix));
+            },
+
+            /**
+             * Computes the parent cells, of NSIDE nside/2.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name parent
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - Pixel index or an array of pixel indices.
+             * @returns {BigUint64Array} - The parent of each cell.
+             */
+            parent: function(nside, ipix) {
+                return coreWasm().HEALPixParent(nside, toArray(ipix));
+            },
+
+            /**
+             * Computes the children cells, of NSIDE 2*nside.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name children
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - Pixel index or an array of pixel indices.
+             * @returns {BigUint64Array} - The 4 children of each cell.
+             */
+            children: function(nside, ipix) {
+                return coreWasm().HEALPixChildren(nside, toArray(ipix));
+            },
+
+            /**
+             * Converts NESTED pixel indices to RING ones.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name nest2ring
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - NESTED pixel index or an array of them.
+             * @returns {BigUint64Array} - The RING pixel indices.
+             */
+            nest2ring: function(nside, ipix) {
+                return coreWasm().HEALPixNestToRing(nside, toArray(ipix));
+            },
+
+            /**
+             * Converts RING pixel indices to NESTED ones.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name ring2nest
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - RING pixel index or an array of them.
+             * @returns {BigUint64Array} - The NESTED pixel indices.
+             */
+            ring2nest: function(nside, ipix) {
+                return coreWasm().HEALPixRingToNest(nside, toArray(ipix));
+            },
+
+            /**
+             * Encodes NESTED cells into UNIQ indices.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name toUniq
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} ipix - Pixel index or an array of pixel indices.
+             * @returns {BigUint64Array} - The UNIQ indices, i.e. 4*nside^2 + ipix.
+             */
+            toUniq: function(nside, ipix) {
+                return coreWasm().HEALPixToUniq(nside, toArray(ipix));
+            },
+
+            /**
+             * Decodes UNIQ indices into NESTED cells.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name fromUniq
+             *
+             * @param {number | number[]} uniq - UNIQ index or an array of them.
+             * @returns {BigUint64Array} - The interleaved [nside0, ipix0, nside1, ipix1, ...] cells.
+             */
+            fromUniq: function(uniq) {
+                return coreWasm().HEALPixFromUniq(toArray(uniq));
+            },
+
+            /**
+             * Gets the cells overlapping a cone.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name queryCone
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number} lon - Longitude of the center in radians.
+             * @param {number} lat - Latitude of the center in radians.
+             * @param {number} radius - Radius in radians, clamped to [0, π].
+             * @returns {BigUint64Array} - The NESTED pixel indices.
+             */
+            queryCone: function(nside, lon, lat, radius) {
+                return coreWasm().HEALPixQueryCone(nside, lon, lat, radius);
+            },
+
+            /**
+             * Gets the cells overlapping an elliptical cone.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name queryEllipse
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number} lon - Longitude of the center in radians.
+             * @param {number} lat - Latitude of the center in radians.
+             * @param {number} a - Semi-major axis in radians.
+             * @param {number} b - Semi-minor axis in radians.
+             * @param {number} pa - Position angle in radians, counted from the north towards the east.
+             * @returns {BigUint64Array} - The NESTED pixel indices.
+             */
+            queryEllipse: function(nside, lon, lat, a, b, pa) {
+                return coreWasm().HEALPixQueryEllipse(nside, lon, lat, a, b, pa);
+            },
+
+            /**
+             * Gets the cells overlapping a box.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name queryBox
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number} lon - Longitude of the center in radians.
+             * @param {number} lat - Latitude of the center in radians.
+             * @param {number} a - Half width of the box in radians.
+             * @param {number} b - Half height of the box in radians.
+             * @param {number} pa - Position angle in radians, counted from the north towards the east.
+             * @returns {BigUint64Array} - The NESTED pixel indices.
+             */
+            queryBox: function(nside, lon, lat, a, b, pa) {
+                return coreWasm().HEALPixQueryBox(nside, lon, lat, a, b, pa);
+            },
+
+            /**
+             * Gets the cells overlapping a polygon.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name queryPolygon
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number[]} lon - Longitudes of the vertices in radians.
+             * @param {number[]} lat - Latitudes of the vertices in radians.
+             * @returns {BigUint64Array} - The NESTED pixel indices.
+             */
+            queryPolygon: function(nside, lon, lat) {
+                return coreWasm().HEALPixQueryPolygon(nside, lon, lat);
+            },
+
+            /**
+             * Computes the cells and weights of a bilinear interpolation.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name bilinearInterpolation
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number | number[]} lon - Longitude or an array of longitudes in radians.
+             * @param {number | number[]} lat - Latitude or an array of latitudes in radians.
+             * @returns {Object[]} - 4 objects per position, each having an ipix and a weight property.
+             */
+            bilinearInterpolation: function(nside, lon, lat) {
+                return coreWasm().HEALPixBilinearInterpolation(nside, toArray(lon), toArray(lat));
+            },
+
+            /**
+             * Computes a path along the edge of a cell, starting from its south vertex.
+             *
+             * @function
+             * @memberof AladinUtils.HEALPix
+             * @name pathAlongCellEdge
+             *
+             * @param {number} nside - NSIDE parameter for the HEALPix grid.
+             * @param {number} ipix - Pixel index.
+             * @param {number} numSegmentsBySide - Number of segments along each side of the cell.
+             * @returns {LonLat[]} - The 4*numSegmentsBySide vertices in radians.
+             */
+            pathAlongCellEdge: function(nside, ipix, numSegmentsBySide) {
+                return coreWasm().HEALPixPathAlongCellEdge(nside, BigInt(ipix), numSegmentsBySide);
             }
         },
 
@@ -62,6 +62,12 @@ export let Catalog = (function () {
      * @param {boolean} [options.readOnly=false] - Whether the catalog is read-only.
      * @param {string} [options.raField] - The ID or name of the field holding Right Ascension (RA).
      * @param {string} [options.decField] - The ID or name of the field holding Declination (dec).
+     * @param {number} [options.epoch=2000] - The epoch of the source positions as a Julian year (e.g. 2016 for Gaia DR3).
+     * @param {string} [options.pmraField] - The name of the field holding the proper motion in RA (including the cos(dec) factor) in mas/yr.
+     * @param {string} [options.pmdecField] - The name of the field holding the proper motion in dec in mas/yr.
+     * @param {string} [options.parallaxField] - The name of the field holding the parallax in mas.
+     * @param {string} [options.rvField] - The name of the field holding the radial velocity in km/s.
+     * If the proper motion fields are given, the sources are drawn at the epoch set by {@link Aladin#setCatalogEpoch}.
      * @param {function} [options.filter] - The filtering function for sources.
      * @param {string} [options.selectionColor] - The color to apply to selected sources in the catalog.
      * @param {string} [options.hoverColor] - The color to apply to sources in the catalog when they are hovered.
@@ -110,6 +116,13 @@ export let Catalog = (function () {
         this.raField = options.raField || undefined; // ID or name of the field holding RA
         this.decField = options.decField || undefined; // ID or name of the field holding dec
 
+        // Space motion of the sources, to draw them at another epoch
+        this.epoch = options.epoch || 2000;
+        this.pmraField = options.pmraField || undefined;
+        this.pmdecField = options.pmdecField || undefined;
+        this.parallaxField = options.parallaxField || undefined;
+        this.rvField = options.rvField || undefined;
+
         // allows for filtering of sources
         this.filterFn = options.filter || undefined; // TODO: do the same for catalog
         this.selectionColor = options.selectionColor || "#00ff00";
@@ -133,6 +146,10 @@ export let Catalog = (function () {
         this.sources = [];
         this.ra = [];
         this.dec = [];
+        this.pmra = [];
+        this.pmdec = [];
+        this.parallax = [];
+        this.rv = [];
         this.footprints = [];
 
         // create this.cacheCanvas
@@ -552,6 +569,15 @@ export let Catalog = (function () {
             // Create columns oriented ra and dec
             this.ra.push(sources[k].ra);
             this.dec.push(sources[k].dec);
+
+            if (this.hasSpaceMotion()) {
+                const data = sources[k].data;
+                // Unknown values are given as NaN
+                this.pmra.push(parseFloat(data[this.pmraField]));
+                this.pmdec.push(parseFloat(data[this.pmdecField]));
+                this.parallax.push(parseFloat(data[this.parallaxField]));
+                this.rv.push(parseFloat(data[this.rvField]));
+            }
         }
 
         this.recomputeFootprints = true;
@@ -701,6 +727,17 @@ export let Catalog = (function () {
         }
     };
 
+    /**
+     * Tell whether the proper motions of the sources are known
+     *
+     * @memberof Catalog
+     *
+     * @returns {boolean} - True if the proper motion fields have been given
+     */
+    Catalog.prototype.hasSpaceMotion = function () {
+        return this.pmraField !== undefined && this.pmdecField !== undefined;
+    };
+
     Catalog.prototype.setView = function (view) {
         this.view = view;
         this.reportChange();
@@ -748,6 +785,12 @@ export let Catalog = (function () {
 
         this.ra.splice(idx, 1);
         this.dec.splice(idx, 1);
+        if (this.hasSpaceMotion()) {
+            this.pmra.splice(idx, 1);
+            this.pmdec.splice(idx, 1);
+            this.parallax.splice(idx, 1);
+            this.rv.splice(idx, 1);
+        }
 
         this.recomputeFootprints = true;
 
@@ -759,6 +802,10 @@ export let Catalog = (function () {
         this.sources = [];
         this.ra = [];
         this.dec = [];
+        this.pmra = [];
+        this.pmdec = [];
+        this.parallax = [];
+        this.rv = [];
         this.footprints = [];
     };
 
@@ -799,11 +846,25 @@ export let Catalog = (function () {
             return;
         }
 
-        let xy = this.view.wasm.worldToScreenVec(this.ra, this.dec);
+        let xy;
+        if (this.hasSpaceMotion()) {
+            // The sources are drawn at the epoch of the view
+            xy = this.view.wasm.worldToScreenVecAtEpoch(
+                this.ra,
+                this.dec,
+                this.epoch,
+                this.pmra,
+                this.pmdec,
+                this.parallax,
+                this.rv
+            );
+        } else {
+            xy = this.view.wasm.worldToScreenVec(this.ra, this.dec);
+        }
 
         let self = this;
         this.sources.forEach(function (s, idx) {
-            if (xy[2 * idx] && xy[2 * idx + 1]) {
+            if (Number.isFinite(xy[2 * idx]) && Number.isFinite(xy[2 * idx + 1])) {
                 if (!self.filterFn || self.filterFn(s)) {
                     s.x = xy[2 * idx];
                     s.y = xy[2 * idx + 1];
@@ -30,13 +30,18 @@
 
 export let CooFrameEnum = (function() {
 
-    var systems = {J2000: 'J2000', GAL: 'Galactic'};
+    var systems = {J2000: 'J2000', GAL: 'Galactic', ECL: 'Ecliptic', SGAL: 'Supergalactic', FK5: 'FK5', FK4: 'FK4', ALTAZ: 'AltAz'};
     return {
         SYSTEMS: systems,
 
         J2000: {label: "J2000", system: systems.J2000},
         J2000d: {label: "J2000d", system: systems.J2000},
         GAL:  {label: "Galactic", system: systems.GAL},
+        ECL:  {label: "Ecliptic", system: systems.ECL},
+        SGAL: {label: "Supergalactic", system: systems.SGAL},
+        FK5:  {label: "FK5", system: systems.FK5},
+        FK4:  {label: "FK4", system: systems.FK4},
+        ALTAZ: {label: "AltAz", system: systems.ALTAZ},
 
         fromString: function(str, defaultValue) {
             if (! str) {
@@ -54,6 +59,21 @@ export let CooFrameEnum = (function() {
             else if (str.indexOf('gal')==0) {
                 return CooFrameEnum.GAL;
             }
+            else if (str.indexOf('ecl')==0) {
+                return CooFrameEnum.ECL;
+            }
+            else if (str.indexOf('supergal')==0 || str.indexOf('sgal')==0) {
+                return CooFrameEnum.SGAL;
+            }
+            else if (str.indexOf('fk5')==0) {
+                return CooFrameEnum.FK5;
+            }
+            else if (str.indexOf('fk4')==0 || str.indexOf('b1950')==0) {
+                return CooFrameEnum.FK4;
+            }
+            else if (str.indexOf('altaz')==0 || str.indexOf('hor')==0) {
+                return CooFrameEnum.ALTAZ;
+            }
             else {
                 return defaultValue ? defaultValue : null;
             }
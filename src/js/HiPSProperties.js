@@ -134,6 +134,24 @@ HiPSProperties.fetchFromUrl = async function(urlOrId) {
     return result;
 }
 
+// The urls of the mirrors of a HiPS service i.e. the hips_service_url, hips_service_url_1, ... values
+HiPSProperties.getServiceUrls = function (metadata) {
+    let urls = [];
+    if (metadata.hips_service_url) {
+        urls.push(metadata.hips_service_url);
+    }
+
+    let numHiPSServiceURL = 1;
+    while (metadata.hasOwnProperty("hips_service_url_" + numHiPSServiceURL.toString())) {
+        const key = "hips_service_url_" + numHiPSServiceURL.toString();
+
+        urls.push(metadata[key]);
+        numHiPSServiceURL += 1;
+    }
+
+    return urls;
+}
+
 HiPSProperties.getFasterMirrorUrl = function (metadata, currUrl) {
     const pingHiPSServiceUrl = async (baseUrl) => {
         baseUrl = Utils.fixURLForHTTPS(baseUrl);
@@ -167,21 +185,8 @@ HiPSProperties.getFasterMirrorUrl = function (metadata, currUrl) {
     };
 
     // Get all the possible hips_service_url urls
-    let promises = [];
-    let urls = [metadata.hips_service_url];
-
-    promises.push(pingHiPSServiceUrl(metadata.hips_service_url));
-
-    let numHiPSServiceURL = 1;
-    while (metadata.hasOwnProperty("hips_service_url_" + numHiPSServiceURL.toString())) {
-        const key = "hips_service_url_" + numHiPSServiceURL.toString();
-
-        let curUrl = metadata[key];
-        promises.push(pingHiPSServiceUrl(curUrl))
-        numHiPSServiceURL += 1;
-
-        urls.push(curUrl)
-    }
+    let urls = HiPSProperties.getServiceUrls(metadata);
+    let promises = urls.map((url) => pingHiPSServiceUrl(url));
 
     return Promise.all(promises)
         .then((responses) => {
@@ -204,6 +204,12 @@ export let ImageHiPS = (function () {
 
         self.view = view;
 
+        if (self.propertiesFromCore) {
+            // The properties file is fetched and parsed by the core when the layer is added
+            self.query = Promise.resolve(self);
+            return;
+        }
+
         let isMOCServerToBeQueried = true;
         if (this.imgFormat === "fits") {
             // a fits is given
@@ -250,9 +256,7 @@ export let ImageHiPS = (function () {
                         }
                     });
 
-                //obsTitle = properties.obs_title;
-                self.creatorDid = properties.creator_did || self.creatorDid;
-                // url
+                self._setProperties(properties);
 
                 if (isCDSId) {
                     self.url = properties.hips_service_url;
@@ -294,164 +298,197 @@ export let ImageHiPS = (function () {
                             console.error(e);
                         });
                 }
+            }
 
-                // Max order
-                self.maxOrder =
-                    PropertyParser.maxOrder(properties) || self.maxOrder;
-
-                // Tile size
-                self.tileSize =
-                    PropertyParser.tileSize(properties) || self.tileSize;
-
-                // Tile formats
-                self.formats =
-                    PropertyParser.formats(properties) || self.formats;
-
-                // min order
-                self.minOrder =
-                    PropertyParser.minOrder(properties) || self.minOrder;
-
-                // Frame
-                self.cooFrame =
-                    PropertyParser.cooFrame(properties) || self.cooFrame;
-
-                // sky fraction
-                self.skyFraction = PropertyParser.skyFraction(properties);
-
-                // Initial fov/ra/dec
-                self.initialFov = PropertyParser.initialFov(properties);
-                self.initialRa =
-                    properties &&
-                    properties.hips_initial_ra &&
-                    +properties.hips_initial_ra;
-                self.initialDec =
-                    properties &&
-                    properties.hips_initial_dec &&
-                    +properties.hips_initial_dec;
-
-                // Cutouts
-                const cutoutFromProperties = PropertyParser.cutouts(properties);
-                self.minCut = cutoutFromProperties[0];
-                self.maxCut = cutoutFromProperties[1];
-
-                // Bitpix
-                self.numBitsPerPixel =
-                    PropertyParser.bitpix(properties) || self.numBitsPerPixel;
-
-                // HiPS body
-                if (properties.hips_body) {
-                    self.hipsBody = properties.hips_body;
-                    // Use the property to define and check some user given infos
-                    // Longitude reversed
-                    self.longitudeReversed = true;
-                }
+            self._fillMissingMetadata(["webp", "png", "jpeg", "fits"]);
 
-                // Give a better name if we have the HiPS metadata
-                self.name = self.name || properties.obs_title;
-            }
+            return self;
+        })()
+    };
 
-            self.name = self.name || self.id || self.url;
-            self.name = self.name.replace(/  +/g, ' ');
+    // Fill the fields of the survey from the key/value pairs of its properties file
+    ImageHiPS.prototype._setProperties = function (properties) {
+        let self = this;
 
-            self.creatorDid = self.creatorDid || self.id || self.url;
+        //obsTitle = properties.obs_title;
+        self.creatorDid = properties.creator_did || self.creatorDid;
+        // The mirrors the tiles can be fetched from if the current url fails
+        self.mirrors = HiPSProperties.getServiceUrls(properties).map(
+            (url) => Utils.fixURLForHTTPS(url)
+        );
+
+        // Max order
+        self.maxOrder =
+            PropertyParser.maxOrder(properties) || self.maxOrder;
+
+        // Tile size
+        self.tileSize =
+            PropertyParser.tileSize(properties) || self.tileSize;
+
+        // Tile formats
+        self.formats =
+            PropertyParser.formats(properties) || self.formats;
+
+        // min order
+        self.minOrder =
+            PropertyParser.minOrder(properties) || self.minOrder;
+
+        // Frame
+        self.cooFrame =
+            PropertyParser.cooFrame(properties) || self.cooFrame;
+
+        // sky fraction
+        self.skyFraction = PropertyParser.skyFraction(properties);
+
+        // Initial fov/ra/dec
+        self.initialFov = PropertyParser.initialFov(properties);
+        self.initialRa =
+            properties &&
+            properties.hips_initial_ra &&
+            +properties.hips_initial_ra;
+        self.initialDec =
+            properties &&
+            properties.hips_initial_dec &&
+            +properties.hips_initial_dec;
+
+        // HiPS cube slices and their world coordinates along the third axis
+        self.cubeDepth =
+            properties.hips_cube_depth && +properties.hips_cube_depth;
+        self.cubeFirstFrame =
+            properties.hips_cube_firstframe &&
+            +properties.hips_cube_firstframe;
+        self.cubeCrpix3 =
+            properties.hips_cube_crpix3 && +properties.hips_cube_crpix3;
+        self.cubeCrval3 =
+            properties.hips_cube_crval3 && +properties.hips_cube_crval3;
+        self.cubeCdelt3 =
+            properties.hips_cube_cdelt3 && +properties.hips_cube_cdelt3;
+        self.cubeBunit3 = properties.hips_cube_bunit3;
+
+        // Cutouts
+        const cutoutFromProperties = PropertyParser.cutouts(properties);
+        self.minCut = cutoutFromProperties[0];
+        self.maxCut = cutoutFromProperties[1];
+
+        // Bitpix
+        self.numBitsPerPixel =
+            PropertyParser.bitpix(properties) || self.numBitsPerPixel;
+
+        // HiPS body
+        if (properties.hips_body) {
+            self.hipsBody = properties.hips_body;
+            // Use the property to define and check some user given infos
+            // Longitude reversed
+            self.longitudeReversed = true;
+        }
 
-            // Image format
-            if (self.imgFormat) {
-                // transform to lower case
-                self.imgFormat = self.imgFormat.toLowerCase();
-                // convert JPG -> JPEG
-                if (self.imgFormat === "jpg") {
-                    self.imgFormat = "jpeg";
-                }
+        // Give a better name if we have the HiPS metadata
+        self.name = self.name || properties.obs_title;
+    };
 
-                // user wants a fits but the properties tells this format is not available
-                if (
-                    self.imgFormat === "fits" &&
-                    self.formats &&
-                    self.formats.indexOf("fits") < 0
-                ) {
-                    throw self.name + " does not provide fits tiles";
-                }
+    // Check the metadata given by the user and fill the missing ones.
+    // When no image format is given, the first of formatsByPreference provided by the HiPS is taken
+    ImageHiPS.prototype._fillMissingMetadata = function (formatsByPreference) {
+        let self = this;
 
-                if (
-                    self.imgFormat === "webp" &&
-                    self.formats &&
-                    self.formats.indexOf("webp") < 0
-                ) {
-                    throw self.name + " does not provide webp tiles";
-                }
+        self.name = self.name || self.id || self.url;
+        self.name = self.name.replace(/  +/g, ' ');
 
-                if (
-                    self.imgFormat === "png" &&
-                    self.formats &&
-                    self.formats.indexOf("png") < 0
-                ) {
-                    throw self.name + " does not provide png tiles";
-                }
+        self.creatorDid = self.creatorDid || self.id || self.url;
 
-                if (
-                    self.imgFormat === "jpeg" &&
-                    self.formats &&
-                    self.formats.indexOf("jpeg") < 0
-                ) {
-                    throw self.name + " does not provide jpeg tiles";
-                }
-            } else {
-                // user wants nothing then we choose one from the properties
-                if (self.formats.indexOf("webp") >= 0) {
-                    self.imgFormat = "webp";
-                } else if (self.formats.indexOf("png") >= 0) {
-                    self.imgFormat = "png";
-                } else if (self.formats.indexOf("jpeg") >= 0) {
-                    self.imgFormat = "jpeg";
-                } else if (self.formats.indexOf("fits") >= 0) {
-                    self.imgFormat = "fits";
-                } else {
-                    throw (
-                        "Unsupported format(s) found in the properties: " +
-                        self.formats
-                    );
-                }
+        // Image format
+        if (self.imgFormat) {
+            // transform to lower case
+            self.imgFormat = self.imgFormat.toLowerCase();
+            // convert JPG -> JPEG
+            if (self.imgFormat === "jpg") {
+                self.imgFormat = "jpeg";
             }
 
-            // Cutouts
-            let minCut, maxCut;
-            if (self.imgFormat === "fits") {
-                // Take into account the default cuts given by the property file (this is true especially for FITS HiPSes)
-                minCut = self.colorCfg.minCut || self.minCut || 0.0;
-                maxCut = self.colorCfg.maxCut || self.maxCut || 1.0;
-            } else {
-                minCut = self.colorCfg.minCut || 0.0;
-                maxCut = self.colorCfg.maxCut || 1.0;
+            // user wants a fits but the properties tells this format is not available
+            if (
+                self.imgFormat === "fits" &&
+                self.formats &&
+                self.formats.indexOf("fits") < 0
+            ) {
+                throw self.name + " does not provide fits tiles";
             }
 
-            self.colorCfg.setCuts(minCut, maxCut);
+            if (
+                self.imgFormat === "webp" &&
+                self.formats &&
+                self.formats.indexOf("webp") < 0
+            ) {
+                throw self.name + " does not provide webp tiles";
+            }
+
+            if (
+                self.imgFormat === "png" &&
+                self.formats &&
+                self.formats.indexOf("png") < 0
+            ) {
+                throw self.name + " does not provide png tiles";
+            }
 
-            // Coo frame
             if (
-                self.cooFrame == "ICRS" ||
-                self.cooFrame == "ICRSd" ||
-                self.cooFrame == "equatorial" ||
-                self.cooFrame == "j2000"
+                self.imgFormat === "jpeg" &&
+                self.formats &&
+                self.formats.indexOf("jpeg") < 0
             ) {
-                self.cooFrame = "ICRS";
-            } else if (self.cooFrame == "galactic") {
-                self.cooFrame = "GAL";
-            } else {
-                self.cooFrame = "ICRS";
-                console.warn(
-                    "Invalid cooframe given: " +
-                        self.cooFrame +
-                        '. Coordinate systems supported: "ICRS", "ICRSd", "j2000" or "galactic". ICRS is chosen by default'
+                throw self.name + " does not provide jpeg tiles";
+            }
+        } else {
+            // user wants nothing then we choose one from the properties
+            self.imgFormat = formatsByPreference.find(
+                (format) => self.formats.indexOf(format) >= 0
+            );
+            if (!self.imgFormat) {
+                throw (
+                    "Unsupported format(s) found in the properties: " +
+                    self.formats
                 );
             }
+        }
 
-            self.formats = self.formats || [self.imgFormat];
+        // Cutouts
+        let minCut, maxCut;
+        if (self.imgFormat === "fits") {
+            // Take into account the default cuts given by the property file (this is true especially for FITS HiPSes)
+            minCut = self.colorCfg.minCut || self.minCut || 0.0;
+            maxCut = self.colorCfg.maxCut || self.maxCut || 1.0;
+        } else {
+            minCut = self.colorCfg.minCut || 0.0;
+            maxCut = self.colorCfg.maxCut || 1.0;
+        }
+
+        self.colorCfg.setCuts(minCut, maxCut);
+
+        // Coo frame
+        if (
+            self.cooFrame == "ICRS" ||
+            self.cooFrame == "ICRSd" ||
+            self.cooFrame == "equatorial" ||
+            self.cooFrame == "j2000"
+        ) {
+            self.cooFrame = "ICRS";
+        } else if (self.cooFrame == "galactic") {
+            self.cooFrame = "GAL";
+        } else if (self.cooFrame == "ecliptic") {
+            self.cooFrame = "ECL";
+        } else if (self.cooFrame == "supergalactic") {
+            self.cooFrame = "SGAL";
+        } else {
+            self.cooFrame = "ICRS";
+            console.warn(
+                "Invalid cooframe given: " +
+                    self.cooFrame +
+                    '. Coordinate systems supported: "ICRS", "ICRSd", "j2000", "galactic", "ecliptic" or "supergalactic". ICRS is chosen by default'
+            );
+        }
 
-            self._saveInCache();
+        self.formats = self.formats || [self.imgFormat];
 
-            return self;
-        })()
+        self._saveInCache();
     };
 
     ImageHiPS.prototype._saveInCache = function () {
@@ -785,6 +822,10 @@ export let ImageHiPS = (function () {
         this.layer = layer;
         let self = this;
 
+        if (this.propertiesFromCore) {
+            return this._addFromURL(layer);
+        }
+
         this.view.wasm.addImageHiPS({
             layer,
             properties: {
@@ -800,6 +841,13 @@ export let ImageHiPS = (function () {
                 hipsInitialFov: self.initialFov,
                 hipsInitialRa: self.initialRa,
                 hipsInitialDec: self.initialDec,
+                cubeDepth: self.cubeDepth,
+                cubeFirstFrame: self.cubeFirstFrame,
+                cubeCrpix3: self.cubeCrpix3,
+                cubeCrval3: self.cubeCrval3,
+                cubeCdelt3: self.cubeCdelt3,
+                cubeBunit3: self.cubeBunit3,
+                mirrors: self.mirrors,
                 isPlanetaryBody: self.isPlanetaryBody(),
                 hipsBody: self.hipsBody,
             },
@@ -820,6 +868,65 @@ export let ImageHiPS = (function () {
             });
     };
 
+    // Add the survey to the backend, its properties file being fetched and parsed by the core
+    ImageHiPS.prototype._addFromURL = function (layer) {
+        let self = this;
+
+        return this.view.wasm
+            .addImageHiPSFromURL(layer, this.url, {
+                ...this.colorCfg.get(),
+                longitudeReversed: this.longitudeReversed,
+                imgFormat: this.imgFormat,
+            })
+            .then((properties) => {
+                self.properties = properties;
+                self._setProperties(properties);
+                // The same format as the one chosen by the core when none is given
+                self._fillMissingMetadata(["jpeg", "png", "fits", "webp"]);
+
+                // The cuts of the FITS tiles and the orientation of the planetary bodies
+                // are only known once the properties have been parsed
+                self.view.wasm.setImageMetadata(layer, {
+                    ...self.colorCfg.get(),
+                    longitudeReversed: self.longitudeReversed,
+                    imgFormat: self.imgFormat,
+                });
+
+                if (self.successCallback) {
+                    self.successCallback(self);
+                }
+
+                return self;
+            });
+    };
+
+    /**
+     * Create a survey whose <code>properties</code> file is fetched and parsed by the core once it is added to a view
+     *
+     * @memberof ImageHiPS
+     *
+     * @param {string} url - The base url of the HiPS, or the url of its <code>properties</code> file
+     * @param {ImageHiPSOptions} [options] - The option for the survey. The ones describing the HiPS, e.g. <code>maxOrder</code>, are taken from the <code>properties</code> file
+     *
+     * @returns {ImageHiPS} - The survey. Once added, its <code>properties</code> field stores the key/value pairs of the <code>properties</code> file
+     */
+    ImageHiPS.fromURL = function (url, options) {
+        url = url.replace(/\/+$/, "");
+        if (url.endsWith("/properties")) {
+            url = url.slice(0, -"/properties".length);
+        }
+
+        let imgFormat = options && options.imgFormat && options.imgFormat.toLowerCase();
+        if (imgFormat === "jpg") {
+            imgFormat = "jpeg";
+        }
+
+        let hips = new ImageHiPS(url, url, { ...options, imgFormat });
+        hips.propertiesFromCore = true;
+
+        return hips;
+    };
+
     // @api
     ImageHiPS.prototype.toggle = function () {
         if (this.colorCfg.getOpacity() != 0.0) {
@@ -855,6 +962,32 @@ export let ImageHiPS = (function () {
         return this.view.wasm.readPixel(x, y, this.layer);
     };
 
+    /**
+     * Sets the slice of a HiPS cube to display
+     *
+     * The 3 slices displayed last are kept in the GPU so that going back to one of them does not download its tiles again.
+     * A HiPS cube can then use up to 4 times the GPU memory of a plain HiPS.
+     *
+     * @memberof ImageHiPS
+     *
+     * @param {number} index - Index of the slice, starting from 0
+     */
+    ImageHiPS.prototype.setCubeFrame = function (index) {
+        this.view.wasm.setHiPSCubeFrame(this.layer, index);
+    };
+
+    /**
+     * Gets the slices of a HiPS cube
+     *
+     * @memberof ImageHiPS
+     *
+     * @returns {Object|null} The number of slices (depth), the displayed one (frame) and, if given by the properties,
+     * the world coordinate of each slice along the third axis (frameCoos) with its unit. Null if the HiPS is not a cube.
+     */
+    ImageHiPS.prototype.getCube = function () {
+        return this.view.wasm.getHiPSCube(this.layer);
+    };
+
     ImageHiPS.DEFAULT_SURVEY_ID = "CDS/P/DSS2/color";
 
     return ImageHiPS;
@@ -96,6 +96,7 @@ export let MOC = (function() {
         this.isShowing = true;
         this.ready = false;
         this.skyFrac = undefined;
+        this.areaDeg2 = undefined;
     }
 
     /**
@@ -106,6 +107,13 @@ export let MOC = (function() {
         return this.skyFrac;
     };
 
+    /**
+     *  Return the area covered by the MOC in square degrees
+     */
+    MOC.prototype.area = function() {
+        return this.areaDeg2;
+    };
+
     /**
      * set MOC data by parsing a MOC serialized in JSON
      * (as defined in IVOA MOC document, section 3.1.1)
@@ -133,16 +141,81 @@ export let MOC = (function() {
             .then((data) => {
                 if (data instanceof ArrayBuffer) {
                     // from an url
-                    const buf = data;
-                    self.view.wasm.addFITSMoc(self.mocParams, new Uint8Array(buf));
+                    const buf = new Uint8Array(data);
+                    const magic = new TextDecoder().decode(buf.subarray(0, 6));
+                    if (magic === 'SIMPLE') {
+                        self.view.wasm.addFITSMoc(self.mocParams, buf);
+                    } else {
+                        // text serializations
+                        const text = new TextDecoder().decode(buf).trim();
+                        if (text.startsWith('{')) {
+                            self.view.wasm.addJSONMoc(self.mocParams, JSON.parse(text));
+                        } else {
+                            self.view.wasm.addASCIIMoc(self.mocParams, text);
+                        }
+                    }
+                } else if(data.ascii) {
+                    // ASCII serialization of a S-MOC, T-MOC or ST-MOC
+                    self.view.wasm.addASCIIMoc(self.mocParams, data.ascii);
+                } else if(data.operation) {
+                    // set operation between loaded MOCs
+                    const [first, second] = data.mocs.map((moc) => moc.mocParams);
+                    switch (data.operation) {
+                        case 'union':
+                            self.view.wasm.addMOCUnion(self.mocParams, first, second);
+                            break;
+                        case 'intersection':
+                            self.view.wasm.addMOCIntersection(self.mocParams, first, second);
+                            break;
+                        case 'difference':
+                            self.view.wasm.addMOCDifference(self.mocParams, first, second);
+                            break;
+                        case 'symmetricDifference':
+                            self.view.wasm.addMOCSymmetricDifference(self.mocParams, first, second);
+                            break;
+                        case 'complement':
+                            self.view.wasm.addMOCComplement(self.mocParams, first);
+                            break;
+                        default:
+                            throw 'Unknown MOC operation ' + data.operation;
+                    }
+                } else if(data.image && data.level) {
+                    // credible region of a probability map
+                    const layer = (typeof data.image === 'string') ? data.image : data.image.layer;
+                    self.areaDeg2 = self.view.wasm.addCredibleRegionMOC(self.mocParams, layer, data.level);
+                } else if(data.rings) {
+                    // polygon made of several rings
+                    const ra = data.rings.flatMap((ring) => ring.ra);
+                    const dec = data.rings.flatMap((ring) => ring.dec);
+                    const lengths = new Uint32Array(data.rings.map((ring) => ring.ra.length));
+                    const c = data.controlPoint || {};
+                    self.view.wasm.addMultiPolygonMOC(self.mocParams, ra, dec, lengths, c.ra, c.dec, data.depth);
+                } else if(data.shape) {
+                    const s = data;
+                    switch (s.shape) {
+                        case 'ellipse':
+                            self.view.wasm.addEllipseMOC(self.mocParams, s.ra, s.dec, s.a, s.b, s.pa || 0, s.depth);
+                            break;
+                        case 'box':
+                            self.view.wasm.addBoxMOC(self.mocParams, s.ra, s.dec, s.a, s.b, s.pa || 0, s.depth);
+                            break;
+                        case 'zone':
+                            self.view.wasm.addZoneMOC(self.mocParams, s.raMin, s.decMin, s.raMax, s.decMax, s.depth);
+                            break;
+                        case 'ring':
+                            self.view.wasm.addRingMOC(self.mocParams, s.ra, s.dec, s.rInt, s.rExt, s.depth);
+                            break;
+                        default:
+                            throw 'Unknown MOC shape ' + s.shape;
+                    }
                 } else if(data.ra && data.dec && data.radius) {
                     // circle
                     const c = data;
-                    self.view.wasm.addConeMOC(self.mocParams, c.ra, c.dec, c.radius);
+                    self.view.wasm.addConeMOC(self.mocParams, c.ra, c.dec, c.radius, c.depth);
                 } else if(data.ra && data.dec) {
                     // polygon
                     const p = data;
-                    self.view.wasm.addPolyMOC(self.mocParams, p.ra, p.dec);
+                    self.view.wasm.addPolyMOC(self.mocParams, p.ra, p.dec, p.depth);
                 } else {
                     // json moc
                     self.view.wasm.addJSONMoc(self.mocParams, data);
@@ -155,7 +228,7 @@ export let MOC = (function() {
                 }
 
                 // Cache the sky fraction
-                self.skyFrac = self.view.wasm.getMOCSkyFraction(this.mocParams);
+                self._updateSkyFraction();
 
                 // Add it to the view
                 self.view.mocs.push(self);
@@ -173,6 +246,14 @@ export let MOC = (function() {
             })
     };
 
+    MOC.prototype._updateSkyFraction = function() {
+        this.skyFrac = this.view.wasm.getMOCSkyFraction(this.mocParams);
+        if (this.areaDeg2 === undefined) {
+            // The whole sky covers 4 pi steradians
+            this.areaDeg2 = this.skyFrac * 4 * Math.PI * Math.pow(180 / Math.PI, 2);
+        }
+    };
+
     MOC.prototype.reportChange = function() {
         if (this.view) {
             // update the new moc params to the backend
@@ -206,6 +287,185 @@ export let MOC = (function() {
         this.reportChange();
     };
 
+    // Create the MOC resulting from a set operation between this MOC and other ones
+    // and add it to the view
+    MOC.prototype._setOperation = function(operation, others, options) {
+        const mocs = [this, ...others];
+        if (mocs.some((moc) => !moc.ready)) {
+            throw "The MOCs are not yet ready, either because they have not been downloaded yet or because they have not been added to the aladin instance."
+        }
+
+        const moc = new MOC(options);
+        moc.parse({operation: operation, mocs: mocs});
+        this.view.aladin.addMOC(moc);
+
+        return moc;
+    };
+
+    /**
+     * Add the union of this MOC with another one as a new MOC layer
+     *
+     * @memberof MOC
+     * @param {MOC} other - Another MOC added to the view
+     * @param {MOCOptions} [options] - Display options for the resulting MOC
+     * @returns {MOC} The resulting MOC
+     */
+    MOC.prototype.union = function(other, options) {
+        return this._setOperation('union', [other], options);
+    };
+
+    /**
+     * Add the intersection of this MOC with another one as a new MOC layer
+     *
+     * @memberof MOC
+     * @param {MOC} other - Another MOC added to the view
+     * @param {MOCOptions} [options] - Display options for the resulting MOC
+     * @returns {MOC} The resulting MOC
+     */
+    MOC.prototype.intersection = function(other, options) {
+        return this._setOperation('intersection', [other], options);
+    };
+
+    /**
+     * Add the part of this MOC not covered by another one as a new MOC layer
+     *
+     * @memberof MOC
+     * @param {MOC} other - Another MOC added to the view
+     * @param {MOCOptions} [options] - Display options for the resulting MOC
+     * @returns {MOC} The resulting MOC
+     */
+    MOC.prototype.difference = function(other, options) {
+        return this._setOperation('difference', [other], options);
+    };
+
+    /**
+     * Add the part of the sky covered either by this MOC or by another one, but not by both, as a new MOC layer
+     *
+     * @memberof MOC
+     * @param {MOC} other - Another MOC added to the view
+     * @param {MOCOptions} [options] - Display options for the resulting MOC
+     * @returns {MOC} The resulting MOC
+     */
+    MOC.prototype.symmetricDifference = function(other, options) {
+        return this._setOperation('symmetricDifference', [other], options);
+    };
+
+    /**
+     * Add the part of the sky not covered by this MOC as a new MOC layer
+     *
+     * @memberof MOC
+     * @param {MOCOptions} [options] - Display options for the resulting MOC
+     * @returns {MOC} The resulting MOC
+     */
+    MOC.prototype.complement = function(options) {
+        return this._setOperation('complement', [], options);
+    };
+
+    /**
+     * Compute the overlap between this MOC and another one without adding a new layer
+     *
+     * @memberof MOC
+     * @param {MOC} other - Another MOC added to the view
+     * @returns {Object} The sky fraction (<code>skyFraction</code>) and the area in square degrees (<code>area</code>)
+     * of the intersection, and the fractions of this MOC (<code>fractionOfFirst</code>) and of the other one
+     * (<code>fractionOfSecond</code>) it covers
+     */
+    MOC.prototype.overlap = function(other) {
+        if (!this.ready || !other.ready) {
+            throw "The MOCs are not yet ready, either because they have not been downloaded yet or because they have not been added to the aladin instance."
+        }
+
+        return this.view.wasm.getMOCOverlap(this.mocParams, other.mocParams);
+    };
+
+    /**
+     * Get a summary of the MOC
+     *
+     * @memberof MOC
+     * @returns {Object} The sky fraction (<code>skyFraction</code>), the area in square degrees (<code>area</code>),
+     * the max order (<code>maxOrder</code>) and the number of cells of each order (<code>numCellsPerOrder</code>) of the MOC.
+     * It also gives a cone (<code>boundingCone</code>, with <code>ra</code>, <code>dec</code> and <code>radius</code>)
+     * and a box (<code>boundingBox</code>, with <code>raMin</code>, <code>raMax</code>, <code>decMin</code> and <code>decMax</code>)
+     * containing the MOC, in degrees, both null for an empty MOC. <code>raMin</code> is negative if the box crosses the meridian 0.
+     * Finally, <code>components</code> lists the sky fraction and the area of each connected part of the MOC, by decreasing area.
+     */
+    MOC.prototype.summary = function() {
+        if (!this.ready) {
+            throw this.name + " is not yet ready, either because it has not been downloaded yet or because it has not been added to the aladin instance."
+        }
+
+        return this.view.wasm.getMOCSummary(this.mocParams);
+    };
+
+    /**
+     * Serialize the MOC, e.g. to download it or to send it to a VizieR or TAP service
+     *
+     * @memberof MOC
+     * @param {string} [format='fits'] - One of <code>'fits'</code> (IVOA MOC 2.0 FITS), <code>'json'</code> (Aladin JSON format, as accepted by {@link A.MOCFromJSON})
+     * or <code>'ascii'</code> (IVOA MOC 2.0 ASCII)
+     * @param {number} [depth] - The HEALPix order of the serialized MOC. Defaults to the order of the MOC.
+     * @returns {Uint8Array|Object|string} The FITS bytes, the JSON object or the ASCII string
+     */
+    MOC.prototype.serialize = function(format, depth) {
+        if (!this.ready) {
+            throw this.name + " is not yet ready, either because it has not been downloaded yet or because it has not been added to the aladin instance."
+        }
+
+        format = (format || 'fits').toLowerCase();
+        switch (format) {
+            case 'fits':
+                return this.view.wasm.getMOCAsFITS(this.mocParams, depth);
+            case 'json':
+                return this.view.wasm.getMOCAsJSON(this.mocParams, depth);
+            case 'ascii':
+                return this.view.wasm.getMOCAsASCII(this.mocParams, depth);
+            default:
+                throw "Unknown MOC serialization format: " + format + ". Must be 'fits', 'json' or 'ascii'";
+        }
+    };
+
+    /**
+     * Get the time interval covered by a T-MOC or a ST-MOC
+     *
+     * @memberof MOC
+     * @returns {number[]|null} The julian days of the start and of the end of the interval, null if the MOC is empty
+     * @throws If the MOC is a spatial MOC
+     */
+    MOC.prototype.getTimeRange = function() {
+        if (!this.ready) {
+            throw this.name + " is not yet ready, either because it has not been downloaded yet or because it has not been added to the aladin instance."
+        }
+
+        const range = this.view.wasm.getMOCTimeRange(this.mocParams);
+        return range ? Array.from(range) : null;
+    };
+
+    /**
+     * Display the spatial coverage of a T-MOC or a ST-MOC observed during a time interval
+     *
+     * A T-MOC covers the whole sky during its time ranges.
+     *
+     * @memberof MOC
+     * @param {number|Date} start - The start of the interval, given as a julian day or a date
+     * @param {number|Date} end - The end of the interval, given as a julian day or a date
+     * @throws If the MOC is a spatial MOC
+     */
+    MOC.prototype.setTimeRange = function(start, end) {
+        if (!this.ready) {
+            throw this.name + " is not yet ready, either because it has not been downloaded yet or because it has not been added to the aladin instance."
+        }
+
+        // the unix epoch is JD 2440587.5
+        const toJD = (t) => (t instanceof Date) ? t.getTime() / 86400000 + 2440587.5 : t;
+        this.view.wasm.setMOCTimeRange(this.mocParams, toJD(start), toJD(end));
+
+        // the covered area depends on the interval
+        this.areaDeg2 = undefined;
+        this._updateSkyFraction();
+
+        this.view.requestRedraw();
+    };
+
     // Tests whether a given (ra, dec) point on the sky is within the current MOC object
     //
     // returns true if point is contained, false otherwise
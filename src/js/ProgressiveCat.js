@@ -441,7 +441,7 @@ export let ProgressiveCat = (function() {
             let xy = this.view.wasm.worldToScreenVec(ra, dec);
             let self = this;
             sources.forEach(function(s, idx) {
-                if (xy[2*idx] && xy[2*idx + 1]) {
+                if (Number.isFinite(xy[2*idx]) && Number.isFinite(xy[2*idx + 1])) {
                     if (!self.filterFn || self.filterFn(s)) {
                         s.x = xy[2*idx];
                         s.y = xy[2*idx + 1];
@@ -4,6 +4,7 @@ import CatalogAitoffVS from '../glsl/webgl1/catalogs/aitoff.vert';
 import CatalogMercatVS from '../glsl/webgl1/catalogs/mercator.vert';
 import CatalogArcVS from '../glsl/webgl1/catalogs/arc.vert';
 import CatalogTanVS from '../glsl/webgl1/catalogs/tan.vert';
+import CatalogClipVS from '../glsl/webgl1/catalogs/clip.vert';
 import CatalogMollVS from '../glsl/webgl1/catalogs/mollweide.vert';
 import CatalogOrthoVS from '../glsl/webgl1/catalogs/ortho.vert';
 import CatalogOrthoFS from '../glsl/webgl1/catalogs/ortho.frag';
@@ -49,6 +50,10 @@ let shaders = [
         id: "CatalogTanVS",
         content: CatalogTanVS,
     },
+    {
+        id: "CatalogClipVS",
+        content: CatalogClipVS,
+    },
     {
         id: "CatalogMollVS",
         content: CatalogMollVS,
@@ -4,6 +4,7 @@ import CatalogAitoffVS from '../glsl/webgl2/catalogs/aitoff.vert';
 import CatalogMercatVS from '../glsl/webgl2/catalogs/mercator.vert';
 import CatalogArcVS from '../glsl/webgl2/catalogs/arc.vert';
 import CatalogTanVS from '../glsl/webgl2/catalogs/tan.vert';
+import CatalogClipVS from '../glsl/webgl2/catalogs/clip.vert';
 import CatalogMollVS from '../glsl/webgl2/catalogs/mollweide.vert';
 import CatalogHEALPixVS from '../glsl/webgl2/catalogs/healpix.vert';
 import CatalogOrthoVS from '../glsl/webgl2/catalogs/ortho.vert';
@@ -67,6 +68,10 @@ let shaders = [
         id: "CatalogTanVS",
         content: CatalogTanVS,
     },
+    {
+        id: "CatalogClipVS",
+        content: CatalogClipVS,
+    },
     {
         id: "CatalogMollVS",
         content: CatalogMollVS,
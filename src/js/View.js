@@ -31,7 +31,6 @@
 
 import { Aladin } from "./Aladin.js";
 import A from "./A.js";
-import { HealpixGrid } from "./HealpixGrid.js";
 import { ProjectionEnum } from "./ProjectionEnum.js";
 import { Utils } from "./Utils";
 import { GenericPointer } from "./GenericPointer.js";
@@ -79,6 +78,19 @@ export let View = (function () {
 
                 callback(self.wasm);
             });
+
+            // The tiles of a HiPS are fetched from another of its mirrors after repeated failures
+            this.wasm.setCallbackHiPSMirrorChanged((creatorDid, url) => {
+                self.imageLayers.forEach((imageLayer) => {
+                    if (imageLayer.creatorDid === creatorDid) {
+                        imageLayer.url = url;
+                        // save the new url to the cache
+                        imageLayer._saveInCache();
+                    }
+                });
+
+                ALEvent.HIPS_MIRROR_CHANGED.dispatchedTo(self.aladinDiv, { creatorDid, url });
+            });
         } catch (e) {
             // For browsers not supporting WebGL2:
             // 1. Print the original exception message in the console
@@ -161,7 +173,6 @@ export let View = (function () {
 
         this.minFOV = this.maxFOV = null; // by default, no restriction
 
-        this.healpixGrid = new HealpixGrid();
         this.then = Date.now();
 
         var lon, lat;
@@ -1315,33 +1326,8 @@ export let View = (function () {
             }
         }
 
-        // Redraw HEALPix grid
-        if (this.displayHpxGrid) {
-            if (!this.catalogCanvasCleared) {
-                ctx.clearRect(0, 0, this.width, this.height);
-                this.catalogCanvasCleared = true;
-            }
-
-            var cornersXYViewMapAllsky = this.getVisibleCells(3);
-            var cornersXYViewMapHighres = null;
-            if (this.curNorder >= 3) {
-                if (this.curNorder == 3) {
-                    cornersXYViewMapHighres = cornersXYViewMapAllsky;
-                }
-                else {
-                    cornersXYViewMapHighres = this.getVisibleCells(this.curNorder);
-                }
-            }
-            if (cornersXYViewMapHighres && this.curNorder > 3) {
-                this.healpixGrid.redraw(ctx, cornersXYViewMapHighres, this.fov, this.curNorder);
-            }
-            else {
-                this.healpixGrid.redraw(ctx, cornersXYViewMapAllsky, this.fov, 3);
-            }
-        }
-
         // display grid labels
-        if (this.gridCfg.enabled && this.gridCfg.showLabels) {
+        if ((this.gridCfg.enabled && this.gridCfg.showLabels) || this.displayHpxGrid) {
             if (!this.catalogCanvasCleared) {
                 ctx.clearRect(0, 0, this.width, this.height);
                 this.catalogCanvasCleared = true;
@@ -1377,7 +1363,9 @@ export let View = (function () {
         let [lon, lat] = this.aladin.pix2world(this.cx, this.cy, 'icrs');
 
         var radius = this.fov * 0.5 * this.ratio;
-        this.wasm.queryDisc(norder, lon, lat, radius).forEach(x => pixList.push(Number(x)));
+        const deg2rad = Math.PI / 180.0;
+        Aladin.wasmLibs.core.HEALPixQueryCone(1 << norder, lon * deg2rad, lat * deg2rad, radius * deg2rad)
+            .forEach(x => pixList.push(Number(x)));
 
         return pixList;
     };
@@ -1523,6 +1511,17 @@ export let View = (function () {
         return this.gridCfg;
     }
 
+    View.prototype.setTissotOptions = function (options) {
+        this.tissotCfg = {...this.tissotCfg, ...options};
+        this.wasm.setTissotOptions(this.tissotCfg);
+
+        this.requestRedraw();
+    };
+
+    View.prototype.getDistortion = function (x, y) {
+        return this.wasm.getDistortion(x, y);
+    };
+
     View.prototype.updateZoomState = function () {
         // Get the new zoom values from the backend
         let fov = this.wasm.getFieldOfView();
@@ -1779,23 +1778,41 @@ export let View = (function () {
         this.needRedraw = true;
     };
 
-    View.prototype.setProjection = function (projName) {
+    View.prototype.setProjection = function (projName, params) {
         if (!ProjectionEnum[projName]) {
             console.warn(projName + " is not a valid projection.")
             projName = 'SIN'
         }
 
-        if (this.projection && this.projection.id === ProjectionEnum[projName].id) {
+        params = params || {};
+        if (this.projection && this.projection.id === ProjectionEnum[projName].id &&
+            JSON.stringify(this.projectionParams) === JSON.stringify(params)) {
             return;
         }
 
+        // Change the projection here
+        this.wasm.setProjection(projName, params);
+
         this.projection = ProjectionEnum[projName];
+        this.projectionParams = params;
+        this.updateZoomState();
 
-        // Change the projection here
-        this.wasm.setProjection(projName);
+        this.requestRedraw();
+    };
+
+    View.prototype.setNativeWCS = function (layer) {
+        const wcs = this.wasm.setNativeWCS(layer);
+
+        // The backend has changed the projection, the frame, the center and the zoom,
+        // synchronize the view with it
+        this.projection = ProjectionEnum[wcs.projection];
+        this.projectionParams = wcs.params;
+        this.changeFrame(CooFrameEnum.fromString(wcs.frame, this.cooFrame));
         this.updateZoomState();
 
         this.requestRedraw();
+
+        return wcs;
     };
 
     View.prototype.changeFrame = function (cooFrame) {
@@ -1808,6 +1825,21 @@ export let View = (function () {
         else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.J2000) {
             this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.ICRS);
         }
+        else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.ECL) {
+            this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.ECL);
+        }
+        else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.SGAL) {
+            this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.SGAL);
+        }
+        else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.FK5) {
+            this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.FK5J2000);
+        }
+        else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.FK4) {
+            this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.FK4B1950);
+        }
+        else if (this.cooFrame.system == CooFrameEnum.SYSTEMS.ALTAZ) {
+            this.wasm.setCooSystem(Aladin.wasmLibs.core.CooSystem.ALTAZ);
+        }
 
         // Set the grid label format
         if (this.cooFrame.label == "J2000d") {
@@ -1832,7 +1864,13 @@ export let View = (function () {
     }
 
     View.prototype.showHealpixGrid = function (show) {
-        this.displayHpxGrid = show;
+        this.setHEALPixGridOptions({enabled: show});
+    };
+
+    View.prototype.setHEALPixGridOptions = function (options) {
+        this.hpxGridCfg = {...this.hpxGridCfg, ...options};
+        this.wasm.setHEALPixGridOptions(this.hpxGridCfg);
+        this.displayHpxGrid = this.hpxGridCfg.enabled === true;
 
         if (!this.displayHpxGrid) {
             this.mustClearCatalog = true;
@@ -55,6 +55,8 @@ export class ALEvent {
 
   static HIPS_CACHE_UPDATED = new ALEvent("AL:HiPSCache.updated");
 
+  static HIPS_MIRROR_CHANGED = new ALEvent("AL:HiPSMirror.changed");
+
   static HIPS_LAYER_CHANGED  = new ALEvent("AL:HiPSLayer.changed");
 
   static GRAPHIC_OVERLAY_LAYER_ADDED  = new ALEvent("AL:GraphicOverlayLayer.added");
@@ -66,7 +66,7 @@ export let Polyline = (function() {
         }
     }
 
-    /*function _isAcrossCollignonZoneForHpxProjection(line, view) {
+    function _isAcrossCollignonZoneForHpxProjection(line, view) {
         const [x1, y1] = view.wasm.screenToClip(line.x1, line.y1);
         const [x2, y2] = view.wasm.screenToClip(line.x2, line.y2);
 
@@ -89,7 +89,7 @@ export let Polyline = (function() {
         }
 
         return false;
-    }*/
+    }
 
     /**
      * Constructor function for creating a new polyline.
@@ -334,7 +334,7 @@ export let Polyline = (function() {
                     return true;
                 };
             }
-        /*} else if (view.projection === ProjectionEnum.HPX) {
+        } else if (view.projection === ProjectionEnum.HPX) {
             drawLine = (v0, v1) => {
                 const line = new Line(v0.x, v0.y, v1.x, v1.y);
 
@@ -374,7 +374,7 @@ export let Polyline = (function() {
                         return false;
                     }
                 };
-            }*/
+            }
         } else {
             drawLine = (v0, v1) => {
                 const l = {x1: v0.x, y1: v0.y, x2: v1.x, y2: v1.y};